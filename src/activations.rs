@@ -1,35 +1,1113 @@
+use super::math::{cos, exp, ln_1p, sin, sqrt, tanh};
+use super::network::ParamSets;
+use super::Float;
+
+
+/// Fast approximation of `e^x` for [f64], used by the built-in activations when the `fast-exp` feature is enabled. 
+/// 
+/// Splits `x` into `k * ln(2) + r` with `|r| <= ln(2) / 2`, evaluates a degree 5 polynomial for `e^r` and scales 
+/// it by `2^k` directly through the exponent bits, avoiding the slower general purpose `libm` routine on soft-float targets. 
+/// 
+/// The relative error against `libm` is below `4e-6` for all finite inputs, values over `709` return infinity, 
+/// values under `-708` return `0` and NaN is propagated. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::activations::fast_exp;
+/// 
+/// for i in -7_000..=7_000 {
+///     let x = i as f64 / 10.0;
+///     let error = (fast_exp(x) - x.exp()).abs() / x.exp();
+///     assert!(error < 4e-6, "relative error {} at {}", error, x);
+/// }
+/// assert_eq!(fast_exp(0.0), 1.0);
+/// assert_eq!(fast_exp(1_000.0), f64::INFINITY);
+/// assert_eq!(fast_exp(-1_000.0), 0.0);
+/// assert!(fast_exp(f64::NAN).is_nan());
+/// ```
 #[cfg(not(feature = "f32"))]
-use core::f64::consts::E;
-#[cfg(not(feature = "f32"))]
-use libm::pow;
+pub fn fast_exp(x: f64) -> f64 {
+    use core::f64::consts::{LN_2, LOG2_E};
 
-#[cfg(feature = "f32")]
-use core::f32::consts::E;
-// Micromath works better on smaller 8 bit MCUs where we would be using 32 bits  
-#[cfg(feature = "f32")]
-use micromath::F32Ext; 
+    if x > 709.0 {
+        return f64::INFINITY;
+    }
+    if x < -708.0 {
+        return 0.0;
+    }
 
-use super::Float;
+    let k = (x * LOG2_E + if x < 0.0 { -0.5 } else { 0.5 }) as i64;
+    let r = x - k as f64 * LN_2;
+    let poly = 1.0 + r * (1.0 + r / 2.0 * (1.0 + r / 3.0 * (1.0 + r / 4.0 * (1.0 + r / 5.0))));
 
+    poly * f64::from_bits(((k + 1023) as u64) << 52)
+}
+
+/// Numerically safe `ln(1 + e^x)`, computed as `max(x, 0) + ln(1 + e^-|x|)`. 
+fn softplus(x: Float) -> Float {
+    let abs = if x < 0.0 { -x } else { x };
+    let max = if x > 0.0 { x } else { 0.0 };
+    max + ln_1p(exp(-abs))
+}
 
 /// Helper container type holding the closures for the activation function and the derivative. 
 /// 
-/// Used for forward and backwards propagation in the neural network. 
+/// Used for forward and backwards propagation in the neural network, the derivative receives the same 
+/// pre-activation value the function does. 
 pub struct Activation<'a> {
     pub function: &'a dyn Fn(Float) -> Float,
     pub derivative: &'a dyn Fn(Float) -> Float
 }
 
+/// Generic behaviour for a scalar activation function and its derivative, dispatched statically. 
+/// 
+/// The built-in activations are zero sized types implementing this trait, letting the compiler inline them 
+/// into [predict](crate::network::Network::predict) and [train](crate::network::Network::train) 
+/// rather than calling through a `&dyn Fn` as [Activation] does. 
+pub trait ActivationFn {
+    /// The activation function applied to a single value. 
+    fn function(&self, x: Float) -> Float;
+
+    /// The derivative of the activation function with respect to its input, receives the same pre-activation value as [function](ActivationFn::function). 
+    fn derivative(&self, x: Float) -> Float;
+
+    /// A short name for the activation, such as `"sigmoid"`, shown in a network's summary, `"custom"` by default. 
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+}
+
+impl<'a> ActivationFn for Activation<'a> {
+    fn function(&self, x: Float) -> Float {
+        (self.function)(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        (self.derivative)(x)
+    }
+}
+
+/// Activation function built from plain function pointers rather than closures. 
+/// 
+/// Unlike [Activation] this is `Send`, `Sync` and `Copy` so it can be kept in a `static`, placed in flash and 
+/// referenced from interrupt handlers, non-capturing closures coerce to the pointers. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::FnActivation;
+/// use mynn::network::Network;
+/// 
+/// static STEP_RELU: FnActivation = FnActivation::new(
+///     |x| if x > 0.0 { x } else { 0.0 },
+///     |x| if x > 0.0 { 1.0 } else { 0.0 }
+/// );
+/// 
+/// let mut network = make_network!(2, 3, 1);
+/// network.predict([1.0, 0.0], &STEP_RELU);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnActivation {
+    pub function: fn(Float) -> Float,
+    pub derivative: fn(Float) -> Float
+}
+
+impl FnActivation {
+    /// Instantiates a new activation from the function and derivative pointers, usable in `const` and `static` items. 
+    pub const fn new(function: fn(Float) -> Float, derivative: fn(Float) -> Float) -> FnActivation {
+        FnActivation {
+            function,
+            derivative
+        }
+    }
+}
+
+impl ActivationFn for FnActivation {
+    fn function(&self, x: Float) -> Float {
+        (self.function)(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        (self.derivative)(x)
+    }
+}
+
+/// Generic behaviour for applying an activation function across all the values in a layer at once. 
+/// 
+/// Implemented element-wise for every [ActivationFn], and directly by [VectorActivation] and [Softmax] 
+/// which need to see the entire layer. 
+pub trait LayerActivation {
+    /// Maps the values of a layer through the activation function in place. 
+    fn activate(&self, values: &mut [Float]);
+
+    /// Maps the pre-activation values of a layer to the derivatives of the activation (the diagonal of the Jacobian) in place. 
+    fn derive(&self, values: &mut [Float]);
+
+    /// Maps the values of the network's output layer through the activation function in place, the same as 
+    /// [activate](LayerActivation::activate) unless overridden as in [Split]. 
+    fn activate_output(&self, values: &mut [Float]) {
+        self.activate(values)
+    }
+
+    /// Maps the pre-activation values of the network's output layer to their derivatives in place, the same as 
+    /// [derive](LayerActivation::derive) unless overridden as in [Split]. 
+    fn derive_output(&self, values: &mut [Float]) {
+        self.derive(values)
+    }
+
+    /// Maps the errors with respect to a layer's activated values to the errors with respect to its pre-activation 
+    /// values in place, multiplying each by its [derivative](LayerActivation::derive) unless overridden as in 
+    /// [Softmax] where every output depends on every input. 
+    /// 
+    /// # Parameters 
+    /// * `values` The pre-activation values of the layer, overwritten. 
+    /// * `errors` The errors with respect to the layer's activated values. 
+    fn derive_errors(&self, values: &mut [Float], errors: &mut [Float]) {
+        self.derive(values);
+        multiply_errors(values, errors);
+    }
+
+    /// Maps the errors of the network's output layer as [derive_errors](LayerActivation::derive_errors), the same 
+    /// unless overridden as in [Split]. 
+    fn derive_errors_output(&self, values: &mut [Float], errors: &mut [Float]) {
+        self.derive_errors(values, errors)
+    }
+
+    /// A short name for the activation, see [ActivationFn::name], `"custom"` by default. 
+    fn name(&self) -> &'static str {
+        "custom"
+    }
+
+    /// The bound of the uniform distribution the weights of a dense layer owning this activation are drawn from 
+    /// when [randomized](crate::network::Network::randomize), `None` for the default of `1`, as overridden by [Siren]. 
+    /// 
+    /// # Parameters 
+    /// * `fan_in` The number of values the layer is fed. 
+    fn weight_limit(&self, _fan_in: usize) -> Option<Float> {
+        None
+    }
+}
+
+/// Multiplies each error by the derivative at the same index. 
+fn multiply_errors(derivatives: &[Float], errors: &mut [Float]) {
+    for (error, derivative) in errors.iter_mut().zip(derivatives) {
+        *error *= *derivative;
+    }
+}
+
+/// Pairs a hidden layer activation function with a distinct one for the output layer, for example ReLU hidden 
+/// layers with an identity output for regression. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{Split, RELU, IDENTITY};
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let act = Split::new(RELU, IDENTITY);
+/// let mut network = make_network!(2, 3, 1);
+/// 
+/// network.train(0.01, [[1.0, 2.0]], [[3.0]], 100, &act, &MSE);
+/// network.predict([1.0, 2.0], &act);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Split<H: LayerActivation, O: LayerActivation> {
+    /// The activation function used by every layer but the last. 
+    pub hidden: H,
+    /// The activation function used by the output layer. 
+    pub output: O
+}
+
+impl<H: LayerActivation, O: LayerActivation> Split<H, O> {
+    /// Instantiates a new pair from the hidden and output activation functions. 
+    pub const fn new(hidden: H, output: O) -> Split<H, O> {
+        Split {
+            hidden,
+            output
+        }
+    }
+}
+
+impl<H: LayerActivation, O: LayerActivation> LayerActivation for Split<H, O> {
+    fn activate(&self, values: &mut [Float]) {
+        self.hidden.activate(values)
+    }
+
+    fn derive(&self, values: &mut [Float]) {
+        self.hidden.derive(values)
+    }
+
+    fn activate_output(&self, values: &mut [Float]) {
+        self.output.activate(values)
+    }
+
+    fn derive_output(&self, values: &mut [Float]) {
+        self.output.derive(values)
+    }
+
+    fn derive_errors(&self, values: &mut [Float], errors: &mut [Float]) {
+        self.hidden.derive_errors(values, errors)
+    }
+
+    fn derive_errors_output(&self, values: &mut [Float], errors: &mut [Float]) {
+        self.output.derive_errors(values, errors)
+    }
+}
+
+/// Wraps an activation so the output layer of a sub-chain uses the hidden activation, used for the inner 
+/// layers of a [ResidualLayer](crate::network::ResidualLayer). 
+pub(crate) struct Hidden<'a, A: LayerActivation + ?Sized>(pub(crate) &'a A);
+
+impl<'a, A: LayerActivation + ?Sized> LayerActivation for Hidden<'a, A> {
+    fn activate(&self, values: &mut [Float]) {
+        self.0.activate(values)
+    }
+
+    fn derive(&self, values: &mut [Float]) {
+        self.0.derive(values)
+    }
+
+    fn derive_errors(&self, values: &mut [Float], errors: &mut [Float]) {
+        self.0.derive_errors(values, errors)
+    }
+
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+}
+
+impl<T: ActivationFn> LayerActivation for T {
+    fn activate(&self, values: &mut [Float]) {
+        for value in values.iter_mut() {
+            *value = self.function(*value);
+        }
+    }
+
+    fn derive(&self, values: &mut [Float]) {
+        for value in values.iter_mut() {
+            *value = self.derivative(*value);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        ActivationFn::name(self)
+    }
+}
+
+/// Placeholder activation for a layer that doesn't own an activation function, it uses the one passed to 
+/// [predict](crate::network::Network::predict) and [train](crate::network::Network::train) instead. 
+/// 
+/// This is the default for [ProcessLayer](crate::network::ProcessLayer). 
+#[derive(Clone, Copy, Default)]
+pub struct Inherit;
+
+/// Generic behaviour for the activation stored in a layer, either applying its own [LayerActivation] or 
+/// falling back to the one passed down the network when it's [Inherit]. 
+/// 
+/// `output` is set when the layer is the network's output layer, for the fallback to pick its output activation. 
+pub trait StoredActivation {
+    /// Maps the values of a layer through this activation, or `fallback` if there is none, in place. 
+    fn activate_or<F: LayerActivation + ?Sized>(&self, fallback: &F, output: bool, values: &mut [Float]);
+
+    /// Maps the pre-activation values of a layer to the derivatives of this activation, or `fallback` if there is none, in place. 
+    fn derive_or<F: LayerActivation + ?Sized>(&self, fallback: &F, output: bool, values: &mut [Float]);
+
+    /// Maps the errors with respect to a layer's activated values to those of its pre-activation values with this 
+    /// activation, or `fallback` if there is none, in place, see [LayerActivation::derive_errors]. 
+    fn derive_errors_or<F: LayerActivation + ?Sized>(&self, fallback: &F, output: bool, values: &mut [Float], errors: &mut [Float]) {
+        self.derive_or(fallback, output, values);
+        multiply_errors(values, errors);
+    }
+
+    /// The number of learnable parameters of this activation, trained along with those of the layer owning it, `0` 
+    /// by default. 
+    const PARAMS: usize = 0;
+
+    /// Plain copy of the learnable parameters of this activation, or of a value kept for each of them such as its 
+    /// gradient, held in the layer's [Snapshot](crate::network::Layer::Snapshot), `[P; 0]` for none. 
+    type Params<P: Copy>: ParamSets<P> + AsRef<[P]> + AsMut<[P]>;
+
+    /// The learnable parameters of this activation, empty by default. 
+    fn params(&self) -> &[Float] {
+        &[]
+    }
+
+    /// The learnable parameters of this activation, given mutably, empty by default. 
+    fn params_mut(&mut self) -> &mut [Float] {
+        &mut []
+    }
+
+    /// Adds the gradient of the loss with respect to each learnable parameter of this activation to `gradients`, 
+    /// does nothing by default. 
+    /// 
+    /// # Parameters 
+    /// * `pre_activations` The pre-activation values of the layer from the last feed forward. 
+    /// * `errors` The errors with respect to the layer's activated outputs (the negative loss gradient). 
+    /// * `gradients` The gradients gathered so far, one for each learnable parameter. 
+    fn gather_gradients(&self, _pre_activations: &[Float], _errors: &[Float], _gradients: &mut [Float]) {}
+
+    /// Copies the learnable parameters of this activation into a [StoredActivation::Params]. 
+    fn save_params(&self) -> Self::Params<Float> {
+        let mut params = Self::Params::filled(0.0);
+        params.as_mut().copy_from_slice(self.params());
+        params
+    }
+
+    /// Copies the learnable parameters of this activation back from a [StoredActivation::Params]. 
+    fn load_params(&mut self, params: &Self::Params<Float>) {
+        self.params_mut().copy_from_slice(params.as_ref());
+    }
+
+    /// The name of this activation, see [LayerActivation::name], or `None` if the fallback is used, the default. 
+    fn name(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// The bound the layer's weights are drawn within, see [LayerActivation::weight_limit], `None` if the fallback 
+    /// is used, the default. 
+    fn weight_limit(&self, _fan_in: usize) -> Option<Float> {
+        None
+    }
+}
+
+impl StoredActivation for Inherit {
+    fn activate_or<F: LayerActivation + ?Sized>(&self, fallback: &F, output: bool, values: &mut [Float]) {
+        if output { fallback.activate_output(values) } else { fallback.activate(values) }
+    }
+
+    fn derive_or<F: LayerActivation + ?Sized>(&self, fallback: &F, output: bool, values: &mut [Float]) {
+        if output { fallback.derive_output(values) } else { fallback.derive(values) }
+    }
+
+    fn derive_errors_or<F: LayerActivation + ?Sized>(&self, fallback: &F, output: bool, values: &mut [Float], errors: &mut [Float]) {
+        if output { fallback.derive_errors_output(values, errors) } else { fallback.derive_errors(values, errors) }
+    }
+
+    type Params<P: Copy> = [P; 0];
+}
+
+impl<T: LayerActivation> StoredActivation for T {
+    fn activate_or<F: LayerActivation + ?Sized>(&self, _fallback: &F, _output: bool, values: &mut [Float]) {
+        self.activate(values)
+    }
+
+    fn derive_or<F: LayerActivation + ?Sized>(&self, _fallback: &F, _output: bool, values: &mut [Float]) {
+        self.derive(values)
+    }
+
+    fn derive_errors_or<F: LayerActivation + ?Sized>(&self, _fallback: &F, _output: bool, values: &mut [Float], errors: &mut [Float]) {
+        self.derive_errors(values, errors)
+    }
+
+    type Params<P: Copy> = [P; 0];
+
+    fn name(&self) -> Option<&'static str> {
+        Some(LayerActivation::name(self))
+    }
+
+    fn weight_limit(&self, fan_in: usize) -> Option<Float> {
+        LayerActivation::weight_limit(self, fan_in)
+    }
+}
+
+/// Helper container type holding the closures for an activation function that needs every value in a layer, and its derivative. 
+/// 
+/// Both closures map the slice of pre-activation values in place, the derivative returns the diagonal of the Jacobian. 
+pub struct VectorActivation<'a> {
+    pub function: &'a dyn Fn(&mut [Float]),
+    pub derivative: &'a dyn Fn(&mut [Float])
+}
+
+impl<'a> LayerActivation for VectorActivation<'a> {
+    fn activate(&self, values: &mut [Float]) {
+        (self.function)(values)
+    }
+
+    fn derive(&self, values: &mut [Float]) {
+        (self.derivative)(values)
+    }
+}
+
+/// Container owning the closures of a parameterised activation function, such as one returned by [leaky_relu]. 
+/// 
+/// Closures capturing parameters can't be referenced from a constant, so this keeps them alive, it can be 
+/// passed to the network directly or lent out as an [Activation]. 
+pub struct OwnedActivation<F: Fn(Float) -> Float, D: Fn(Float) -> Float> {
+    pub function: F,
+    pub derivative: D
+}
+
+impl<F: Fn(Float) -> Float, D: Fn(Float) -> Float> ActivationFn for OwnedActivation<F, D> {
+    fn function(&self, x: Float) -> Float {
+        (self.function)(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        (self.derivative)(x)
+    }
+}
+
+impl<F: Fn(Float) -> Float, D: Fn(Float) -> Float> OwnedActivation<F, D> {
+    /// Borrows the owned closures as an [Activation] to be passed to the network. 
+    pub fn activation(&self) -> Activation<'_> {
+        Activation {
+            function: &self.function,
+            derivative: &self.derivative
+        }
+    }
+}
+
 /// Sigmoid activation function, used a lot in the examples and tests. 
-#[cfg(not(feature = "f32"))]
-pub const SIGMOID: Activation = Activation {
-    function: &|x| 1.0 / (1.0 + pow(E, -x)),
-    derivative: &|x| x * (1.0 - x)
-};
+#[derive(Clone, Copy)]
+pub struct Sigmoid;
+
+impl ActivationFn for Sigmoid {
+    fn function(&self, x: Float) -> Float {
+        1.0 / (1.0 + exp(-x))
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let s = self.function(x);
+        s * (1.0 - s)
+    }
+
+    fn name(&self) -> &'static str {
+        "sigmoid"
+    }
+}
 
 /// Sigmoid activation function, used a lot in the examples and tests. 
-#[cfg(feature = "f32")]
-pub const SIGMOID: Activation = Activation {
-    function: &|x| 1.0 / (1.0 + E.powf(-x)),
-    derivative: &|x| x * (1.0 - x)
-};
+pub const SIGMOID: Sigmoid = Sigmoid;
+
+/// Rectified linear unit activation function, outputs `x` when positive and `0` otherwise. 
+/// 
+/// The gradient at exactly zero is taken as `0`. 
+#[derive(Clone, Copy)]
+pub struct Relu;
+
+impl ActivationFn for Relu {
+    fn function(&self, x: Float) -> Float {
+        if x > 0.0 { x } else { 0.0 }
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        if x > 0.0 { 1.0 } else { 0.0 }
+    }
+
+    fn name(&self) -> &'static str {
+        "relu"
+    }
+}
+
+/// Rectified linear unit activation function, see [Relu]. 
+pub const RELU: Relu = Relu;
+
+/// Hyperbolic tangent activation function, zero-centred alternative to [Sigmoid] ranging from `-1` to `1`. 
+#[derive(Clone, Copy)]
+pub struct Tanh;
+
+impl ActivationFn for Tanh {
+    fn function(&self, x: Float) -> Float {
+        tanh(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let t = tanh(x);
+        1.0 - t * t
+    }
+
+    fn name(&self) -> &'static str {
+        "tanh"
+    }
+}
+
+/// Hyperbolic tangent activation function, see [Tanh]. 
+pub const TANH: Tanh = Tanh;
+
+/// Leaky rectified linear unit activation function, behaves like [Relu] but negative inputs are scaled by `alpha` rather than zeroed. 
+/// 
+/// # Parameters 
+/// * `alpha` The slope used for negative inputs, typically a small value such as `0.01`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::leaky_relu;
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let leaky = leaky_relu(0.01);
+/// let mut network = make_network!(2, 3, 1);
+/// 
+/// network.train(0.1, [[0.0, 1.0]], [[1.0]], 10, &leaky, &MSE);
+/// ```
+pub fn leaky_relu(alpha: Float) -> OwnedActivation<impl Fn(Float) -> Float, impl Fn(Float) -> Float> {
+    OwnedActivation {
+        function: move |x| if x > 0.0 { x } else { alpha * x },
+        derivative: move |x| if x > 0.0 { 1.0 } else { alpha }
+    }
+}
+
+/// Parametric rectified linear unit activation function, like [leaky_relu] but the negative slope `alpha` is a 
+/// parameter of the layer owning it, trained along with the layer's weights. 
+/// 
+/// Owned by a layer so each layer learns its own slope, [leaky_relu] gives a fixed slope to pass to 
+/// [train](crate::network::Network::train). 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{PRelu, SIGMOID};
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let mut network = make_network!(2, 3 => PRelu::new(0.25), 1 => SIGMOID).with_seed(1);
+/// network.train(0.1, [[-1.0, -1.0], [1.0, 1.0]], [[1.0], [0.0]], 100, &SIGMOID, &MSE);
+/// 
+/// // The slope is saved, checked and updated along with the weights and biases. 
+/// assert_eq!(network.snapshot().activation, [network.activation.alpha]);
+/// let max = network.grad_check([-1.0, -1.0], [1.0], &SIGMOID, &MSE, 1e-5, &mut [0.0; 2]);
+/// #[cfg(not(feature = "f32"))]
+/// assert!(max < 1e-4);
+/// println!("learnt slope: {}", network.activation.alpha);
+/// ```
+#[derive(Clone, Copy)]
+pub struct PRelu {
+    /// The slope used for negative inputs. 
+    pub alpha: Float
+}
+
+impl PRelu {
+    /// Instantiates a new parametric ReLU with the initial negative slope, commonly `0.25`. 
+    pub const fn new(alpha: Float) -> PRelu {
+        PRelu {
+            alpha
+        }
+    }
+}
+
+impl StoredActivation for PRelu {
+    fn activate_or<F: LayerActivation + ?Sized>(&self, _fallback: &F, _output: bool, values: &mut [Float]) {
+        for value in values.iter_mut() {
+            if *value < 0.0 {
+                *value *= self.alpha;
+            }
+        }
+    }
+
+    fn derive_or<F: LayerActivation + ?Sized>(&self, _fallback: &F, _output: bool, values: &mut [Float]) {
+        for value in values.iter_mut() {
+            *value = if *value > 0.0 { 1.0 } else { self.alpha };
+        }
+    }
+
+    const PARAMS: usize = 1;
+
+    type Params<P: Copy> = [P; 1];
+
+    fn params(&self) -> &[Float] {
+        core::slice::from_ref(&self.alpha)
+    }
+
+    fn params_mut(&mut self) -> &mut [Float] {
+        core::slice::from_mut(&mut self.alpha)
+    }
+
+    fn gather_gradients(&self, pre_activations: &[Float], errors: &[Float], gradients: &mut [Float]) {
+        for (&x, &error) in pre_activations.iter().zip(errors) {
+            if x < 0.0 {
+                gradients[0] -= error * x;
+            }
+        }
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("prelu")
+    }
+}
+
+/// Exponential linear unit activation function with an `alpha` of `1`, see [elu] to configure `alpha`. 
+#[derive(Clone, Copy)]
+pub struct Elu;
+
+impl ActivationFn for Elu {
+    fn function(&self, x: Float) -> Float {
+        if x > 0.0 { x } else { exp(x) - 1.0 }
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        if x > 0.0 { 1.0 } else { exp(x) }
+    }
+
+    fn name(&self) -> &'static str {
+        "elu"
+    }
+}
+
+/// Exponential linear unit activation function with an `alpha` of `1`, see [Elu]. 
+pub const ELU: Elu = Elu;
+
+/// Exponential linear unit activation function, outputs `x` when positive and `alpha * (e^x - 1)` otherwise. 
+/// 
+/// # Parameters 
+/// * `alpha` The value negative outputs saturate towards, usually `1.0`. 
+pub fn elu(alpha: Float) -> OwnedActivation<impl Fn(Float) -> Float, impl Fn(Float) -> Float> {
+    OwnedActivation {
+        function: move |x| if x > 0.0 { x } else { alpha * (exp(x) - 1.0) },
+        derivative: move |x| if x > 0.0 { 1.0 } else { alpha * exp(x) }
+    }
+}
+
+/// `sqrt(2 / pi)`, used by the tanh approximation of GELU. 
+const GELU_SCALE: Float = 0.797_884_6;
+/// Cubic coefficient used by the tanh approximation of GELU. 
+const GELU_CUBIC: Float = 0.044_715;
+
+/// Gaussian error linear unit activation function, using the common tanh approximation 
+/// `0.5x(1 + tanh(sqrt(2 / pi)(x + 0.044715x^3)))`. 
+#[derive(Clone, Copy)]
+pub struct Gelu;
+
+impl ActivationFn for Gelu {
+    fn function(&self, x: Float) -> Float {
+        0.5 * x * (1.0 + tanh(GELU_SCALE * (x + GELU_CUBIC * x * x * x)))
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let t = tanh(GELU_SCALE * (x + GELU_CUBIC * x * x * x));
+        0.5 * (1.0 + t) + 0.5 * x * (1.0 - t * t) * GELU_SCALE * (1.0 + 3.0 * GELU_CUBIC * x * x)
+    }
+
+    fn name(&self) -> &'static str {
+        "gelu"
+    }
+}
+
+/// Gaussian error linear unit activation function, see [Gelu]. 
+pub const GELU: Gelu = Gelu;
+
+/// Swish (also known as SiLU) activation function, `x * sigmoid(x)`. 
+#[derive(Clone, Copy)]
+pub struct Swish;
+
+impl ActivationFn for Swish {
+    fn function(&self, x: Float) -> Float {
+        x / (1.0 + exp(-x))
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let s = 1.0 / (1.0 + exp(-x));
+        s + x * s * (1.0 - s)
+    }
+
+    fn name(&self) -> &'static str {
+        "swish"
+    }
+}
+
+/// Swish (also known as SiLU) activation function, see [Swish]. 
+pub const SWISH: Swish = Swish;
+
+/// Softplus activation function, a smooth non-negative approximation of [Relu] `ln(1 + e^x)`. 
+/// 
+/// Computed as `max(x, 0) + ln(1 + e^-|x|)` so `e^x` is never evaluated for large inputs, the derivative is the sigmoid of the input. 
+#[derive(Clone, Copy)]
+pub struct Softplus;
+
+impl ActivationFn for Softplus {
+    fn function(&self, x: Float) -> Float {
+        softplus(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        1.0 / (1.0 + exp(-x))
+    }
+
+    fn name(&self) -> &'static str {
+        "softplus"
+    }
+}
+
+/// Softplus activation function, see [Softplus]. 
+pub const SOFTPLUS: Softplus = Softplus;
+
+/// Hard sigmoid activation function, a piecewise-linear approximation of [Sigmoid] `clamp(0.2x + 0.5, 0, 1)`. 
+/// 
+/// Built only from multiplication, addition and comparisons, avoiding `exp` entirely for MCUs without an FPU. 
+#[derive(Clone, Copy)]
+pub struct HardSigmoid;
+
+impl ActivationFn for HardSigmoid {
+    fn function(&self, x: Float) -> Float {
+        (0.2 * x + 0.5).clamp(0.0, 1.0)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        if x > -2.5 && x < 2.5 { 0.2 } else { 0.0 }
+    }
+
+    fn name(&self) -> &'static str {
+        "hard_sigmoid"
+    }
+}
+
+/// Hard sigmoid activation function, see [HardSigmoid]. 
+pub const HARD_SIGMOID: HardSigmoid = HardSigmoid;
+
+/// Identity (linear) activation function, passes values through unchanged. 
+/// 
+/// Suited to regression outputs where targets aren't bounded to the range of the other activations. 
+#[derive(Clone, Copy)]
+pub struct Identity;
+
+impl ActivationFn for Identity {
+    fn function(&self, x: Float) -> Float {
+        x
+    }
+
+    fn derivative(&self, _x: Float) -> Float {
+        1.0
+    }
+
+    fn name(&self) -> &'static str {
+        "identity"
+    }
+}
+
+/// Identity (linear) activation function, see [Identity]. 
+pub const IDENTITY: Identity = Identity;
+
+/// Mish activation function, `x * tanh(softplus(x))`. 
+#[derive(Clone, Copy)]
+pub struct Mish;
+
+impl ActivationFn for Mish {
+    fn function(&self, x: Float) -> Float {
+        x * tanh(softplus(x))
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let t = tanh(softplus(x));
+        let s = 1.0 / (1.0 + exp(-x));
+        t + x * (1.0 - t * t) * s
+    }
+
+    fn name(&self) -> &'static str {
+        "mish"
+    }
+}
+
+/// Mish activation function, see [Mish]. 
+pub const MISH: Mish = Mish;
+
+/// Sine activation function, used by SIREN style networks to fit smooth implicit functions. 
+/// 
+/// Pair it with [Matrix::siren](crate::matrix::Matrix::siren) initialised weights, see [sine] to scale the frequency 
+/// or [Siren] for layers owning the activation and initialised to match. 
+#[derive(Clone, Copy)]
+pub struct Sine;
+
+impl ActivationFn for Sine {
+    fn function(&self, x: Float) -> Float {
+        sin(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        cos(x)
+    }
+
+    fn name(&self) -> &'static str {
+        "sine"
+    }
+}
+
+/// Sine activation function, see [Sine]. 
+pub const SINE: Sine = Sine;
+
+/// Sine activation function with a frequency scale, `sin(omega * x)`. 
+/// 
+/// # Parameters 
+/// * `omega` The frequency scale, SIRENs commonly use `30.0`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::fastrand::Rng;
+/// use mynn::matrix::Matrix;
+/// use mynn::activations::{sine, IDENTITY};
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let omega = 30.0;
+/// let mut rng = Rng::with_seed(1);
+/// let mut network = make_network!(1, 8, 8, 1);
+/// network.weights = Matrix::siren(&mut rng, true, omega);
+/// network.next.weights = Matrix::siren(&mut rng, false, omega);
+/// network.next.next.weights = Matrix::siren(&mut rng, false, omega);
+/// 
+/// network.train(0.001, [[0.0], [0.5], [1.0]], [[0.1], [0.7], [0.2]], 100, &sine(omega), &MSE);
+/// ```
+pub fn sine(omega: Float) -> OwnedActivation<impl Fn(Float) -> Float, impl Fn(Float) -> Float> {
+    OwnedActivation {
+        function: move |x| sin(omega * x),
+        derivative: move |x| omega * cos(omega * x)
+    }
+}
+
+/// Sine activation function with a frequency scale, `sin(omega * x)`, for a layer of a SIREN style network to own, 
+/// the layer's weights drawn with the SIREN scheme of [Matrix::siren](crate::matrix::Matrix::siren) whenever the 
+/// network is [randomized](crate::network::Network::randomize). 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{Siren, IDENTITY};
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let omega = 30.0;
+/// let mut network = make_network!(1, 8 => Siren::first(omega), 8 => Siren::hidden(omega), 1 => IDENTITY).with_seed(1);
+/// 
+/// // Within `1 / 1` for the first layer, and `sqrt(6 / 8) / omega` for the second. 
+/// assert!(network.weights.data.as_flattened().iter().all(|w| w.abs() <= 1.0));
+/// assert!(network.next.weights.data.as_flattened().iter().all(|w| w.abs() < 0.03));
+/// 
+/// network.train(0.001, [[0.0], [0.5], [1.0]], [[0.1], [0.7], [0.2]], 100, &IDENTITY, &MSE);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Siren {
+    /// The frequency scale, SIRENs commonly use `30.0`. 
+    pub omega: Float,
+    /// Whether this is the activation of the network's first layer, whose weights are drawn from a wider range. 
+    pub first: bool
+}
+
+impl Siren {
+    /// Instantiates the activation of the first layer of a SIREN style network from its frequency scale. 
+    pub const fn first(omega: Float) -> Siren {
+        Siren {
+            omega,
+            first: true
+        }
+    }
+
+    /// Instantiates the activation of a later layer of a SIREN style network from its frequency scale. 
+    pub const fn hidden(omega: Float) -> Siren {
+        Siren {
+            omega,
+            first: false
+        }
+    }
+}
+
+impl LayerActivation for Siren {
+    fn activate(&self, values: &mut [Float]) {
+        for value in values.iter_mut() {
+            *value = sin(self.omega * *value);
+        }
+    }
+
+    fn derive(&self, values: &mut [Float]) {
+        for value in values.iter_mut() {
+            *value = self.omega * cos(self.omega * *value);
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "siren"
+    }
+
+    fn weight_limit(&self, fan_in: usize) -> Option<Float> {
+        let fan_in = fan_in as Float;
+        Some(if self.first { 1.0 / fan_in } else { sqrt(6.0 / fan_in) / self.omega })
+    }
+}
+
+/// Softmax activation function, normalises a layer's outputs into probabilities that sum to `1`. 
+/// 
+/// The largest value is subtracted before exponentiating to avoid overflow, the derivative is the diagonal 
+/// of the softmax Jacobian `p * (1 - p)` while the errors are mapped through the full Jacobian, so it trains with 
+/// any loss. Pair it with [SOFTMAX_CROSS_ENTROPY](crate::loss::SOFTMAX_CROSS_ENTROPY) to skip the Jacobian entirely. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::SOFTMAX;
+/// use mynn::network::Network;
+/// 
+/// let mut network = make_network!(2, 3, 3);
+/// let probabilities = network.predict([1.0, 0.0], &SOFTMAX);
+/// 
+/// assert!((probabilities.iter().sum::<Float>() - 1.0).abs() < 1e-5);
+/// 
+/// // The gradients are also correct with losses that don't fuse with the softmax. 
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::{CROSS_ENTROPY, MSE};
+/// 
+/// let mut network = make_network!(2, 4 => SIGMOID, 3 => SOFTMAX).with_seed(3);
+/// let mut errors = [0.0; 2];
+/// let mse = network.grad_check([0.5, -1.0], [0.0, 1.0, 0.0], &SIGMOID, &MSE, 1e-5, &mut errors);
+/// let cross_entropy = network.grad_check([0.5, -1.0], [0.0, 1.0, 0.0], &SIGMOID, &CROSS_ENTROPY, 1e-5, &mut errors);
+/// 
+/// #[cfg(not(feature = "f32"))]
+/// assert!(mse < 1e-4 && cross_entropy < 1e-4);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Softmax;
+
+impl LayerActivation for Softmax {
+    fn activate(&self, values: &mut [Float]) {
+        let max = values.iter().fold(Float::NEG_INFINITY, |max, &x| if x > max { x } else { max });
+        let mut sum = 0.0;
+        for value in values.iter_mut() {
+            *value = exp(*value - max);
+            sum += *value;
+        }
+        for value in values.iter_mut() {
+            *value /= sum;
+        }
+    }
+
+    fn derive(&self, values: &mut [Float]) {
+        self.activate(values);
+        for value in values.iter_mut() {
+            *value *= 1.0 - *value;
+        }
+    }
+
+    fn derive_errors(&self, values: &mut [Float], errors: &mut [Float]) {
+        self.activate(values);
+        let weighted = errors.iter().zip(values.iter()).map(|(error, p)| error * p).sum::<Float>();
+        for (error, p) in errors.iter_mut().zip(values.iter()) {
+            *error = (*error - weighted) * p;
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "softmax"
+    }
+}
+
+/// Softmax activation function, see [Softmax]. 
+pub const SOFTMAX: Softmax = Softmax;
+
+/// Lower bound of the input range covered by [SIGMOID_TABLE], inputs below this use the first entry. 
+#[cfg(feature = "lut-activations")]
+const LUT_MIN: Float = -8.0;
+/// Spacing between the inputs of consecutive entries in [SIGMOID_TABLE]. 
+#[cfg(feature = "lut-activations")]
+const LUT_STEP: Float = 0.25;
+
+/// Sigmoid sampled every [LUT_STEP] from `-8` to `8`. 
+#[cfg(feature = "lut-activations")]
+const SIGMOID_TABLE: [Float; 65] = [
+    3.353501e-4, 4.305571e-4, 5.527786e-4, 7.096704e-4, 9.110512e-4,
+    0.00116951, 0.001501182, 0.001926735, 0.002472623, 0.003172683,
+    0.004070138, 0.005220126, 0.006692851, 0.008577485, 0.01098694,
+    0.01406363, 0.01798621, 0.02297737, 0.02931223, 0.03732689,
+    0.04742587, 0.06008665, 0.07585818, 0.09534946, 0.1192029,
+    0.1480472, 0.1824255, 0.2227001, 0.2689414, 0.3208213,
+    0.3775407, 0.4378235, 0.5, 0.5621765, 0.6224593,
+    0.6791787, 0.7310586, 0.7772999, 0.8175745, 0.8519528,
+    0.8807971, 0.9046505, 0.9241418, 0.9399133, 0.9525741,
+    0.9626731, 0.9706878, 0.9770226, 0.9820138, 0.9859364,
+    0.9890131, 0.9914225, 0.9933071, 0.9947799, 0.9959299,
+    0.9968273, 0.9975274, 0.9980733, 0.9984988, 0.9988305,
+    0.9990889, 0.9992903, 0.9994472, 0.9995694, 0.9996646,
+];
+
+/// Looks up the sigmoid of `x` in [SIGMOID_TABLE], linearly interpolating between neighbouring entries. 
+#[cfg(feature = "lut-activations")]
+fn sigmoid_lut(x: Float) -> Float {
+    let position = (x - LUT_MIN) / LUT_STEP;
+    if position <= 0.0 {
+        return SIGMOID_TABLE[0];
+    }
+    let index = position as usize;
+    if index >= SIGMOID_TABLE.len() - 1 {
+        return SIGMOID_TABLE[SIGMOID_TABLE.len() - 1];
+    }
+    let fraction = position - index as Float;
+    SIGMOID_TABLE[index] + (SIGMOID_TABLE[index + 1] - SIGMOID_TABLE[index]) * fraction
+}
+
+/// Sigmoid activation function backed by a small constant lookup table with linear interpolation, 
+/// avoiding `exp` entirely on soft-float targets (requires the `lut-activations` feature). 
+/// 
+/// The table covers `-8` to `8` in steps of `0.25`, the absolute error against [Sigmoid] is below `0.001`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::activations::{ActivationFn, SIGMOID, SIGMOID_LUT};
+/// 
+/// for i in -1_000..=1_000 {
+///     let x = i as mynn::Float / 100.0;
+///     let error = (SIGMOID_LUT.function(x) - SIGMOID.function(x)).abs();
+///     assert!(error < 0.001, "absolute error {} at {}", error, x);
+/// }
+/// assert_eq!(SIGMOID_LUT.name(), "sigmoid lut");
+/// ```
+#[cfg(feature = "lut-activations")]
+#[derive(Clone, Copy)]
+pub struct SigmoidLut;
+
+#[cfg(feature = "lut-activations")]
+impl ActivationFn for SigmoidLut {
+    fn function(&self, x: Float) -> Float {
+        sigmoid_lut(x)
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let s = sigmoid_lut(x);
+        s * (1.0 - s)
+    }
+
+    fn name(&self) -> &'static str {
+        "sigmoid lut"
+    }
+}
+
+/// Lookup table sigmoid activation function, see [SigmoidLut]. 
+#[cfg(feature = "lut-activations")]
+pub const SIGMOID_LUT: SigmoidLut = SigmoidLut;
+
+/// Hyperbolic tangent activation function backed by the same lookup table as [SigmoidLut], using 
+/// `tanh(x) = 2 * sigmoid(2x) - 1` (requires the `lut-activations` feature). 
+/// 
+/// The table covers `-4` to `4`, the absolute error against [Tanh] is below `0.002`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::activations::{ActivationFn, TANH, TANH_LUT};
+/// 
+/// for i in -1_000..=1_000 {
+///     let x = i as mynn::Float / 100.0;
+///     let error = (TANH_LUT.function(x) - TANH.function(x)).abs();
+///     assert!(error < 0.002, "absolute error {} at {}", error, x);
+/// }
+/// assert_eq!(TANH_LUT.name(), "tanh lut");
+/// ```
+#[cfg(feature = "lut-activations")]
+#[derive(Clone, Copy)]
+pub struct TanhLut;
+
+#[cfg(feature = "lut-activations")]
+impl ActivationFn for TanhLut {
+    fn function(&self, x: Float) -> Float {
+        2.0 * sigmoid_lut(2.0 * x) - 1.0
+    }
+
+    fn derivative(&self, x: Float) -> Float {
+        let t = self.function(x);
+        1.0 - t * t
+    }
+
+    fn name(&self) -> &'static str {
+        "tanh lut"
+    }
+}
+
+/// Lookup table hyperbolic tangent activation function, see [TanhLut]. 
+#[cfg(feature = "lut-activations")]
+pub const TANH_LUT: TanhLut = TanhLut;
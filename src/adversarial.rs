@@ -0,0 +1,222 @@
+use super::{activations::LayerActivation, loss::{Loss, Upstream}, matrix::Matrix, network::{BackProps, Network, ParamSets}};
+use super::math::gaussian;
+use super::optimizers::Sgd;
+use super::training::GradientStep;
+use super::Float;
+use fastrand::Rng;
+
+
+/// Adversarial training of two networks against each other, a generator turning random noise into samples and a 
+/// discriminator judging whether a sample is real or generated, as in a generative adversarial network (GAN), 
+/// such as to learn to generate more data resembling a small dataset. 
+/// 
+/// Training alternates between the two, each update leaving the other network untouched: the discriminator learns 
+/// to give [real](Adversarial::real) to real samples and [fake](Adversarial::fake) to generated ones, while the 
+/// generator learns through the discriminator to have its samples given `real`. 
+/// 
+/// # Type Parameters
+/// * `NOISE` The number of random values the generator is fed, its inputs. 
+/// * `SAMPLE` The number of values in a sample, the generator's outputs and the discriminator's inputs. 
+/// * `JUDGE` The number of values the discriminator gives for a sample, its outputs. 
+/// * `G` The type of the generator, must implement [Network]. 
+/// * `D` The type of the discriminator, must implement [Network]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{IDENTITY, SIGMOID, TANH};
+/// use mynn::adversarial::Adversarial;
+/// use mynn::loss::BINARY_CROSS_ENTROPY;
+/// use mynn::network::Network;
+/// 
+/// // Real samples lie around the point (0.5, -0.5).
+/// let real = [[0.45, -0.5], [0.5, -0.45], [0.55, -0.5], [0.5, -0.55]];
+/// let generator = make_network!(2, 4 => TANH, 2 => IDENTITY).with_seed(1);
+/// let discriminator = make_network!(2, 6 => TANH, 1 => SIGMOID).with_seed(2);
+/// let mut gan = Adversarial::new(generator, discriminator).with_seed(3);
+/// 
+/// gan.train(&real, 2_000, 0.05, &TANH, &BINARY_CROSS_ENTROPY, &BINARY_CROSS_ENTROPY);
+/// 
+/// let mut mean = [0.0; 2];
+/// for _ in 0..100 {
+///     let [x, y] = gan.generate(&TANH);
+///     mean = [mean[0] + x / 100.0, mean[1] + y / 100.0];
+/// }
+/// assert!((mean[0] - 0.5).abs() < 0.2 && (mean[1] + 0.5).abs() < 0.2);
+/// ```
+pub struct Adversarial<const NOISE: usize, const SAMPLE: usize, const JUDGE: usize, G, D>
+where 
+    G: Network<NOISE, SAMPLE>, 
+    D: Network<SAMPLE, JUDGE>
+{
+    /// The network generating samples from noise. 
+    pub generator: G,
+    /// The network judging whether samples are real or generated. 
+    pub discriminator: D,
+    /// The target the discriminator is trained to give real samples, and the generator to have its samples given. 
+    pub real: [Float; JUDGE],
+    /// The target the discriminator is trained to give generated samples. 
+    pub fake: [Float; JUDGE],
+    /// The number of updates of the discriminator per update of the generator, at least one. 
+    pub discriminator_steps: usize,
+    /// The number of real samples in each batch of [train](Adversarial::train), at least one. 
+    pub batch: usize,
+    /// The generator noise is drawn from. 
+    pub rng: Rng
+}
+
+impl <const NOISE: usize, const SAMPLE: usize, const JUDGE: usize, G, D> Adversarial<NOISE, SAMPLE, JUDGE, G, D>
+where 
+    G: Network<NOISE, SAMPLE>, 
+    D: Network<SAMPLE, JUDGE>
+{
+
+    /// Instantiates the training of the two networks, the discriminator trained to give `1` for real samples and `0` 
+    /// for generated ones, once per generator update, in batches of `8`, with the generator of noise seeded with `0`. 
+    pub fn new(generator: G, discriminator: D) -> Adversarial<NOISE, SAMPLE, JUDGE, G, D> {
+        Adversarial {
+            generator,
+            discriminator,
+            real: [1.0; JUDGE],
+            fake: [0.0; JUDGE],
+            discriminator_steps: 1,
+            batch: 8,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Consumes the training, returning it with its generator of noise seeded with `seed`. 
+    pub fn with_seed(mut self, seed: u64) -> Adversarial<NOISE, SAMPLE, JUDGE, G, D> {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
+    /// Consumes the training, returning it with the targets the discriminator is trained to give real and generated 
+    /// samples, such as `0.9` rather than `1` to smooth the labels. 
+    pub fn labels(mut self, real: [Float; JUDGE], fake: [Float; JUDGE]) -> Adversarial<NOISE, SAMPLE, JUDGE, G, D> {
+        self.real = real;
+        self.fake = fake;
+        self
+    }
+
+    /// Consumes the training, returning it updating the discriminator `steps` times per update of the generator, 
+    /// at least once. 
+    pub fn discriminator_steps(mut self, steps: usize) -> Adversarial<NOISE, SAMPLE, JUDGE, G, D> {
+        self.discriminator_steps = steps.max(1);
+        self
+    }
+
+    /// Consumes the training, returning it with `batch` real samples in each batch, at least one. 
+    pub fn batch(mut self, batch: usize) -> Adversarial<NOISE, SAMPLE, JUDGE, G, D> {
+        self.batch = batch.max(1);
+        self
+    }
+
+    /// Draws the noise fed to the generator, each value normally distributed with a mean of `0` and standard 
+    /// deviation of `1`. 
+    pub fn noise(&mut self) -> [Float; NOISE] {
+        core::array::from_fn(|_| gaussian(&mut self.rng))
+    }
+
+    /// Generates a sample from freshly drawn noise. 
+    pub fn generate<F: LayerActivation + ?Sized>(&mut self, act: &F) -> [Float; SAMPLE] {
+        let noise = self.noise();
+        self.generator.predict(noise, act)
+    }
+
+    /// Updates the discriminator upon a batch of real samples and as many generated ones, the generator left 
+    /// untouched, returning the average loss. 
+    /// 
+    /// # Parameters 
+    /// * `real` The real samples, nothing is trained while it's empty. 
+    /// * `l_rate` The learning rate. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss of the discriminator's judgement against [real](Adversarial::real) or [fake](Adversarial::fake). 
+    pub fn train_discriminator<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, real: &[[Float; SAMPLE]], l_rate: Float, act: &F, loss: &L) -> Float {
+        if real.is_empty() {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        let mut gradients = D::Snapshot::filled(0.0);
+        for sample in real {
+            let fake = self.generate(act);
+            for (sample, target) in [(*sample, self.real), (fake, self.fake)] {
+                self.discriminator.set_training(true);
+                let outputs = self.discriminator.feed_forward(Matrix::from([sample]).transpose(), act);
+                self.discriminator.set_training(false);
+                total += loss.loss(&outputs, &target);
+                self.discriminator.back_propagate(outputs, target, act, loss, &mut gradients);
+            }
+        }
+        let count = 2 * real.len();
+        let step = GradientStep { scale: 1.0 / count as Float, ..GradientStep::new(l_rate) };
+        self.discriminator.apply_gradients(&Sgd, &step, &mut gradients, &mut ParamSets::filled([0.0; 2]));
+        total / count as Float
+    }
+
+    /// Updates the generator upon a batch of generated samples, following the gradient of the loss of the 
+    /// discriminator's judgement back through the discriminator, which is left untouched, returning the average loss. 
+    /// 
+    /// # Parameters 
+    /// * `batch` The number of samples generated, their gradients averaged into a single update. 
+    /// * `l_rate` The learning rate. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss of the discriminator's judgement of a generated sample against [real](Adversarial::real). 
+    pub fn train_generator<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, batch: usize, l_rate: Float, act: &F, loss: &L) -> Float {
+        if batch == 0 {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        let mut gradients = G::Snapshot::filled(0.0);
+        for _ in 0..batch {
+            let noise = self.noise();
+            self.generator.set_training(true);
+            let sample = self.generator.feed_forward(Matrix::from([noise]).transpose(), act);
+            self.generator.set_training(false);
+
+            let outputs = self.discriminator.feed_forward(Matrix::from([sample]).transpose(), act);
+            total += loss.loss(&outputs, &self.real);
+            // The discriminator's own gradients are gathered apart and thrown away, leaving it untouched. 
+            let BackProps(errors) = self.discriminator.back_propagate(outputs, self.real, act, loss, &mut D::Snapshot::filled(0.0));
+            let upstream = errors.transpose().data[0].map(|error| -error);
+            self.generator.back_propagate(sample, sample, act, &Upstream { gradients: &upstream }, &mut gradients);
+        }
+        let step = GradientStep { scale: 1.0 / batch as Float, ..GradientStep::new(l_rate) };
+        self.generator.apply_gradients(&Sgd, &step, &mut gradients, &mut ParamSets::filled([0.0; 2]));
+        total / batch as Float
+    }
+
+    /// Trains the two networks in turn for a number of epochs, each epoch going through the real samples in 
+    /// [batches](Adversarial::batch), updating the discriminator [discriminator_steps](Adversarial::discriminator_steps) 
+    /// times upon each batch then the generator once upon as many generated samples. 
+    /// 
+    /// Returns the average loss of the discriminator and of the generator over the last epoch. 
+    /// 
+    /// # Parameters 
+    /// * `real` The real samples. 
+    /// * `epochs` Number of epochs. 
+    /// * `l_rate` The learning rate of both networks. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `discriminator_loss` The loss of the discriminator's judgement, see [train_discriminator](Adversarial::train_discriminator). 
+    /// * `generator_loss` The loss of the generator's samples, see [train_generator](Adversarial::train_generator). 
+    pub fn train<F, DL, GL>(&mut self, real: &[[Float; SAMPLE]], epochs: usize, l_rate: Float, act: &F, discriminator_loss: &DL, generator_loss: &GL) -> (Float, Float)
+    where 
+        F: LayerActivation + ?Sized, 
+        DL: Loss + ?Sized, 
+        GL: Loss + ?Sized
+    {
+        let mut losses = (0.0, 0.0);
+        for _ in 0..epochs {
+            let (mut discriminator, mut generator, mut batches) = (0.0, 0.0, 0);
+            for batch in real.chunks(self.batch.max(1)) {
+                for _ in 0..self.discriminator_steps.max(1) {
+                    discriminator += self.train_discriminator(batch, l_rate, act, discriminator_loss) / self.discriminator_steps.max(1) as Float;
+                }
+                generator += self.train_generator(batch.len(), l_rate, act, generator_loss);
+                batches += 1;
+            }
+            losses = (discriminator / batches.max(1) as Float, generator / batches.max(1) as Float);
+        }
+        losses
+    }
+}
@@ -0,0 +1,205 @@
+use super::{activations::LayerActivation, loss::Loss, matrix::Matrix, optimizers::Optimizer, training::GradientStep};
+use super::network::{BackProps, Layer, Visitor, VisitorMut};
+use super::preprocessing::argmax_label;
+use super::Float;
+use core::fmt;
+use fastrand::Rng;
+
+
+/// Baseline regression model ignoring its inputs, always predicting the mean of the targets it was fitted to, the 
+/// obvious answer any useful network must beat. 
+/// 
+/// Implements [Layer] with nothing to train, so is measured with the same methods as a network such as 
+/// [evaluate](crate::network::Network::evaluate) or [regression_metrics](crate::network::Network::regression_metrics). 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of inputs, ignored. 
+/// * `END_S` The number of outputs. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, activations::{IDENTITY, TANH}};
+/// use mynn::baseline::MeanBaseline;
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[0.0], [0.25], [0.5], [0.75], [1.0]];
+/// let targets = inputs.map(|[x]| [2.0 * x + 1.0]);
+/// let mut baseline = MeanBaseline::fit(&targets);
+/// let mut network = make_network!(1, 3 => TANH, 1 => IDENTITY).with_seed(1);
+/// 
+/// network.train(0.1, inputs, targets, 2_000, &TANH, &MSE);
+/// 
+/// assert_eq!(baseline.mean, [2.0]);
+/// assert!(network.evaluate(&inputs, &targets, &TANH, &MSE) < baseline.evaluate(&inputs, &targets, &TANH, &MSE));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MeanBaseline<const NEURONS: usize, const END_S: usize> {
+    /// The value predicted for each output. 
+    pub mean: [Float; END_S]
+}
+
+impl <const NEURONS: usize, const END_S: usize> MeanBaseline<NEURONS, END_S> {
+
+    /// Instantiates a model predicting the mean of each output of the targets, `0` should there be none. 
+    pub fn fit(targets: &[[Float; END_S]]) -> MeanBaseline<NEURONS, END_S> {
+        let mut mean = [0.0; END_S];
+        for target in targets {
+            for (mean, value) in mean.iter_mut().zip(target) {
+                *mean += value / targets.len() as Float;
+            }
+        }
+        MeanBaseline {
+            mean
+        }
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize> Layer<NEURONS, END_S> for MeanBaseline<NEURONS, END_S> {
+    const IS_END: bool = true;
+    const OUTPUTS: usize = END_S;
+    const PARAMS: usize = 0;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, _feed: Matrix<NEURONS, 1>, _act: &A) -> [Float; END_S] {
+        self.mean
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, _outputs: [Float; END_S], _targets: [Float; END_S], _act: &A, _loss: &L, _gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        BackProps(Matrix::zeros())
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, _optimizer: &O, _step: &GradientStep, _gradients: &mut Self::Snapshot, _state: &mut Self::StateSnapshot) {}
+
+    fn init_params(&mut self, _rng: &mut Rng) {}
+
+    fn set_training(&mut self, _training: bool) {}
+
+    fn clear_state(&mut self) {}
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("mean baseline", NEURONS, END_S);
+        visitor.end();
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("mean baseline", NEURONS, END_S);
+        visitor.end();
+    }
+
+    type Snapshot = ();
+
+    type StateSnapshot = ();
+}
+
+impl <const NEURONS: usize, const END_S: usize> fmt::Display for MeanBaseline<NEURONS, END_S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "mean baseline {NEURONS} -> {END_S}, predicting {:?}", self.mean)
+    }
+}
+
+/// Baseline classification model ignoring its inputs, always predicting the most common class of the targets it 
+/// was fitted to, the accuracy any useful classifier must beat, which can be high on imbalanced datasets. 
+/// 
+/// Implements [Layer] with nothing to train, so is measured with the same methods as a network such as 
+/// [accuracy](crate::network::Network::accuracy) or [confusion_matrix](crate::network::Network::confusion_matrix). 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of inputs, ignored. 
+/// * `CLASSES` The number of outputs, one per class for one-hot targets, or a single output for a binary class. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::activations::SIGMOID;
+/// use mynn::baseline::MajorityBaseline;
+/// use mynn::network::Network;
+/// 
+/// // Eight good parts and two defects. 
+/// let inputs = [[0.1], [0.2], [0.3], [0.4], [0.5], [0.6], [0.7], [0.8], [0.9], [1.0]];
+/// let mut targets = [[1.0, 0.0]; 10];
+/// targets[8] = [0.0, 1.0];
+/// targets[9] = [0.0, 1.0];
+/// let mut baseline = MajorityBaseline::fit(&targets);
+/// 
+/// assert_eq!(baseline.class, 0);
+/// assert_eq!(baseline.accuracy(&inputs, &[0, 0, 0, 0, 0, 0, 0, 0, 1, 1], &SIGMOID), 0.8);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MajorityBaseline<const NEURONS: usize, const CLASSES: usize> {
+    /// The index of the class predicted. 
+    pub class: usize
+}
+
+impl <const NEURONS: usize, const CLASSES: usize> MajorityBaseline<NEURONS, CLASSES> {
+
+    /// Instantiates a model predicting the most common class of the targets, the first should there be a tie. 
+    /// 
+    /// The class of a target is the index of its largest value, as for one-hot targets, or with a single output 
+    /// whether it's at least `0.5`. 
+    pub fn fit(targets: &[[Float; CLASSES]]) -> MajorityBaseline<NEURONS, CLASSES> {
+        let mut counts = [0; CLASSES];
+        let mut positives = 0;
+        for target in targets {
+            if let Some(count) = counts.get_mut(argmax_label(target)) {
+                *count += 1;
+            }
+            positives += target.first().is_some_and(|value| *value >= 0.5) as usize;
+        }
+        let class = match CLASSES {
+            1 => (2 * positives > targets.len()) as usize,
+            _ => (0..CLASSES).fold(0, |best, i| if counts[i] > counts[best] { i } else { best })
+        };
+        MajorityBaseline {
+            class
+        }
+    }
+
+    /// The outputs predicted, `1` for the class and `0` for the others, or with a single output the class itself. 
+    pub fn outputs(&self) -> [Float; CLASSES] {
+        match CLASSES {
+            1 => [self.class as Float; CLASSES],
+            _ => core::array::from_fn(|i| (i == self.class) as u8 as Float)
+        }
+    }
+}
+
+impl <const NEURONS: usize, const CLASSES: usize> Layer<NEURONS, CLASSES> for MajorityBaseline<NEURONS, CLASSES> {
+    const IS_END: bool = true;
+    const OUTPUTS: usize = CLASSES;
+    const PARAMS: usize = 0;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, _feed: Matrix<NEURONS, 1>, _act: &A) -> [Float; CLASSES] {
+        self.outputs()
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, _outputs: [Float; CLASSES], _targets: [Float; CLASSES], _act: &A, _loss: &L, _gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        BackProps(Matrix::zeros())
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, _optimizer: &O, _step: &GradientStep, _gradients: &mut Self::Snapshot, _state: &mut Self::StateSnapshot) {}
+
+    fn init_params(&mut self, _rng: &mut Rng) {}
+
+    fn set_training(&mut self, _training: bool) {}
+
+    fn clear_state(&mut self) {}
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("majority baseline", NEURONS, CLASSES);
+        visitor.end();
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("majority baseline", NEURONS, CLASSES);
+        visitor.end();
+    }
+
+    type Snapshot = ();
+
+    type StateSnapshot = ();
+}
+
+impl <const NEURONS: usize, const CLASSES: usize> fmt::Display for MajorityBaseline<NEURONS, CLASSES> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "majority baseline {NEURONS} -> {CLASSES}, predicting class {}", self.class)
+    }
+}
@@ -0,0 +1,147 @@
+use super::{activations::LayerActivation, loss::Loss, network::{Network, TrainStats}, training::TrainOptions};
+use super::Float;
+use fastrand::Rng;
+
+
+/// Several identically shaped networks trained apart whose predictions are combined, averaging out the mistakes any 
+/// one member makes for a cheap gain in accuracy. 
+/// 
+/// Each member is initialised and shuffles its samples from its own generator forked from `rng`, so members trained 
+/// on the same data still differ. 
+/// 
+/// # Type Parameters
+/// * `MEMBERS` The number of networks. 
+/// * `NEURONS` The number of inputs to each network. 
+/// * `END_S` The number of outputs from each network. 
+/// * `N` The type of each network, must implement [Network]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::ensemble::Ensemble;
+/// use mynn::loss::MSE;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [[1.0, 0.0], [0.0, 1.0], [0.0, 1.0], [1.0, 0.0]];
+/// let mut ensemble = Ensemble::<3, _, _, _>::from_fn(|_| make_network!(2, 4, 2)).with_seed(9);
+/// 
+/// ensemble.train(0.5, inputs, targets, 2_000, &SIGMOID, &MSE);
+/// 
+/// assert!(ensemble.predict([0.0, 1.0], &SIGMOID)[1] > 0.8);
+/// assert_eq!(ensemble.vote([1.0, 1.0], &SIGMOID), 0);
+/// ```
+pub struct Ensemble<const MEMBERS: usize, const NEURONS: usize, const END_S: usize, N: Network<NEURONS, END_S>> {
+    /// The networks making up the ensemble. 
+    pub members: [N; MEMBERS],
+    /// The generator each member's own generator is forked from. 
+    pub rng: Rng
+}
+
+impl <const MEMBERS: usize, const NEURONS: usize, const END_S: usize, N: Network<NEURONS, END_S>> Ensemble<MEMBERS, NEURONS, END_S, N> {
+
+    /// Instantiates an ensemble from its members, with its generator seeded with `0`. 
+    pub fn new(members: [N; MEMBERS]) -> Ensemble<MEMBERS, NEURONS, END_S, N> {
+        Ensemble {
+            members,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Instantiates an ensemble with each member built by `member`, given the index of the member. 
+    pub fn from_fn<F: FnMut(usize) -> N>(member: F) -> Ensemble<MEMBERS, NEURONS, END_S, N> {
+        Ensemble::new(core::array::from_fn(member))
+    }
+
+    /// Consumes the ensemble, returning it with its generator seeded with `seed` and each member's weights randomized 
+    /// from its own generator forked from it. 
+    pub fn with_seed(mut self, seed: u64) -> Ensemble<MEMBERS, NEURONS, END_S, N> {
+        self.rng = Rng::with_seed(seed);
+        for member in self.members.iter_mut() {
+            member.randomize(&mut self.rng.fork());
+        }
+        self
+    }
+
+    /// Predicts the average of the members' predictions. 
+    /// 
+    /// # Parameters 
+    /// * `data` The data for the prediction to be made upon. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    pub fn predict<F: LayerActivation + ?Sized>(&mut self, data: [Float; NEURONS], act: &F) -> [Float; END_S] {
+        let mut result = [0.0; END_S];
+        for member in self.members.iter_mut() {
+            for (total, output) in result.iter_mut().zip(member.predict(data, act)) {
+                *total += output;
+            }
+        }
+        result.map(|total| total / MEMBERS.max(1) as Float)
+    }
+
+    /// Predicts the class most members [classify](Network::classify) the data as, a tie going to the lowest class. 
+    /// 
+    /// # Parameters 
+    /// * `data` The data for the prediction to be made upon. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    pub fn vote<F: LayerActivation + ?Sized>(&mut self, data: [Float; NEURONS], act: &F) -> usize {
+        let mut votes = [0usize; END_S];
+        for member in self.members.iter_mut() {
+            votes[member.classify(data, act)] += 1;
+        }
+        (0..END_S).fold(0, |best, i| if votes[i] > votes[best] { i } else { best })
+    }
+
+    /// Trains each member as [Network::train] does, shuffling the samples each epoch from the member's own generator, 
+    /// returning each member's statistics. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `inputs` Array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `epochs` Number of epochs each member is trained for. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    pub fn train<F: LayerActivation + ?Sized, L: Loss + ?Sized, const DATA_S: usize>(&mut self, l_rate: Float, inputs: [[Float; NEURONS]; DATA_S], targets: [[Float; END_S]; DATA_S], epochs: usize, act: &F, loss: &L) -> [TrainStats; MEMBERS] {
+        self.train_with(&inputs, &targets, act, loss, |rng| TrainOptions::new(l_rate, epochs).shuffle(rng))
+    }
+
+    /// Trains each member as [Network::train_with] does, with the options for each built by `options`, given the 
+    /// member's own generator for any stochastic options, returning each member's statistics. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// * `options` Builds the options for each member's training run from its generator. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::ensemble::Ensemble;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// 
+    /// let mut ensemble = Ensemble::new([make_network!(2, 3, 1), make_network!(2, 3, 1)]).with_seed(1);
+    /// 
+    /// let stats = ensemble.train_with(&[[0.0, 1.0], [1.0, 0.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, |rng| {
+    ///     TrainOptions::new(0.5, 500).shuffle(rng).weight_decay(1e-4)
+    /// });
+    /// assert!(stats.iter().all(|stats| stats.average_loss < 0.05));
+    /// ```
+    pub fn train_with<F, L, O, const DATA_S: usize>(&mut self, inputs: &[[Float; NEURONS]; DATA_S], targets: &[[Float; END_S]; DATA_S], act: &F, loss: &L, mut options: O) -> [TrainStats; MEMBERS]
+    where 
+        F: LayerActivation + ?Sized, 
+        L: Loss + ?Sized, 
+        O: for<'r> FnMut(&'r mut Rng) -> TrainOptions<'r, NEURONS, END_S>
+    {
+        let mut stats = [TrainStats::default(); MEMBERS];
+        for (member, stats) in self.members.iter_mut().zip(stats.iter_mut()) {
+            let mut rng = self.rng.fork();
+            *stats = member.train_with(inputs, targets, act, loss, options(&mut rng));
+        }
+        stats
+    }
+
+}
@@ -0,0 +1,547 @@
+use super::network::{zip_sets, Network, Visitor, VisitorMut};
+use super::math::{exp, gaussian, ln, sqrt, uniform};
+use super::matrix::Matrix;
+use super::schedules::Schedule;
+use super::Float;
+use fastrand::Rng;
+
+
+/// Genetic algorithm evolving a fixed size population of networks towards a higher fitness, for tasks such as 
+/// control where the reward isn't differentiable and back propagation can't be used. 
+/// 
+/// Each generation every member is scored by the fitness closure, the fittest `survivors` are kept as they are and 
+/// the rest are replaced by children of two survivors chosen by tournament, each parameter taken from either parent 
+/// and then mutated. 
+/// 
+/// # Type Parameters
+/// * `POPULATION` The number of networks in the population. 
+/// * `NEURONS` The number of inputs to each network. 
+/// * `END_S` The number of outputs from each network. 
+/// * `N` The type of each network, must implement [Network]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::TANH;
+/// use mynn::gradient_free::Genetic;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [-1.0, 1.0, 1.0, -1.0];
+/// let mut genetic = Genetic::<32, _, _, _>::from_fn(|_| make_network!(2, 3, 1)).with_seed(2);
+/// 
+/// // The fitness is the negative squared error, it could as well be the reward from a simulation.
+/// let best = genetic.evolve(200, |network| {
+///     -inputs.iter().zip(targets).map(|(&input, target)| (network.predict(input, &TANH)[0] - target).powi(2)).sum::<Float>()
+/// });
+/// 
+/// assert!(best > -0.1);
+/// assert!(genetic.best().predict([0.0, 1.0], &TANH)[0] > 0.5);
+/// ```
+pub struct Genetic<const POPULATION: usize, const NEURONS: usize, const END_S: usize, N: Network<NEURONS, END_S>> {
+    /// The networks making up the population, ordered from the fittest after [evolve](Genetic::evolve). 
+    pub members: [N; POPULATION],
+    /// The fitness of each member in the last generation evaluated. 
+    pub fitness: [Float; POPULATION],
+    /// The number of the fittest members kept unchanged each generation, the parents of the rest. 
+    pub survivors: usize,
+    /// The chance of each parameter of a child being mutated. 
+    pub mutation_rate: Float,
+    /// The standard deviation of the gaussian noise added to a mutated parameter. 
+    pub mutation_scale: Float,
+    /// The generator used for selection, crossover and mutation. 
+    pub rng: Rng
+}
+
+impl <const POPULATION: usize, const NEURONS: usize, const END_S: usize, N: Network<NEURONS, END_S>> Genetic<POPULATION, NEURONS, END_S, N> {
+
+    /// Instantiates a population from its members, keeping the fittest half each generation, mutating a tenth of 
+    /// the parameters of each child by noise with a standard deviation of `0.1`, with its generator seeded with `0`. 
+    pub fn new(members: [N; POPULATION]) -> Genetic<POPULATION, NEURONS, END_S, N> {
+        Genetic {
+            members,
+            fitness: [Float::NEG_INFINITY; POPULATION],
+            survivors: POPULATION.div_ceil(2),
+            mutation_rate: 0.1,
+            mutation_scale: 0.1,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Instantiates a population with each member built by `member`, given the index of the member. 
+    pub fn from_fn<F: FnMut(usize) -> N>(member: F) -> Genetic<POPULATION, NEURONS, END_S, N> {
+        Genetic::new(core::array::from_fn(member))
+    }
+
+    /// Consumes the population, returning it with its generator seeded with `seed` and each member's weights 
+    /// randomized from it. 
+    pub fn with_seed(mut self, seed: u64) -> Genetic<POPULATION, NEURONS, END_S, N> {
+        self.rng = Rng::with_seed(seed);
+        for member in self.members.iter_mut() {
+            member.randomize(&mut self.rng);
+        }
+        self
+    }
+
+    /// Consumes the population, returning it keeping the `survivors` fittest members each generation, at least one. 
+    pub fn survivors(mut self, survivors: usize) -> Genetic<POPULATION, NEURONS, END_S, N> {
+        self.survivors = survivors.max(1);
+        self
+    }
+
+    /// Consumes the population, returning it mutating each parameter of a child with the chance `rate`, adding 
+    /// gaussian noise with the standard deviation `scale`. 
+    pub fn mutation(mut self, rate: Float, scale: Float) -> Genetic<POPULATION, NEURONS, END_S, N> {
+        self.mutation_rate = rate;
+        self.mutation_scale = scale;
+        self
+    }
+
+    /// Evolves the population for a number of generations, returning the fitness of the fittest member of the last, 
+    /// which is left first in [members](Genetic::members). 
+    /// 
+    /// # Parameters 
+    /// * `generations` The number of generations to evaluate. 
+    /// * `fitness` Scores a network, higher being better, `NaN` scores are ranked last. 
+    pub fn evolve<F: FnMut(&mut N) -> Float>(&mut self, generations: usize, mut fitness: F) -> Float {
+        for generation in 0..generations {
+            for (member, score) in self.members.iter_mut().zip(self.fitness.iter_mut()) {
+                *score = fitness(member);
+                if score.is_nan() {
+                    *score = Float::NEG_INFINITY;
+                }
+            }
+            self.rank();
+            if generation + 1 < generations {
+                self.breed();
+            }
+        }
+        self.fitness.first().copied().unwrap_or(Float::NEG_INFINITY)
+    }
+
+    /// The fittest member of the last generation evaluated. 
+    pub fn best(&mut self) -> &mut N {
+        &mut self.members[0]
+    }
+
+    /// Orders the members and their fitness from the fittest. 
+    fn rank(&mut self) {
+        for i in 0..POPULATION {
+            let best = (i..POPULATION).fold(i, |best, j| if self.fitness[j] > self.fitness[best] { j } else { best });
+            self.members.swap(i, best);
+            self.fitness.swap(i, best);
+        }
+    }
+
+    /// Replaces every member past the survivors with a mutated child of two survivors. 
+    fn breed(&mut self) {
+        let survivors = self.survivors.clamp(1, POPULATION.max(1));
+        for child in survivors..POPULATION {
+            let first = self.tournament(survivors);
+            let second = self.tournament(survivors);
+            let mut params = self.members[first].snapshot();
+            let from_second = self.members[second].snapshot();
+            zip_sets(&mut params, &from_second, |param, from_second| {
+                if !self.rng.bool() {
+                    *param = from_second;
+                }
+                if uniform(&mut self.rng) < self.mutation_rate {
+                    *param += self.mutation_scale * gaussian(&mut self.rng);
+                }
+            });
+            self.members[child].restore(&params);
+        }
+    }
+
+    /// Picks the fitter of two random survivors, which being ranked is the one earlier in the population. 
+    fn tournament(&mut self, survivors: usize) -> usize {
+        self.rng.usize(..survivors).min(self.rng.usize(..survivors))
+    }
+}
+
+/// Simulated annealing, repeatedly perturbing every parameter of a network by gaussian noise, keeping a change 
+/// should it raise the fitness or, by chance, lower it while the temperature is still high, letting the search 
+/// escape local optima early on before settling. 
+/// 
+/// The temperature for each iteration is given by a [Schedule], such as [ExponentialDecay](crate::schedules::ExponentialDecay) 
+/// for geometric cooling, a temperature of `0.0` only ever keeps improvements, a plain random search. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::SIGMOID;
+/// use mynn::gradient_free::Annealing;
+/// use mynn::network::Network;
+/// use mynn::schedules::ExponentialDecay;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [0.0, 1.0, 1.0, 1.0];
+/// let mut network = make_network!(2, 2, 1).with_seed(1);
+/// let mut annealing = Annealing::new(ExponentialDecay::new(0.1, 0.01), 0.2).with_seed(3);
+/// 
+/// let best = annealing.anneal(&mut network, 2_000, |network| {
+///     -inputs.iter().zip(targets).map(|(&input, target)| (network.predict(input, &SIGMOID)[0] - target).powi(2)).sum::<Float>()
+/// });
+/// 
+/// assert!(best > -0.05);
+/// assert!(network.predict([0.0, 0.0], &SIGMOID)[0] < 0.2);
+/// ```
+pub struct Annealing<S: Schedule> {
+    /// The temperature for each iteration. 
+    pub temperature: S,
+    /// The standard deviation of the gaussian noise added to each parameter every iteration. 
+    pub step: Float,
+    /// The generator used for the perturbations and to accept changes lowering the fitness. 
+    pub rng: Rng
+}
+
+impl <S: Schedule> Annealing<S> {
+
+    /// Instantiates the search from its cooling schedule and the size of its steps, with its generator seeded with `0`. 
+    /// 
+    /// # Parameters 
+    /// * `temperature` The temperature for each iteration. 
+    /// * `step` The standard deviation of the gaussian noise added to each parameter every iteration. 
+    pub fn new(temperature: S, step: Float) -> Annealing<S> {
+        Annealing {
+            temperature,
+            step,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Consumes the search, returning it with its generator seeded with `seed`. 
+    pub fn with_seed(mut self, seed: u64) -> Annealing<S> {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
+    /// Searches for the parameters of a network giving the highest fitness, leaving the network with the best found 
+    /// and returning its fitness. 
+    /// 
+    /// # Parameters 
+    /// * `network` The network to search from. 
+    /// * `iterations` The number of perturbations to try. 
+    /// * `fitness` Scores a network, higher being better, `NaN` scores are never kept. 
+    pub fn anneal<const NEURONS: usize, const END_S: usize, N, F>(&mut self, network: &mut N, iterations: usize, mut fitness: F) -> Float
+    where 
+        N: Network<NEURONS, END_S>, 
+        F: FnMut(&mut N) -> Float
+    {
+        let mut score = |network: &mut N| {
+            let score = fitness(network);
+            if score.is_nan() { Float::NEG_INFINITY } else { score }
+        };
+        let mut current = score(network);
+        let mut best = current;
+        let mut best_params = network.save_params();
+
+        for iteration in 0..iterations {
+            let previous = network.save_params();
+            network.visit_mut(&mut Perturb { rng: &mut self.rng, step: self.step });
+
+            let candidate = score(network);
+            let temperature = self.temperature.rate(iteration);
+            let accept = candidate >= current
+                || (temperature > 0.0 && candidate > Float::NEG_INFINITY && uniform(&mut self.rng) < exp((candidate - current) / temperature));
+
+            if !accept {
+                network.load_params(&previous);
+                continue;
+            }
+            current = candidate;
+            if current > best {
+                best = current;
+                best_params = network.save_params();
+            }
+        }
+        network.load_params(&best_params);
+        best
+    }
+}
+
+/// Visitor adding gaussian noise to every parameter for [Annealing]. 
+struct Perturb<'r> {
+    rng: &'r mut Rng,
+    step: Float
+}
+
+impl<'r> VisitorMut for Perturb<'r> {
+    fn params(&mut self, _name: &'static str, params: &mut [Float]) {
+        for param in params.iter_mut() {
+            *param += self.step * gaussian(self.rng);
+        }
+    }
+}
+
+/// Covariance matrix adaptation evolution strategy (CMA-ES), searching the parameters of a network as one vector, 
+/// sampling a population of candidates each generation from a gaussian whose mean, step size and covariance are 
+/// adapted towards the fittest, learning which directions and scales the fitness responds to. 
+/// 
+/// The covariance and its eigenvectors are `DIM` by `DIM` matrices, best suited to networks of up to a few hundred 
+/// parameters. 
+/// 
+/// # Type Parameters
+/// * `DIM` The number of parameters of the network, [Layer::PARAMS](crate::network::Layer::PARAMS), checked at compile time. 
+/// * `LAMBDA` The number of candidates each generation, commonly `4 + 3 * ln(DIM)`, the fittest half are recombined. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::TANH;
+/// use mynn::gradient_free::CmaEs;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [-1.0, 1.0, 1.0, -1.0];
+/// let mut network = make_network!(2, 3, 1).with_seed(1);
+/// let mut cma = CmaEs::<13, 12>::new(0.5).with_seed(2);
+/// 
+/// let best = cma.optimize(&mut network, 300, |network| {
+///     -inputs.iter().zip(targets).map(|(&input, target)| (network.predict(input, &TANH)[0] - target).powi(2)).sum::<Float>()
+/// });
+/// 
+/// assert!(best > -1e-3);
+/// assert!(network.predict([1.0, 0.0], &TANH)[0] > 0.9);
+/// ```
+pub struct CmaEs<const DIM: usize, const LAMBDA: usize> {
+    /// The mean of the distribution candidates are sampled from. 
+    pub mean: [Float; DIM],
+    /// The overall step size. 
+    pub sigma: Float,
+    /// The covariance of the distribution, scaled by `sigma` squared. 
+    pub covariance: Matrix<DIM, DIM>,
+    /// The eigenvectors of the covariance, one per column. 
+    pub eigenvectors: Matrix<DIM, DIM>,
+    /// The square roots of the eigenvalues of the covariance, the scale along each eigenvector. 
+    pub scales: [Float; DIM],
+    /// The evolution path of the covariance, the recent direction the mean has moved in. 
+    pub covariance_path: [Float; DIM],
+    /// The evolution path of the step size, the recent whitened direction the mean has moved in. 
+    pub sigma_path: [Float; DIM],
+    /// The number of generations made. 
+    pub generation: usize,
+    /// The generation the eigenvectors were last found in. 
+    pub decomposed: usize,
+    /// The generator candidates are sampled from. 
+    pub rng: Rng
+}
+
+impl <const DIM: usize, const LAMBDA: usize> CmaEs<DIM, LAMBDA> {
+
+    /// Instantiates the search with the initial step size, the standard deviation candidates are first sampled 
+    /// with about the network's parameters, with its generator seeded with `0`. 
+    pub fn new(sigma: Float) -> CmaEs<DIM, LAMBDA> {
+        let mut covariance = Matrix::zeros();
+        let mut eigenvectors = Matrix::zeros();
+        for i in 0..DIM {
+            covariance.data[i][i] = 1.0;
+            eigenvectors.data[i][i] = 1.0;
+        }
+        CmaEs {
+            mean: [0.0; DIM],
+            sigma,
+            covariance,
+            eigenvectors,
+            scales: [1.0; DIM],
+            covariance_path: [0.0; DIM],
+            sigma_path: [0.0; DIM],
+            generation: 0,
+            decomposed: 0,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Consumes the search, returning it with its generator seeded with `seed`. 
+    pub fn with_seed(mut self, seed: u64) -> CmaEs<DIM, LAMBDA> {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
+    /// Searches for the parameters of a network giving the highest fitness, starting from the network's own, leaving 
+    /// the network with the best found and returning its fitness. 
+    /// 
+    /// Fails to compile should `DIM` not match the number of parameters of the network. 
+    /// 
+    /// # Parameters 
+    /// * `network` The network to search from. 
+    /// * `generations` The number of generations to sample. 
+    /// * `fitness` Scores a network, higher being better, `NaN` scores are ranked last. 
+    pub fn optimize<const NEURONS: usize, const END_S: usize, N, F>(&mut self, network: &mut N, generations: usize, mut fitness: F) -> Float
+    where 
+        N: Network<NEURONS, END_S>, 
+        F: FnMut(&mut N) -> Float
+    {
+        const { assert!(DIM == N::PARAMS, "DIM must be the number of parameters of the network") };
+        let dim = DIM.max(1) as Float;
+        let parents = (LAMBDA / 2).max(1).min(LAMBDA);
+        let mut weights = [0.0; LAMBDA];
+        for (i, weight) in weights.iter_mut().enumerate().take(parents) {
+            *weight = ln(parents as Float + 0.5) - ln(i as Float + 1.0);
+        }
+        let total: Float = weights.iter().sum();
+        weights.iter_mut().for_each(|weight| *weight /= total);
+        let mu_eff = 1.0 / weights.iter().map(|weight| weight * weight).sum::<Float>();
+
+        let cc = (4.0 + mu_eff / dim) / (dim + 4.0 + 2.0 * mu_eff / dim);
+        let cs = (mu_eff + 2.0) / (dim + mu_eff + 5.0);
+        let c1 = 2.0 / ((dim + 1.3) * (dim + 1.3) + mu_eff);
+        let cmu = (1.0 - c1).min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((dim + 2.0) * (dim + 2.0) + mu_eff));
+        let damps = 1.0 + 2.0 * (sqrt((mu_eff - 1.0) / (dim + 1.0)) - 1.0).max(0.0) + cs;
+        let chi_n = sqrt(dim) * (1.0 - 1.0 / (4.0 * dim) + 1.0 / (21.0 * dim * dim));
+
+        network.visit(&mut Flatten { params: &mut self.mean, index: 0 });
+        let mut best = Float::NEG_INFINITY;
+        let mut best_params = self.mean;
+        let mut candidates = [[0.0; DIM]; LAMBDA];
+        let mut scores = [0.0; LAMBDA];
+
+        for _ in 0..generations {
+            for (candidate, score) in candidates.iter_mut().zip(scores.iter_mut()) {
+                let z: [Float; DIM] = core::array::from_fn(|i| self.scales[i] * gaussian(&mut self.rng));
+                for (i, param) in candidate.iter_mut().enumerate() {
+                    let step: Float = (0..DIM).map(|j| self.eigenvectors.data[i][j] * z[j]).sum();
+                    *param = self.mean[i] + self.sigma * step;
+                }
+                network.visit_mut(&mut Unflatten { params: candidate, index: 0 });
+                *score = fitness(network);
+                if score.is_nan() {
+                    *score = Float::NEG_INFINITY;
+                }
+                if *score > best {
+                    best = *score;
+                    best_params = *candidate;
+                }
+            }
+            let mut order: [usize; LAMBDA] = core::array::from_fn(|i| i);
+            order.sort_unstable_by(|&a, &b| scores[b].partial_cmp(&scores[a]).unwrap_or(core::cmp::Ordering::Equal));
+
+            let previous = self.mean;
+            for (i, mean) in self.mean.iter_mut().enumerate() {
+                *mean = (0..parents).map(|k| weights[k] * candidates[order[k]][i]).sum();
+            }
+            let shift: [Float; DIM] = core::array::from_fn(|i| (self.mean[i] - previous[i]) / self.sigma);
+
+            // Whitens the shift by the inverse square root of the covariance. 
+            let rotated: [Float; DIM] = core::array::from_fn(|j| {
+                (0..DIM).map(|i| self.eigenvectors.data[i][j] * shift[i]).sum::<Float>() / self.scales[j]
+            });
+            let sigma_rate = sqrt(cs * (2.0 - cs) * mu_eff);
+            for (i, path) in self.sigma_path.iter_mut().enumerate() {
+                let whitened: Float = (0..DIM).map(|j| self.eigenvectors.data[i][j] * rotated[j]).sum();
+                *path = (1.0 - cs) * *path + sigma_rate * whitened;
+            }
+
+            self.generation += 1;
+            let path_norm = sqrt(self.sigma_path.iter().map(|x| x * x).sum());
+            let decay = 1.0 - exp(2.0 * self.generation as Float * ln(1.0 - cs));
+            let stalled = path_norm / sqrt(decay) / chi_n >= 1.4 + 2.0 / (dim + 1.0);
+            let covariance_rate = if stalled { 0.0 } else { sqrt(cc * (2.0 - cc) * mu_eff) };
+            for (path, shift) in self.covariance_path.iter_mut().zip(shift) {
+                *path = (1.0 - cc) * *path + covariance_rate * shift;
+            }
+
+            let keep = 1.0 - c1 - cmu + if stalled { c1 * cc * (2.0 - cc) } else { 0.0 };
+            for i in 0..DIM {
+                for j in 0..=i {
+                    let rank_mu: Float = (0..parents).map(|k| {
+                        let candidate = &candidates[order[k]];
+                        weights[k] * (candidate[i] - previous[i]) * (candidate[j] - previous[j])
+                    }).sum::<Float>() / (self.sigma * self.sigma);
+                    let value = keep * self.covariance.data[i][j]
+                        + c1 * self.covariance_path[i] * self.covariance_path[j]
+                        + cmu * rank_mu;
+                    self.covariance.data[i][j] = value;
+                    self.covariance.data[j][i] = value;
+                }
+            }
+            self.sigma *= exp((cs / damps) * (path_norm / chi_n - 1.0)).min(1e10);
+
+            if (self.generation - self.decomposed) as Float > LAMBDA as Float / (c1 + cmu) / dim / 10.0 {
+                self.decompose();
+            }
+        }
+        network.visit_mut(&mut Unflatten { params: &best_params, index: 0 });
+        best
+    }
+
+    /// Finds the eigenvectors and the scales along them of the covariance by cyclic Jacobi rotations. 
+    fn decompose(&mut self) {
+        self.decomposed = self.generation;
+        let mut a = self.covariance.clone();
+        let v = &mut self.eigenvectors;
+        *v = Matrix::zeros();
+        for i in 0..DIM {
+            v.data[i][i] = 1.0;
+        }
+        for _ in 0..50 {
+            let off: Float = (0..DIM).flat_map(|p| (p + 1..DIM).map(move |q| (p, q))).map(|(p, q)| a.data[p][q] * a.data[p][q]).sum();
+            if off < 1e-30 {
+                break;
+            }
+            for p in 0..DIM {
+                for q in p + 1..DIM {
+                    if a.data[p][q] == 0.0 {
+                        continue;
+                    }
+                    let theta = (a.data[q][q] - a.data[p][p]) / (2.0 * a.data[p][q]);
+                    let t = if theta >= 0.0 { 1.0 } else { -1.0 } / (theta.abs() + sqrt(theta * theta + 1.0));
+                    let c = 1.0 / sqrt(t * t + 1.0);
+                    let s = t * c;
+                    for k in 0..DIM {
+                        let (kp, kq) = (a.data[k][p], a.data[k][q]);
+                        a.data[k][p] = c * kp - s * kq;
+                        a.data[k][q] = s * kp + c * kq;
+                    }
+                    for k in 0..DIM {
+                        let (pk, qk) = (a.data[p][k], a.data[q][k]);
+                        a.data[p][k] = c * pk - s * qk;
+                        a.data[q][k] = s * pk + c * qk;
+                    }
+                    for k in 0..DIM {
+                        let (kp, kq) = (v.data[k][p], v.data[k][q]);
+                        v.data[k][p] = c * kp - s * kq;
+                        v.data[k][q] = s * kp + c * kq;
+                    }
+                }
+            }
+        }
+        for i in 0..DIM {
+            self.scales[i] = sqrt(a.data[i][i].max(1e-20));
+        }
+    }
+}
+
+/// Visitor copying every parameter of a network into a flat slice, in the order they are visited. 
+struct Flatten<'p> {
+    params: &'p mut [Float],
+    index: usize
+}
+
+impl<'p> Visitor for Flatten<'p> {
+    fn params(&mut self, _name: &'static str, params: &[Float]) {
+        for &param in params {
+            if let Some(target) = self.params.get_mut(self.index) {
+                *target = param;
+            }
+            self.index += 1;
+        }
+    }
+}
+
+/// Visitor copying every parameter of a network back from a flat slice, see [Flatten]. 
+struct Unflatten<'p> {
+    params: &'p [Float],
+    index: usize
+}
+
+impl<'p> VisitorMut for Unflatten<'p> {
+    fn params(&mut self, _name: &'static str, params: &mut [Float]) {
+        for param in params.iter_mut() {
+            if let Some(&value) = self.params.get(self.index) {
+                *param = value;
+            }
+            self.index += 1;
+        }
+    }
+}
@@ -30,6 +30,24 @@
 //! mynn = { version = "0.1.1", features = ["f32"] }
 //! ```
 //! 
+//! To use lookup table backed `SIGMOID_LUT` and `TANH_LUT` activations, avoiding `exp` on soft-float targets, supply the `lut-activations` flag:
+//! 
+//! ```text
+//! mynn = { version = "0.1.1", features = ["lut-activations"] }
+//! ```
+//! 
+//! To use a fast polynomial approximation of `exp` in the built-in activations on the `f64` path, supply the `fast-exp` flag:
+//! 
+//! ```text
+//! mynn = { version = "0.1.1", features = ["fast-exp"] }
+//! ```
+//! 
+//! To use the helpers needing the standard library, such as the ROC curve of a binary classifier, supply the `std` flag:
+//! 
+//! ```text
+//! mynn = { version = "0.1.1", features = ["std"] }
+//! ```
+//! 
 //! ## Example  
 //! 
 //! Short example approximates the output of a XOR gate. 
@@ -37,6 +55,8 @@
 //! ```rust
 //! use mynn::make_network;
 //! use mynn::activations::SIGMOID;
+//! use mynn::loss::MSE;
+//! use mynn::network::Network;
 //! 
 //! fn main() {
 //!     let inputs = [[0.0, 0.0],  [0.0, 1.0], [1.0, 0.0],  [1.0, 1.0]];
@@ -44,7 +64,7 @@
 //! 
 //! 
 //!     let mut network = make_network!(2, 3, 1);
-//!     network.train(0.5, inputs, targets, 10_000, &SIGMOID);
+//!     network.train(0.5, inputs, targets, 10_000, &SIGMOID, &MSE);
 //! 
 //! 
 //!     println!("0 and 0: {:?}", network.predict([0.0, 0.0], &SIGMOID));
@@ -55,12 +75,45 @@
 //! ```
 #![no_std]
 
-/// Contains types for and an example activation function. 
+#[cfg(feature = "std")]
+extern crate std;
+
+/// Contains types for and a selection of activation functions. 
 pub mod activations;
 /// Contains the types and functionality for processing matrices. 
 pub mod matrix;
 /// Contains the types and functionality for the neural network. 
 pub mod network;
+/// Contains the loss functions used to train the neural network. 
+pub mod loss;
+/// Contains the optimizers used to update the weights and biases of the neural network. 
+pub mod optimizers;
+/// Contains the learning rate schedules consulted each epoch of training. 
+pub mod schedules;
+/// Contains the options and recorders used while training the neural network. 
+pub mod training;
+/// Contains the ensembles combining the predictions of several networks. 
+pub mod ensemble;
+/// Contains the linear and logistic regression models, single layer networks with no hidden layers. 
+pub mod regression;
+/// Contains the gradient-free trainers, searching for the weights and biases without back propagation. 
+pub mod gradient_free;
+/// Contains the helpers for training networks from rewards rather than targets. 
+pub mod reinforcement;
+/// Contains the helpers for training two networks against each other, a generator and a discriminator. 
+pub mod adversarial;
+/// Contains the metrics measuring how well a network predicts a dataset. 
+pub mod metrics;
+/// Contains the baseline models predicting the obvious answer, to compare trained networks against. 
+pub mod baseline;
+/// Contains the helpers preparing datasets for training, such as splitting them. 
+pub mod preprocessing;
+/// Contains the helpers turning a network's outputs into decisions, such as thresholding them. 
+pub mod postprocessing;
+/// Re-export of the random number generator used for initialisation and stochastic training options. 
+pub use fastrand;
+/// Float width agnostic maths functions backed by `libm` or `micromath`. 
+mod math;
 
 /// Centralized type for floating point operations that can be easily changed to [f32] or [f64] (default is [f64], use `f32` feature for [f32]).  
 #[cfg(not(feature = "f32"))]
@@ -137,8 +190,62 @@ macro_rules! make_net_type {
     };
 }
 
+/// Helper macro, finds the number of neurons in the final layer of a list given to [make_network] using `=> activation`, 
+/// skipping over layers given as `[constructor]` which pass on as many values as they're fed. 
+/// 
+/// # Example
+/// ```
+/// use mynn::end_size;
+/// 
+/// assert_eq!(end_size!(2; 3 => 0, 4 => 0, [()]), 4);
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! end_size {
+    ($size:expr $(;)?) => {$size};
+    ($size:expr; $next:expr => $act:expr $(, $($rest:tt)*)?) => {$crate::end_size!($next $(; $($rest)*)?)};
+    ($size:expr; [$layer:expr] $(, $($rest:tt)*)?) => {$crate::end_size!($size $(; $($rest)*)?)};
+}
+
+/// Helper macro, instantiates a neural network where every layer after the first owns its activation function. 
+/// 
+/// Used by [make_network] when each layer size is followed by `=> activation`, `$end` is the number of neurons in the final layer, 
+/// other kinds of layer given as `[constructor]` or `size => [constructor]` are called with the layers following them. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::instantiate_act_net;
+/// use mynn::activations::{RELU, SIGMOID};
+/// use mynn::network::Network;
+/// 
+/// let mut network = instantiate_act_net!(1; 2, 3 => RELU, 1 => SIGMOID);
+/// network.predict([1.0, 0.0], &SIGMOID);
+/// ```
+#[doc(hidden)]
+#[macro_export]
+macro_rules! instantiate_act_net {
+    ($end:expr; $neurons:expr $(,)?) => {
+        $crate::network::EndLayer::<$neurons>()
+    };
+    ($end:expr; $neurons:expr, $next:expr => [$layer:expr] $(, $($rest:tt)*)?) => {
+        $crate::network::fed::<$neurons, {$end}, _>(($layer)($crate::instantiate_act_net!($end; $next $(, $($rest)*)?)))
+    };
+    ($end:expr; $neurons:expr, $next:expr => $act:expr $(, $($rest:tt)*)?) => {
+        $crate::network::ProcessLayer::<$next, $neurons, {$end}, _>::new($crate::instantiate_act_net!($end; $next $(, $($rest)*)?))
+            .with_activation($act)
+    };
+    ($end:expr; $neurons:expr, [$layer:expr] $(, $($rest:tt)*)?) => {
+        $crate::network::fed::<$neurons, {$end}, _>(($layer)($crate::instantiate_act_net!($end; $neurons $(, $($rest)*)?)))
+    };
+}
+
 /// Helper macro used to initialize a neural network, simply pass a comma separated list the number of neurons for each layer, works for any sized neural network. 
 /// 
+/// Each layer after the first can optionally be given its own activation function with `=> activation`, in which case every layer must be given one. 
+/// Other kinds of layer, such as a [DropoutLayer](crate::network::DropoutLayer), can then be placed between them with `[constructor]`, 
+/// a function or closure given the layers that follow it, or with `size => [constructor]` for those such as a 
+/// [Conv2dLayer](crate::network::Conv2dLayer) passing on a different number of values than they're fed. 
+/// 
 /// # Example 
 /// ```
 /// use mynn::network::{ProcessLayer, EndLayer};
@@ -149,8 +256,26 @@ macro_rules! make_net_type {
 /// 
 /// assert_eq!(std::any::type_name_of_val(&network), std::any::type_name_of_val(&network2));
 /// ```
+/// 
+/// With per-layer activation functions, a ReLU hidden layer and identity output for regression: 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{RELU, IDENTITY};
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// 
+/// let mut network = make_network!(2, 3 => RELU, 1 => IDENTITY);
+/// 
+/// network.train(0.01, [[1.0, 2.0]], [[3.0]], 100, &IDENTITY, &MSE);
+/// ```
 #[macro_export]
 macro_rules! make_network {
+    ($neurons:expr, $next:expr => $($rest:tt)+) => {
+        $crate::instantiate_act_net!($crate::end_size!($neurons; $next => $($rest)+); $neurons, $next => $($rest)+)
+    };
+    ($neurons:expr, [$layer:expr] $($rest:tt)*) => {
+        $crate::instantiate_act_net!($crate::end_size!($neurons; [$layer] $($rest)*); $neurons, [$layer] $($rest)*)
+    };
     ($neurons:expr) => {
         $crate::network::EndLayer::<$neurons>()
     };
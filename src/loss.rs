@@ -0,0 +1,594 @@
+use super::Float;
+use super::math::ln;
+
+
+/// Generic behaviour for a loss function, measuring how far a prediction is from its targets and the 
+/// gradient used to correct it during back propagation. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::loss::{Loss, MSE};
+/// 
+/// let mut gradients = [0.0; 2];
+/// MSE.gradient(&[0.5, 1.0], &[1.0, 1.0], &mut gradients);
+/// 
+/// assert_eq!(MSE.loss(&[0.5, 1.0], &[1.0, 1.0]), 0.125);
+/// assert_eq!(gradients, [-0.5, 0.0]);
+/// ```
+pub trait Loss {
+    /// Calculates the loss of a single prediction. 
+    /// 
+    /// # Parameters 
+    /// * `outputs` The outputs from the prediction. 
+    /// * `targets` The targeted values for the prediction, the same length as `outputs`. 
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float;
+
+    /// Calculates the gradient of the loss with respect to each output, writing it into `gradients`. 
+    /// 
+    /// # Parameters 
+    /// * `outputs` The outputs from the prediction. 
+    /// * `targets` The targeted values for the prediction, the same length as `outputs`. 
+    /// * `gradients` Receives the gradient for each output, the same length as `outputs`. 
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]);
+
+    /// Whether [gradient](Loss::gradient) is already taken with respect to the output layer's pre-activation values, 
+    /// fusing in the output activation's derivative which is then skipped, `false` by default. 
+    fn fused(&self) -> bool {
+        false
+    }
+
+    /// Whether the gradient of the output at `index` is fused, see [fused](Loss::fused), the same as `fused` unless 
+    /// overridden as in [Heads] where each head may differ. 
+    fn fused_output(&self, _index: usize) -> bool {
+        self.fused()
+    }
+
+    /// Calculates the part of the loss of a single prediction due to the output at `index`, by default the loss 
+    /// of that output alone, suiting losses summing a term per output. 
+    /// 
+    /// # Parameters 
+    /// * `outputs` The outputs from the prediction. 
+    /// * `targets` The targeted values for the prediction, the same length as `outputs`. 
+    /// * `index` The index of the output. 
+    fn output_loss(&self, outputs: &[Float], targets: &[Float], index: usize) -> Float {
+        match (outputs.get(index..=index), targets.get(index..=index)) {
+            (Some(output), Some(target)) => self.loss(output, target),
+            _ => 0.0
+        }
+    }
+}
+
+/// Wraps a loss scaling its gradient by a weight, used to weight samples during training. 
+pub(crate) struct Weighted<'l, L: Loss + ?Sized> {
+    pub(crate) loss: &'l L,
+    pub(crate) weight: Float
+}
+
+impl<'l, L: Loss + ?Sized> Loss for Weighted<'l, L> {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        self.weight * self.loss.loss(outputs, targets)
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        self.loss.gradient(outputs, targets, gradients);
+        gradients.iter_mut().for_each(|gradient| *gradient *= self.weight);
+    }
+
+    fn fused(&self) -> bool {
+        self.loss.fused()
+    }
+
+    fn fused_output(&self, index: usize) -> bool {
+        self.loss.fused_output(index)
+    }
+
+    fn output_loss(&self, outputs: &[Float], targets: &[Float], index: usize) -> Float {
+        self.weight * self.loss.output_loss(outputs, targets, index)
+    }
+}
+
+/// Stands in for the loss at the end of a sub-chain, giving the gradients passed back from the layers after it. 
+pub(crate) struct Upstream<'g> {
+    pub(crate) gradients: &'g [Float]
+}
+
+impl<'g> Loss for Upstream<'g> {
+    fn loss(&self, _outputs: &[Float], _targets: &[Float]) -> Float {
+        0.0
+    }
+
+    fn gradient(&self, _outputs: &[Float], _targets: &[Float], gradients: &mut [Float]) {
+        gradients.copy_from_slice(self.gradients);
+    }
+}
+
+/// Stands in for the loss at the end of one head of a [BranchLayer](crate::network::BranchLayer), giving its part of 
+/// the gradients of the whole network's loss, starting at output `offset`. 
+pub(crate) struct Head<'l, L: Loss + ?Sized> {
+    pub(crate) loss: &'l L,
+    pub(crate) gradients: &'l [Float],
+    pub(crate) offset: usize
+}
+
+impl<'l, L: Loss + ?Sized> Loss for Head<'l, L> {
+    fn loss(&self, _outputs: &[Float], _targets: &[Float]) -> Float {
+        0.0
+    }
+
+    fn gradient(&self, _outputs: &[Float], _targets: &[Float], gradients: &mut [Float]) {
+        gradients.copy_from_slice(self.gradients);
+    }
+
+    fn fused(&self) -> bool {
+        self.loss.fused_output(self.offset)
+    }
+
+    fn fused_output(&self, index: usize) -> bool {
+        self.loss.fused_output(self.offset + index)
+    }
+}
+
+/// Loss built from user supplied closures for the loss and its gradient, for experimenting with task specific objectives. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::FnLoss;
+/// use mynn::network::Network;
+/// 
+/// // Penalise under-predictions twice as hard as over-predictions. 
+/// let asymmetric = FnLoss::new(
+///     |outputs, targets| outputs.iter().zip(targets)
+///         .map(|(o, t)| if o < t { 2.0 * (t - o) } else { o - t })
+///         .sum(),
+///     |outputs, targets, gradients| {
+///         for ((g, o), t) in gradients.iter_mut().zip(outputs).zip(targets) {
+///             *g = if o < t { -2.0 } else { 1.0 };
+///         }
+///     }
+/// );
+/// 
+/// let mut network = make_network!(2, 3, 1);
+/// network.train(0.1, [[0.0, 1.0]], [[1.0]], 10, &SIGMOID, &asymmetric);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FnLoss<F, G>
+where 
+    F: Fn(&[Float], &[Float]) -> Float,
+    G: Fn(&[Float], &[Float], &mut [Float])
+{
+    pub loss: F,
+    pub gradient: G
+}
+
+impl<F, G> FnLoss<F, G>
+where 
+    F: Fn(&[Float], &[Float]) -> Float,
+    G: Fn(&[Float], &[Float], &mut [Float])
+{
+    /// Instantiates a new loss from the loss and gradient closures, both receive the outputs then the targets. 
+    pub fn new(loss: F, gradient: G) -> FnLoss<F, G> {
+        FnLoss {
+            loss,
+            gradient
+        }
+    }
+}
+
+impl<F, G> Loss for FnLoss<F, G>
+where 
+    F: Fn(&[Float], &[Float]) -> Float,
+    G: Fn(&[Float], &[Float], &mut [Float])
+{
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        (self.loss)(outputs, targets)
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        (self.gradient)(outputs, targets, gradients)
+    }
+}
+
+/// Squared error loss `0.5 * sum((output - target)^2)`, the loss the network has always been trained with. 
+/// 
+/// Halving the sum keeps the gradient to simply `output - target`. 
+#[derive(Clone, Copy)]
+pub struct Mse;
+
+impl Loss for Mse {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (output, target) in outputs.iter().zip(targets) {
+            sum += (output - target) * (output - target);
+        }
+        0.5 * sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, output), target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = output - target;
+        }
+    }
+}
+
+/// Squared error loss, see [Mse]. 
+pub const MSE: Mse = Mse;
+
+/// Smallest probability used when taking logarithms, avoiding `ln(0)`, the square root of [f64::EPSILON] so 
+/// `1 - EPSILON` is still well resolved. 
+#[cfg(not(feature = "f32"))]
+const EPSILON: Float = 1.490_116_119_384_765_6e-8;
+/// Smallest probability used when taking logarithms, avoiding `ln(0)`, the square root of [f32::EPSILON] so 
+/// `1 - EPSILON` is still well resolved. 
+#[cfg(feature = "f32")]
+const EPSILON: Float = 3.452_669_8e-4;
+
+/// Clamps a probability to within [EPSILON] of `0` and `1`. 
+fn clamp_probability(p: Float) -> Float {
+    p.clamp(EPSILON, 1.0 - EPSILON)
+}
+
+/// Categorical cross-entropy loss `-sum(target * ln(output))` for one-hot (or probability) targets. 
+/// 
+/// Outputs are clamped away from `0` to avoid `ln(0)`, with a softmax output prefer [SoftmaxCrossEntropy] 
+/// for a stable gradient. 
+#[derive(Clone, Copy)]
+pub struct CrossEntropy;
+
+impl Loss for CrossEntropy {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (&output, &target) in outputs.iter().zip(targets) {
+            sum -= target * ln(clamp_probability(output));
+        }
+        sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, &output), &target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = -target / clamp_probability(output);
+        }
+    }
+}
+
+/// Categorical cross-entropy loss, see [CrossEntropy]. 
+pub const CROSS_ENTROPY: CrossEntropy = CrossEntropy;
+
+/// Categorical cross-entropy loss fused with a [Softmax](crate::activations::Softmax) output layer. 
+/// 
+/// The loss is the same as [CrossEntropy], but the gradient is taken directly with respect to the output layer's 
+/// pre-activation values as `output - target`, which is numerically stable and accounts for the full softmax 
+/// Jacobian. It must be used with a softmax output layer. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{SIGMOID, SOFTMAX};
+/// use mynn::loss::SOFTMAX_CROSS_ENTROPY;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+/// let targets = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+/// let mut network = make_network!(2, 4 => SIGMOID, 3 => SOFTMAX);
+/// 
+/// network.train(0.1, inputs, targets, 1_000, &SIGMOID, &SOFTMAX_CROSS_ENTROPY);
+/// 
+/// let probabilities = network.predict([0.0, 1.0], &SIGMOID);
+/// assert!(probabilities[1] > probabilities[0] && probabilities[1] > probabilities[2]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct SoftmaxCrossEntropy;
+
+impl Loss for SoftmaxCrossEntropy {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        CrossEntropy.loss(outputs, targets)
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, output), target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = output - target;
+        }
+    }
+
+    fn fused(&self) -> bool {
+        true
+    }
+}
+
+/// Categorical cross-entropy loss fused with a softmax output layer, see [SoftmaxCrossEntropy]. 
+pub const SOFTMAX_CROSS_ENTROPY: SoftmaxCrossEntropy = SoftmaxCrossEntropy;
+
+/// Binary cross-entropy loss `-sum(target * ln(output) + (1 - target) * ln(1 - output))`, for outputs in `(0, 1)` 
+/// such as from a [Sigmoid](crate::activations::Sigmoid) output layer. 
+/// 
+/// Outputs are clamped away from `0` and `1` to avoid `ln(0)`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::loss::{Loss, BINARY_CROSS_ENTROPY};
+/// 
+/// let mut gradients = [0.0; 2];
+/// BINARY_CROSS_ENTROPY.gradient(&[0.5, 0.5], &[1.0, 0.0], &mut gradients);
+/// 
+/// assert!(BINARY_CROSS_ENTROPY.loss(&[1.0, 0.0], &[1.0, 0.0]) < 1e-2);
+/// assert!((gradients[0] + 2.0).abs() < 1e-6 && (gradients[1] - 2.0).abs() < 1e-6);
+/// ```
+#[derive(Clone, Copy)]
+pub struct BinaryCrossEntropy;
+
+impl Loss for BinaryCrossEntropy {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (&output, &target) in outputs.iter().zip(targets) {
+            let output = clamp_probability(output);
+            sum -= target * ln(output) + (1.0 - target) * ln(1.0 - output);
+        }
+        sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, &output), &target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            let output = clamp_probability(output);
+            *gradient = (output - target) / (output * (1.0 - output));
+        }
+    }
+}
+
+/// Binary cross-entropy loss, see [BinaryCrossEntropy]. 
+pub const BINARY_CROSS_ENTROPY: BinaryCrossEntropy = BinaryCrossEntropy;
+
+/// Absolute error loss `sum(|output - target|)`, also known as L1 loss. 
+/// 
+/// Its gradient is the sign of the error, so outliers pull no harder than any other sample. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::loss::{Loss, MAE};
+/// 
+/// let mut gradients = [0.0; 3];
+/// MAE.gradient(&[0.5, 1.0, 4.0], &[1.0, 1.0, 1.0], &mut gradients);
+/// 
+/// assert_eq!(MAE.loss(&[0.5, 1.0, 4.0], &[1.0, 1.0, 1.0]), 3.5);
+/// assert_eq!(gradients, [-1.0, 0.0, 1.0]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Mae;
+
+impl Loss for Mae {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (output, target) in outputs.iter().zip(targets) {
+            sum += (output - target).abs();
+        }
+        sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, output), target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = if output > target {
+                1.0
+            } else if output < target {
+                -1.0
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+/// Absolute error loss, see [Mae]. 
+pub const MAE: Mae = Mae;
+
+/// Huber loss, quadratic like [Mse] for errors within `delta` and linear like [Mae] beyond it, 
+/// making it robust to outliers while staying smooth around the targets. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::loss::{Huber, Loss};
+/// 
+/// let huber = Huber::new(1.0);
+/// let mut gradients = [0.0; 2];
+/// huber.gradient(&[0.5, 4.0], &[1.0, 1.0], &mut gradients);
+/// 
+/// assert_eq!(huber.loss(&[0.5, 4.0], &[1.0, 1.0]), 0.125 + 2.5);
+/// assert_eq!(gradients, [-0.5, 1.0]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Huber {
+    /// The error beyond which the loss becomes linear. 
+    pub delta: Float
+}
+
+impl Huber {
+    /// Instantiates a new Huber loss with the given `delta`, commonly `1.0`. 
+    pub const fn new(delta: Float) -> Huber {
+        Huber {
+            delta
+        }
+    }
+}
+
+impl Loss for Huber {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (output, target) in outputs.iter().zip(targets) {
+            let error = (output - target).abs();
+            sum += if error <= self.delta {
+                0.5 * error * error
+            } else {
+                self.delta * (error - 0.5 * self.delta)
+            };
+        }
+        sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, output), target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = (output - target).clamp(-self.delta, self.delta);
+        }
+    }
+}
+
+/// Hinge loss `sum(max(0, 1 - target * output))` for margin classifiers, with targets of `-1` or `1`. 
+/// 
+/// Pairs with a linear or [Tanh](crate::activations::Tanh) output, training a single output network 
+/// much like a small support vector machine. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::IDENTITY;
+/// use mynn::loss::HINGE;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[-1.0, -0.5], [-0.5, -1.0], [1.0, 0.5], [0.5, 1.0]];
+/// let targets = [[-1.0], [-1.0], [1.0], [1.0]];
+/// let mut network = make_network!(2, 1 => IDENTITY);
+/// 
+/// network.train(0.05, inputs, targets, 200, &IDENTITY, &HINGE);
+/// 
+/// assert!(network.predict([1.0, 1.0], &IDENTITY)[0] > 0.0);
+/// assert!(network.predict([-1.0, -1.0], &IDENTITY)[0] < 0.0);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Hinge;
+
+impl Loss for Hinge {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (output, target) in outputs.iter().zip(targets) {
+            sum += (1.0 - target * output).max(0.0);
+        }
+        sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, output), target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = if target * output < 1.0 { -target } else { 0.0 };
+        }
+    }
+}
+
+/// Hinge loss, see [Hinge]. 
+pub const HINGE: Hinge = Hinge;
+
+/// Squared hinge loss `sum(max(0, 1 - target * output)^2)`, a smooth variant of [Hinge] 
+/// that penalises margin violations quadratically. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::loss::{Loss, SQUARED_HINGE};
+/// 
+/// let mut gradients = [0.0; 2];
+/// SQUARED_HINGE.gradient(&[0.5, 2.0], &[1.0, 1.0], &mut gradients);
+/// 
+/// assert_eq!(SQUARED_HINGE.loss(&[0.5, 2.0], &[1.0, 1.0]), 0.25);
+/// assert_eq!(gradients, [-1.0, 0.0]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct SquaredHinge;
+
+impl Loss for SquaredHinge {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let mut sum = 0.0;
+        for (output, target) in outputs.iter().zip(targets) {
+            let margin = (1.0 - target * output).max(0.0);
+            sum += margin * margin;
+        }
+        sum
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        for ((gradient, output), target) in gradients.iter_mut().zip(outputs).zip(targets) {
+            *gradient = -2.0 * target * (1.0 - target * output).max(0.0);
+        }
+    }
+}
+
+/// Squared hinge loss, see [SquaredHinge]. 
+pub const SQUARED_HINGE: SquaredHinge = SquaredHinge;
+
+/// Pairs the losses of the two heads of a network split by a [BranchLayer](crate::network::BranchLayer), `first` 
+/// measuring the first `outputs` outputs and `second` the rest, the loss being their sum. 
+/// 
+/// `outputs` should match the branch layer's `FIRST`, it is clamped to the number of outputs given so a larger 
+/// count leaves `second` measuring none rather than panicking. Nest another pair as `second` for more heads. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::loss::{Heads, Loss, MAE, MSE};
+/// 
+/// let heads = Heads::new(MSE, 1, MAE);
+/// let mut gradients = [0.0; 2];
+/// heads.gradient(&[0.5, 4.0], &[1.0, 1.0], &mut gradients);
+/// 
+/// assert_eq!(heads.loss(&[0.5, 4.0], &[1.0, 1.0]), 0.125 + 3.0);
+/// assert_eq!(gradients, [-0.5, 1.0]);
+/// 
+/// // Too many outputs for the first head leaves the second with none. 
+/// let clamped = Heads::new(MSE, 3, MAE);
+/// assert_eq!(clamped.loss(&[0.5, 4.0], &[1.0, 1.0]), MSE.loss(&[0.5, 4.0], &[1.0, 1.0]));
+/// ```
+#[derive(Clone, Copy)]
+pub struct Heads<A: Loss, B: Loss> {
+    /// The loss of the first head. 
+    pub first: A,
+    /// The number of outputs of the first head, the branch layer's `FIRST`. 
+    pub outputs: usize,
+    /// The loss of the second head. 
+    pub second: B
+}
+
+impl<A: Loss, B: Loss> Heads<A, B> {
+    /// Instantiates a new pair from the loss of the first head, its number of outputs, and the loss of the second head. 
+    pub const fn new(first: A, outputs: usize, second: B) -> Heads<A, B> {
+        Heads {
+            first,
+            outputs,
+            second
+        }
+    }
+
+    /// The index splitting `len` outputs between the two heads, `outputs` clamped to `len`. 
+    fn split(&self, len: usize) -> usize {
+        self.outputs.min(len)
+    }
+}
+
+impl<A: Loss, B: Loss> Loss for Heads<A, B> {
+    fn loss(&self, outputs: &[Float], targets: &[Float]) -> Float {
+        let split = self.split(outputs.len());
+        let (first_outputs, second_outputs) = outputs.split_at(split);
+        let (first_targets, second_targets) = targets.split_at(split);
+        self.first.loss(first_outputs, first_targets) + self.second.loss(second_outputs, second_targets)
+    }
+
+    fn gradient(&self, outputs: &[Float], targets: &[Float], gradients: &mut [Float]) {
+        let split = self.split(outputs.len());
+        let (first_outputs, second_outputs) = outputs.split_at(split);
+        let (first_targets, second_targets) = targets.split_at(split);
+        let (first_gradients, second_gradients) = gradients.split_at_mut(split);
+        self.first.gradient(first_outputs, first_targets, first_gradients);
+        self.second.gradient(second_outputs, second_targets, second_gradients);
+    }
+
+    fn fused_output(&self, index: usize) -> bool {
+        if index < self.outputs {
+            self.first.fused_output(index)
+        } else {
+            self.second.fused_output(index - self.outputs)
+        }
+    }
+
+    fn output_loss(&self, outputs: &[Float], targets: &[Float], index: usize) -> Float {
+        let split = self.split(outputs.len());
+        let (first_outputs, second_outputs) = outputs.split_at(split);
+        let (first_targets, second_targets) = targets.split_at(split);
+        if index < split {
+            self.first.output_loss(first_outputs, first_targets, index)
+        } else {
+            self.second.output_loss(second_outputs, second_targets, index - split)
+        }
+    }
+}
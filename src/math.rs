@@ -0,0 +1,124 @@
+// Micromath works better on smaller 8 bit MCUs where we would be using 32 bits  
+// With the `std` feature the standard library's inherent methods are used instead. 
+#[cfg(all(feature = "f32", not(feature = "std"), not(test)))]
+use micromath::F32Ext; 
+
+use super::Float;
+use fastrand::Rng;
+
+
+/// Exponential function, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(all(not(feature = "f32"), not(feature = "fast-exp")))]
+pub(crate) fn exp(x: Float) -> Float {
+    libm::exp(x)
+}
+
+/// Exponential function, backed by [fast_exp](crate::activations::fast_exp) for [f64] with the `fast-exp` feature. 
+#[cfg(all(not(feature = "f32"), feature = "fast-exp"))]
+pub(crate) fn exp(x: Float) -> Float {
+    crate::activations::fast_exp(x)
+}
+
+/// Exponential function, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn exp(x: Float) -> Float {
+    x.exp()
+}
+
+/// Natural logarithm, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn ln(x: Float) -> Float {
+    libm::log(x)
+}
+
+/// Natural logarithm, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn ln(x: Float) -> Float {
+    x.ln()
+}
+
+/// Natural logarithm of `1 + x`, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn ln_1p(x: Float) -> Float {
+    libm::log1p(x)
+}
+
+/// Natural logarithm of `1 + x`, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn ln_1p(x: Float) -> Float {
+    (1.0 + x).ln()
+}
+
+/// Sine, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn sin(x: Float) -> Float {
+    libm::sin(x)
+}
+
+/// Sine, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn sin(x: Float) -> Float {
+    x.sin()
+}
+
+/// Cosine, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn cos(x: Float) -> Float {
+    libm::cos(x)
+}
+
+/// Cosine, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn cos(x: Float) -> Float {
+    x.cos()
+}
+
+/// Hyperbolic tangent, backed by `libm` for [f64], micromath doesn't provide it so it's computed from `exp` for [f32]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn tanh(x: Float) -> Float {
+    libm::tanh(x)
+}
+
+/// Hyperbolic tangent, backed by `libm` for [f64], micromath doesn't provide it so it's computed from `exp` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn tanh(x: Float) -> Float {
+    1.0 - 2.0 / (exp(2.0 * x) + 1.0)
+}
+
+/// Square root, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn sqrt(x: Float) -> Float {
+    libm::sqrt(x)
+}
+
+/// Square root, backed by `libm` for [f64] and `micromath` for [f32]. 
+#[cfg(feature = "f32")]
+pub(crate) fn sqrt(x: Float) -> Float {
+    x.sqrt()
+}
+
+/// Archimedes' constant at the width of [Float]. 
+#[cfg(not(feature = "f32"))]
+pub(crate) const PI: Float = core::f64::consts::PI;
+/// Archimedes' constant at the width of [Float]. 
+#[cfg(feature = "f32")]
+pub(crate) const PI: Float = core::f32::consts::PI;
+
+/// Uniformly distributed random value in `0..1`. 
+#[cfg(not(feature = "f32"))]
+pub(crate) fn uniform(rng: &mut Rng) -> Float {
+    rng.f64()
+}
+
+/// Uniformly distributed random value in `0..1`. 
+#[cfg(feature = "f32")]
+pub(crate) fn uniform(rng: &mut Rng) -> Float {
+    rng.f32()
+}
+
+/// Normally distributed random value with a mean of `0` and standard deviation of `1`, using the Box-Muller transform. 
+pub(crate) fn gaussian(rng: &mut Rng) -> Float {
+    let u = 1.0 - uniform(rng);
+    let v = uniform(rng);
+    sqrt(-2.0 * ln(u)) * cos(2.0 * PI * v)
+}
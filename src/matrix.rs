@@ -1,5 +1,7 @@
 use fastrand::Rng;
 use super::Float;
+use super::activations::{LayerActivation, Siren};
+use super::math::uniform;
 use core::fmt;
 
 
@@ -11,6 +13,8 @@ pub struct Matrix<const ROWS: usize, const COLS: usize> {
 	pub data: [[Float; COLS]; ROWS],
 }
 
+// Matrix maths reads more clearly with explicit row/column indices. 
+#[allow(clippy::needless_range_loop)]
 impl<const ROWS: usize, const COLS: usize> Matrix<ROWS, COLS> {
 	/// Initializes a matrix with all zeros. 
 	pub fn zeros() -> Matrix<ROWS, COLS> {
@@ -19,30 +23,17 @@ impl<const ROWS: usize, const COLS: usize> Matrix<ROWS, COLS> {
 		}
 	}
 
-	#[cfg(not(feature = "f32"))]
 	pub fn random() -> Matrix<ROWS, COLS> {
-		let mut rng = Rng::with_seed(SEED);
-		let mut data = [[0.0; COLS]; ROWS];
-
-		for row in 0..ROWS {
-			for col in 0..COLS {
-				data[row][col] = rng.f64() * 2.0 - 1.0;
-			}
-		}
-
-		Matrix {
-			data
-		}
+		Matrix::random_with(&mut Rng::with_seed(SEED))
 	}
 
-	#[cfg(feature = "f32")]
-	pub fn random() -> Matrix<ROWS, COLS> {
-		let mut rng = Rng::with_seed(SEED);
+	/// Initializes a matrix with values uniformly distributed in `-1..1` drawn from `rng`, giving reproducible values for a seeded generator. 
+	pub fn random_with(rng: &mut Rng) -> Matrix<ROWS, COLS> {
 		let mut data = [[0.0; COLS]; ROWS];
 
 		for row in 0..ROWS {
 			for col in 0..COLS {
-				data[row][col] = rng.f32() * 2.0 - 1.0;
+				data[row][col] = uniform(rng) * 2.0 - 1.0;
 			}
 		}
 
@@ -51,6 +42,22 @@ impl<const ROWS: usize, const COLS: usize> Matrix<ROWS, COLS> {
 		}
 	}
 
+	/// Initializes a matrix for use as the weights of a SIREN style network using [Sine](crate::activations::Sine) activations. 
+	/// 
+	/// Values are uniformly distributed within `±1 / COLS` for the first layer, and `±sqrt(6 / COLS) / omega` for 
+	/// subsequent layers, keeping the distribution of activations stable through the network. 
+	/// 
+	/// # Parameters 
+	/// * `rng` The generator the values are drawn from. 
+	/// * `first` Whether these are the weights of the first layer. 
+	/// * `omega` The frequency scale used by the sine activations, commonly `30.0`. 
+	pub fn siren(rng: &mut Rng, first: bool, omega: Float) -> Matrix<ROWS, COLS> {
+		let siren = if first { Siren::first(omega) } else { Siren::hidden(omega) };
+		let limit = siren.weight_limit(COLS).unwrap_or(1.0);
+
+		Matrix::random_with(rng).map(&|x| x * limit)
+	}
+
 	/// Will multiply with another matrix with number of rows equal to the number of rows as to this matrix's cols. 
 	pub fn multiply<const OTHER_COLS: usize>(&self, other: &Matrix<COLS, OTHER_COLS>) -> Matrix<ROWS, OTHER_COLS> {
 
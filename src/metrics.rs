@@ -0,0 +1,299 @@
+use super::math::sqrt;
+use super::Float;
+#[cfg(feature = "std")]
+use std::{vec, vec::Vec};
+
+
+/// Counts of how often each class is predicted as each other class, from which the precision, recall and F1 score 
+/// of each class are found, telling more than the accuracy alone when some classes are far rarer than others. 
+/// 
+/// # Type Parameters
+/// * `CLASSES` The number of classes. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::metrics::ConfusionMatrix;
+/// 
+/// // Eight good parts and two defects, one defect missed and one good part flagged. 
+/// let mut confusion = ConfusionMatrix::<2>::new();
+/// for (actual, predicted) in [(0, 0), (0, 0), (0, 0), (0, 0), (0, 0), (0, 0), (0, 0), (0, 1), (1, 1), (1, 0)] {
+///     confusion.record(actual, predicted);
+/// }
+/// 
+/// assert_eq!(confusion.accuracy(), 0.8);
+/// assert_eq!(confusion.precision(1), 0.5);
+/// assert_eq!(confusion.recall(1), 0.5);
+/// assert_eq!(confusion.f1(1), 0.5);
+/// assert!(confusion.macro_f1() < confusion.accuracy());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConfusionMatrix<const CLASSES: usize> {
+    /// The number of samples of each actual class, by row, predicted as each class, by column. 
+    pub counts: [[usize; CLASSES]; CLASSES]
+}
+
+impl <const CLASSES: usize> ConfusionMatrix<CLASSES> {
+
+    /// Instantiates a matrix with nothing recorded. 
+    pub const fn new() -> ConfusionMatrix<CLASSES> {
+        ConfusionMatrix {
+            counts: [[0; CLASSES]; CLASSES]
+        }
+    }
+
+    /// Records a sample of the class `actual` predicted as the class `predicted`, ignored should either be out of range. 
+    pub fn record(&mut self, actual: usize, predicted: usize) {
+        if let Some(count) = self.counts.get_mut(actual).and_then(|row| row.get_mut(predicted)) {
+            *count += 1;
+        }
+    }
+
+    /// The number of samples recorded. 
+    pub fn total(&self) -> usize {
+        self.counts.iter().flatten().sum()
+    }
+
+    /// The fraction of the samples predicted as their actual class, `0` when nothing is recorded. 
+    pub fn accuracy(&self) -> Float {
+        let correct: usize = (0..CLASSES).map(|class| self.counts[class][class]).sum();
+        ratio(correct, self.total())
+    }
+
+    /// The fraction of the samples predicted as the class that actually belong to it, `0` when none are predicted 
+    /// as it. 
+    pub fn precision(&self, class: usize) -> Float {
+        if class >= CLASSES {
+            return 0.0;
+        }
+        let predicted: usize = self.counts.iter().map(|row| row[class]).sum();
+        ratio(self.counts[class][class], predicted)
+    }
+
+    /// The fraction of the samples actually belonging to the class that are predicted as it, `0` when none belong 
+    /// to it. 
+    pub fn recall(&self, class: usize) -> Float {
+        let Some(row) = self.counts.get(class) else { return 0.0 };
+        ratio(row[class], row.iter().sum())
+    }
+
+    /// The F1 score of the class, the harmonic mean of its [precision](ConfusionMatrix::precision) and 
+    /// [recall](ConfusionMatrix::recall), `0` when both are. 
+    pub fn f1(&self, class: usize) -> Float {
+        let (precision, recall) = (self.precision(class), self.recall(class));
+        if precision + recall == 0.0 {
+            return 0.0;
+        }
+        2.0 * precision * recall / (precision + recall)
+    }
+
+    /// The precision averaged over the classes, each counting equally however rare. 
+    pub fn macro_precision(&self) -> Float {
+        self.macro_average(Self::precision)
+    }
+
+    /// The recall averaged over the classes, each counting equally however rare. 
+    pub fn macro_recall(&self) -> Float {
+        self.macro_average(Self::recall)
+    }
+
+    /// The F1 score averaged over the classes, each counting equally however rare. 
+    pub fn macro_f1(&self) -> Float {
+        self.macro_average(Self::f1)
+    }
+
+    /// Averages a metric of each class over the classes. 
+    fn macro_average(&self, metric: fn(&Self, usize) -> Float) -> Float {
+        (0..CLASSES).map(|class| metric(self, class)).sum::<Float>() / CLASSES.max(1) as Float
+    }
+}
+
+impl <const CLASSES: usize> Default for ConfusionMatrix<CLASSES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A point on the receiver operating characteristic (ROC) curve of a binary classifier, the rates it flags 
+/// positives and negatives at when scores at or above `threshold` are taken as positive, see [roc_curve]. 
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RocPoint {
+    /// The lowest score taken as positive. 
+    pub threshold: Float,
+    /// The fraction of the negatives taken as positive. 
+    pub false_positive_rate: Float,
+    /// The fraction of the positives taken as positive. 
+    pub true_positive_rate: Float
+}
+
+/// The receiver operating characteristic (ROC) curve of a binary classifier from the score it gives each sample, 
+/// such as its predicted probability or an anomaly detector's reconstruction error, a point for each distinct 
+/// score taken as the threshold, from highest to lowest, following one taking none as positive. 
+/// 
+/// Shows the trade off between catching positives and flagging negatives at every threshold, for picking the 
+/// threshold to use. 
+/// 
+/// # Parameters 
+/// * `scores` The score of each sample, higher being more likely positive. 
+/// * `labels` Whether each sample is actually positive, the same length as `scores`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::metrics::roc_curve;
+/// 
+/// let points = roc_curve(&[0.9, 0.8, 0.4, 0.3], &[true, false, true, false]);
+/// 
+/// assert_eq!(points.len(), 5);
+/// assert_eq!((points[2].threshold, points[2].false_positive_rate, points[2].true_positive_rate), (0.8, 0.5, 0.5));
+/// ```
+#[cfg(feature = "std")]
+pub fn roc_curve(scores: &[Float], labels: &[bool]) -> Vec<RocPoint> {
+    let len = scores.len().min(labels.len());
+    let mut order: Vec<usize> = (0..len).collect();
+    order.sort_unstable_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+    let positives = labels[..len].iter().filter(|label| **label).count();
+    let negatives = len - positives;
+
+    let mut points = vec![RocPoint { threshold: Float::INFINITY, false_positive_rate: 0.0, true_positive_rate: 0.0 }];
+    let (mut true_positives, mut false_positives) = (0, 0);
+    for (rank, &i) in order.iter().enumerate() {
+        if labels[i] {
+            true_positives += 1;
+        } else {
+            false_positives += 1;
+        }
+        // Samples with equal scores are taken as positive together. 
+        if order.get(rank + 1).is_none_or(|&next| scores[next] != scores[i]) {
+            points.push(RocPoint {
+                threshold: scores[i],
+                false_positive_rate: ratio(false_positives, negatives),
+                true_positive_rate: ratio(true_positives, positives)
+            });
+        }
+    }
+    points
+}
+
+/// The area under the [ROC curve](roc_curve) (AUC) of a binary classifier, the chance it scores a random positive 
+/// above a random negative whatever the threshold, `1` being perfect and `0.5` no better than guessing. 
+/// 
+/// # Parameters 
+/// * `scores` The score of each sample, higher being more likely positive. 
+/// * `labels` Whether each sample is actually positive, the same length as `scores`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::metrics::auc;
+/// 
+/// assert_eq!(auc(&[0.9, 0.8, 0.4, 0.3], &[true, true, false, false]), 1.0);
+/// assert_eq!(auc(&[0.9, 0.8, 0.4, 0.3], &[true, false, true, false]), 0.75);
+/// ```
+#[cfg(feature = "std")]
+pub fn auc(scores: &[Float], labels: &[bool]) -> Float {
+    roc_curve(scores, labels).windows(2).map(|pair| {
+        (pair[1].false_positive_rate - pair[0].false_positive_rate) * (pair[1].true_positive_rate + pair[0].true_positive_rate) / 2.0
+    }).sum()
+}
+
+/// Divides two counts, `0` when the denominator is. 
+fn ratio(numerator: usize, denominator: usize) -> Float {
+    if denominator == 0 {
+        return 0.0;
+    }
+    numerator as Float / denominator as Float
+}
+
+/// Sums of the errors of a regression's predictions, from which the mean absolute error, root mean squared error 
+/// and coefficient of determination (R²) are found, the latter averaged over the outputs. 
+/// 
+/// # Type Parameters
+/// * `OUT` The number of outputs predicted. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::Float;
+/// use mynn::metrics::RegressionMetrics;
+/// 
+/// let mut metrics = RegressionMetrics::<1>::new();
+/// for (output, target) in [(1.5, 1.0), (2.0, 2.0), (2.5, 3.0), (4.0, 4.0)] {
+///     metrics.record(&[output], &[target]);
+/// }
+/// 
+/// assert_eq!(metrics.mae(), 0.25);
+/// // The square root is approximate with the `f32` feature. 
+/// assert!((metrics.rmse() - (0.125 as Float).sqrt()).abs() < 0.05);
+/// assert_eq!(metrics.r_squared(), 0.9);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RegressionMetrics<const OUT: usize> {
+    /// The number of predictions recorded. 
+    pub count: usize,
+    /// The sum of the absolute errors of each output. 
+    pub absolute_errors: [Float; OUT],
+    /// The sum of the squared errors of each output. 
+    pub squared_errors: [Float; OUT],
+    /// The running mean of the targets of each output. 
+    pub target_means: [Float; OUT],
+    /// The running sum of the squared differences of the targets of each output from their mean, kept by Welford's 
+    /// algorithm so large offsets don't cancel out the variance. 
+    pub target_deviations: [Float; OUT]
+}
+
+impl <const OUT: usize> RegressionMetrics<OUT> {
+
+    /// Instantiates the sums with nothing recorded. 
+    pub const fn new() -> RegressionMetrics<OUT> {
+        RegressionMetrics {
+            count: 0,
+            absolute_errors: [0.0; OUT],
+            squared_errors: [0.0; OUT],
+            target_means: [0.0; OUT],
+            target_deviations: [0.0; OUT]
+        }
+    }
+
+    /// Records a prediction against its targets. 
+    pub fn record(&mut self, outputs: &[Float; OUT], targets: &[Float; OUT]) {
+        self.count += 1;
+        for i in 0..OUT {
+            let error = outputs[i] - targets[i];
+            self.absolute_errors[i] += error.abs();
+            self.squared_errors[i] += error * error;
+            let delta = targets[i] - self.target_means[i];
+            self.target_means[i] += delta / self.count as Float;
+            self.target_deviations[i] += delta * (targets[i] - self.target_means[i]);
+        }
+    }
+
+    /// The mean absolute error (MAE) over every output, in the units of the targets, `0` when nothing is recorded. 
+    pub fn mae(&self) -> Float {
+        self.absolute_errors.iter().sum::<Float>() / (self.count * OUT).max(1) as Float
+    }
+
+    /// The root mean squared error (RMSE) over every output, in the units of the targets, weighing large errors 
+    /// more than the [MAE](RegressionMetrics::mae), `0` when nothing is recorded. 
+    pub fn rmse(&self) -> Float {
+        sqrt(self.squared_errors.iter().sum::<Float>() / (self.count * OUT).max(1) as Float)
+    }
+
+    /// The coefficient of determination (R²) averaged over the outputs, the fraction of the variance of the targets 
+    /// the predictions explain, `1` being perfect and `0` no better than predicting the mean, negative being worse. 
+    /// Outputs whose targets don't vary are left out, `0` when none do. 
+    pub fn r_squared(&self) -> Float {
+        let mut total = 0.0;
+        let mut varying = 0;
+        for i in 0..OUT {
+            if self.target_deviations[i] > 0.0 {
+                total += 1.0 - self.squared_errors[i] / self.target_deviations[i];
+                varying += 1;
+            }
+        }
+        total / varying.max(1) as Float
+    }
+}
+
+impl <const OUT: usize> Default for RegressionMetrics<OUT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
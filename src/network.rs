@@ -1,133 +1,362 @@
-use super::{activations::Activation, matrix::Matrix};
+use super::{activations::{Hidden, Inherit, LayerActivation, Softmax, StoredActivation}, loss::{Head, Loss, Upstream, Weighted}, matrix::Matrix, metrics::{ConfusionMatrix, RegressionMetrics}, optimizers::{Optimizer, OptimizerState, Sgd}, preprocessing::argmax_label, training::{GradientStep, HebbianRule, TrainError, TrainOptions}};
+use super::math::{gaussian, sqrt, uniform};
 use super::Float;
 use core::fmt;
+use fastrand::Rng;
 
 /// Generic type for all layers in a neural network defining standard const parameter and behavior. 
 /// 
+/// Each layer holds the next layer in the network, so different kinds of layer, such as a [ProcessLayer] and 
+/// a [DropoutLayer], can be mixed freely as long as the number of values one passes on matches the next. 
+/// 
 /// # Type Parameters
-/// * `NEURONS` The number of neurons in that layer. 
+/// * `NEURONS` The number of neurons in that layer, the number of values it's fed. 
 /// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// 
+/// # Implementing a layer 
+/// Layers can be written outside this crate, each one holds the next layer, calls on to it in every method then 
+/// does its own part. A layer with parameters adds their gradients to its part of the [Snapshot](Layer::Snapshot) 
+/// it's given during [back_propagate](Layer::back_propagate), then applies them with [update_params] once 
+/// [apply_gradients](Layer::apply_gradients) is called along with its part of the optimizer state. Both are kept 
+/// by whatever trains the network rather than by the layer, so a network only used to predict holds nothing more 
+/// than its parameters and the values cached by its last feed forward. The parameters are copied to and from a 
+/// snapshot through [visit](Layer::visit) unless a layer does so itself. Once implemented every [Network] method 
+/// can be used, and the layer placed within [make_network](crate::make_network) with `[constructor]`. A layer 
+/// without parameters need only implement [Transform], placed within a [TransformLayer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::{LayerActivation, SIGMOID};
+/// use mynn::loss::{Loss, MSE};
+/// use mynn::matrix::Matrix;
+/// use mynn::network::{update_params, BackProps, Layer, Network, Visitor, VisitorMut};
+/// use mynn::optimizers::{Optimizer, OptimizerState};
+/// use mynn::training::GradientStep;
+/// use mynn::fastrand::Rng;
+/// 
+/// // Multiplies each value by its own learned factor. 
+/// #[derive(Debug)]
+/// struct ScaleLayer<const N: usize, const END_S: usize, T: Layer<N, END_S>> {
+///     next: T,
+///     scales: [Float; N],
+///     inputs: [Float; N]
+/// }
+/// 
+/// impl<const N: usize, const END_S: usize, T: Layer<N, END_S>> ScaleLayer<N, END_S, T> {
+///     fn new(next: T) -> Self {
+///         ScaleLayer { next, scales: [1.0; N], inputs: [0.0; N] }
+///     }
+/// }
+/// 
+/// impl<const N: usize, const END_S: usize, T: Layer<N, END_S>> Layer<N, END_S> for ScaleLayer<N, END_S, T> {
+///     const OUTPUTS: usize = N;
+///     const PARAMS: usize = N + T::PARAMS;
+/// 
+///     fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<N, 1>, act: &A) -> [Float; END_S] {
+///         self.inputs = feed.transpose().data[0];
+///         let scales = Matrix::from([self.scales]).transpose();
+///         self.next.feed_forward(feed.dot_multiply(&scales), act)
+///     }
+/// 
+///     fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<N> {
+///         let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.1);
+///         for ((gradient, input), [error]) in gradients.0.iter_mut().zip(self.inputs).zip(errors.data) {
+///             *gradient -= error * input;
+///         }
+///         BackProps(errors.dot_multiply(&Matrix::from([self.scales]).transpose()))
+///     }
+/// 
+///     fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+///         update_params(optimizer, step, &mut self.scales, &mut gradients.0, &mut state.0, false);
+///         self.next.apply_gradients(optimizer, step, &mut gradients.1, &mut state.1);
+///     }
+/// 
+///     fn init_params(&mut self, rng: &mut Rng) {
+///         self.scales = [1.0; N];
+///         self.next.init_params(rng);
+///     }
+/// 
+///     fn set_training(&mut self, training: bool) {
+///         self.next.set_training(training);
+///     }
+/// 
+///     fn clear_state(&mut self) {
+///         self.next.clear_state();
+///     }
+/// 
+///     fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+///         visitor.layer("scale", N, N);
+///         visitor.params("scales", &self.scales);
+///         self.next.visit(visitor);
+///     }
+/// 
+///     fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+///         visitor.layer("scale", N, N);
+///         visitor.params("scales", &mut self.scales);
+///         self.next.visit_mut(visitor);
+///     }
+/// 
+///     type Snapshot = ([Float; N], T::Snapshot);
+/// 
+///     type StateSnapshot = ([OptimizerState; N], T::StateSnapshot);
+/// }
+/// 
+/// let mut network = make_network!(2, [ScaleLayer::new], 3 => SIGMOID, 1 => SIGMOID).with_seed(2);
+/// network.train(0.5, [[0.0, 1.0], [1.0, 0.0]], [[1.0], [0.0]], 500, &SIGMOID, &MSE);
+/// 
+/// // The scales are saved and restored through `visit` along with every other parameter. 
+/// let trained = network.snapshot();
+/// network.randomize(&mut Rng::with_seed(3));
+/// network.restore(&trained);
+/// assert_eq!(network.snapshot(), trained);
+/// assert_eq!(network.scales, trained.0);
+/// 
+/// let mut errors = [0.0; 3];
+/// let max = network.grad_check([0.0, 1.0], [1.0], &SIGMOID, &MSE, 1e-5, &mut errors);
+/// #[cfg(not(feature = "f32"))]
+/// assert!(max < 1e-4);
+/// ```
 pub trait Layer<const NEURONS: usize, const END_S: usize>: fmt::Debug {
 
+    /// Whether this layer terminates the network, used to tell the output layer apart from the hidden layers. 
+    /// 
+    /// Layers that pass their values on unchanged while predicting, such as a [DropoutLayer], may use `T::IS_END` 
+    /// so the layer before them is still treated as the output layer. 
+    const IS_END: bool = false;
+
+    /// The number of values this layer passes on to the next layer. 
+    const OUTPUTS: usize;
+
+    /// The number of trainable parameters of this and the following layers. 
+    const PARAMS: usize;
+
     /// Feeds forward data and returns (I.E. predicts) an array of data based on it's current learned state. 
     /// 
     /// # Parameters 
     /// * `feed` The data to be predicted upon, a matrix with 1 column and number of rows equal to the number of neurons. 
-    /// * `act` The Activation function to be used. 
-    fn feed_forward<'a>(&mut self, feed: Matrix<NEURONS, 1>, act: &Activation<'a>) -> [Float; END_S];
+    /// * `act` The activation function to be used, applied to each layer's values at once. 
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<NEURONS, 1>, act: &A) -> [Float; END_S];
 
-    // Back propagates (I.E. makes corrections or "learns") based on the previous outputs and the expected outputs. 
-    // 
-    // # Parameters 
-    // * `l_rate` The learning rate, is multiplied with the calculated difference gradient to allow for smaller/greater changes per learning revision. 
-    // * `outputs` The outputs from the previous prediction. 
-    // * `targets` The actual targeted value for the previous prediction. 
-    // * `act` The activation function. 
-    fn back_propagate<'a>(&mut self, l_rate: Float, outputs: [Float; END_S], targets: [Float; END_S], act: &Activation<'a>) -> BackProps<NEURONS>;
-}
+    /// Back propagates based on the previous outputs and the expected outputs, adding the gradients of each layer's 
+    /// parameters to those gathered so far in `gradients`. 
+    /// 
+    /// A layer calls the next layer's `back_propagate` first, then works back from the errors it returns, these being 
+    /// the negative of the loss gradient. A layer applying an activation skips its derivative when the next layer 
+    /// [is the end](Layer::IS_END) and the loss is [fused](Loss::fused). 
+    /// 
+    /// Returns the errors with respect to the data this layer was fed, for the previous layer to continue from. 
+    /// 
+    /// # Parameters 
+    /// * `outputs` The outputs from the previous prediction. 
+    /// * `targets` The actual targeted value for the previous prediction. 
+    /// * `act` The activation function, its derivative is taken of the pre-activation values cached during the feed forward. 
+    /// * `loss` The loss function, its gradient gives the errors of the final layer. 
+    /// * `gradients` The gradients gathered so far, one for each parameter of this and the following layers. 
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<NEURONS>;
 
+    /// Applies the gathered gradients to the parameters of this and the following layers (I.E. makes corrections or "learns"), 
+    /// clearing them, [update_params] does the work for a single set of parameters. 
+    /// 
+    /// # Parameters 
+    /// * `optimizer` The update rule, see [Optimizer]. 
+    /// * `step` The learning rate and other settings for this update. 
+    /// * `gradients` The gradients gathered by [back_propagate](Layer::back_propagate), zeroed afterwards. 
+    /// * `state` The optimizer's state for each parameter, kept from one update to the next. 
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot);
 
-/// Type for an active (I.E. containing neurons) layer. 
-/// 
-/// Has type bounds to ensure the next layer must have equal number of neurons as there are rows in the weights and biases matrices. 
-/// 
-/// # Type Parameters
-/// * `ROWS` The number of rows in the weights, biases, and number of neurons that must be in the next layer. 
-/// * `NEURONS` The number of neurons (number of columns in the weights matrix) in this layer. 
-/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
-/// * `T` The type of the next layer, must implement [Layer]. 
-pub struct ProcessLayer<const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>> {
-    /// The next layer. 
-    pub next: T,
-    pub weights: Matrix<ROWS, NEURONS>,
-    pub biases: Matrix<ROWS, 1>,
-    /// The data that was last passed in during a feed forward, used to make corrections during back propagation. 
-    pub data: Matrix<NEURONS, 1>
-}
+    /// Initializes the parameters of this and the following layers with random values drawn from `rng`, dense layers 
+    /// zero their biases. 
+    fn init_params(&mut self, rng: &mut Rng);
 
-impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>> fmt::Debug for ProcessLayer<ROWS, NEURONS, END_S, T> {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.debug_struct("")
-            .field("\"weights\"", &self.weights)
-            .field("\"biases\"", &self.biases)
-            .field("\"next\"", &self.next)
-            .finish()
+    /// Switches this and the following layers between training and inference, for layers such as dropout that 
+    /// only act while training. 
+    fn set_training(&mut self, training: bool);
+
+    /// Clears any state this and the following layers carry from one feed forward to the next. 
+    fn clear_state(&mut self);
+
+    /// Enters this layer on `visitor` with a short name for its kind, passing it each of the layer's sets of 
+    /// parameters, then visits the following layers, see [Visitor]. 
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V);
+
+    /// Visits this and the following layers as [visit](Layer::visit) does, with the parameters given mutably, see [VisitorMut]. 
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V);
+
+    /// Plain copy of the parameters of this and the following layers, usually a struct nesting `T::Snapshot`, also 
+    /// holding the gradients gathered for each parameter while training. 
+    type Snapshot: ParamSets<Float>;
+
+    /// Copies the parameters of this and the following layers into a [Layer::Snapshot], by default gathering each 
+    /// set of the snapshot from those [visited](Layer::visit). 
+    fn save_params(&self) -> Self::Snapshot {
+        let mut snapshot = Self::Snapshot::filled(0.0);
+        let mut start = 0;
+        snapshot.each_set_mut(&mut |set: &mut [Float]| {
+            self.visit(&mut Gather { params: set, weight: 1.0, start, index: 0 });
+            start += set.len();
+        });
+        snapshot
     }
+
+    /// Copies the parameters of this and the following layers back from a [Layer::Snapshot], by default into each 
+    /// set [visited](Layer::visit_mut) in turn. 
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.visit_mut(&mut Scatter { snapshot, index: 0 });
+    }
+
+    /// Plain copy of the optimizer's state for each parameter of this and the following layers, shaped as a 
+    /// [Layer::Snapshot] is. 
+    type StateSnapshot: ParamSets<OptimizerState>;
 }
 
-impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>> ProcessLayer<ROWS, NEURONS, END_S, T> {
 
-    /// Instantiates a new layer, accepts the next layer in the linked list as a parameter. 
+/// The prediction and training behaviour of a neural network, implemented for every [Layer] so a network may 
+/// start with any kind of layer. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of inputs to the network. 
+/// * `END_S` The number of outputs from the network. 
+pub trait Network<const NEURONS: usize, const END_S: usize>: Layer<NEURONS, END_S> + Sized {
+
+    /// The number of trainable parameters of the network, known at compile time. 
     /// 
     /// # Example 
     /// ```
-    /// use mynn::network::{ProcessLayer, EndLayer};
+    /// use mynn::network::{EndLayer, Network, ProcessLayer};
     /// 
-    /// let network: ProcessLayer::<3, 2, 1, ProcessLayer<1, 3, 1, EndLayer<1>>> = ProcessLayer::new(ProcessLayer::new(EndLayer()));
+    /// type Xor = ProcessLayer<3, 2, 1, ProcessLayer<1, 3, 1, EndLayer<1>>>;
+    /// 
+    /// const _: () = assert!(Xor::PARAM_COUNT == 2 * 3 + 3 + 3 * 1 + 1);
     /// ```
-    pub fn new(next: T) -> ProcessLayer<ROWS, NEURONS, END_S, T> {
-        ProcessLayer {
-            next,
-            weights: Matrix::zeros(),
-            biases: Matrix::zeros(),
-            data: Matrix::zeros(),
-        }
-    }
+    const PARAM_COUNT: usize = Self::PARAMS;
 
-    /// Instantiates a new layer, accepts the next layer in the linked list as a parameter and also the weights and biases to be used. 
+    /// The number of bytes of memory the network occupies, known at compile time, the parameters along with the 
+    /// values cached by the last feed forward. The gradients and optimizer state used while training are held 
+    /// apart for the length of a training run, see [train_with_state](Network::train_with_state). 
+    const RAM_BYTES: usize = core::mem::size_of::<Self>();
+
+    /// Consumes the network, returning it unchanged, failing to compile should it occupy more than `MAX_BYTES` 
+    /// bytes of memory, see [RAM_BYTES](Network::RAM_BYTES), such as to check a model fits a microcontroller. 
     /// 
-    /// Useful for instantiating pre-trained networks, will likely be used in later revisions to easily store-and-recall models.  
+    /// # Type Parameters
+    /// * `MAX_BYTES` The most bytes of memory the network may occupy. 
     /// 
     /// # Example 
     /// ```
-    /// use mynn::network::{EndLayer, ProcessLayer};
-    /// use mynn::activations::SIGMOID;
+    /// use mynn::make_network;
+    /// use mynn::network::Network;
     /// 
-    /// let first_layer_weights = [[-8.086764, -8.086563],[-10.876657, -10.877184],[10.14248, 10.143111]];
-    /// let first_layer_biases = [3.3848374, 4.80076, -15.381532];
-    /// let second_layer_weights = [[-2.4123971, -6.627293, -8.613715]];
-    /// let second_layer_biases = [4.3186426];
+    /// let network = make_network!(2, 3, 1).assert_fits::<2048>();
+    /// ```
     /// 
-    /// let mut network: ProcessLayer<3, 2, 1, ProcessLayer<1, 3, 1, EndLayer<1>>> = 
-    ///     ProcessLayer::new_with(
-    ///         ProcessLayer::new_with(EndLayer(), second_layer_weights, second_layer_biases), 
-    ///         first_layer_weights, 
-    ///         first_layer_biases
-    ///     );
+    /// ```compile_fail
+    /// use mynn::make_network;
+    /// use mynn::network::Network;
     /// 
-    /// network.predict([1.0, 1.0], &SIGMOID);
+    /// let network = make_network!(64, 64, 1).assert_fits::<2048>();
     /// ```
-    pub fn new_with(next: T, weights: [[Float; NEURONS]; ROWS], biases: [Float; ROWS]) -> ProcessLayer<ROWS, NEURONS, END_S, T> {
-        ProcessLayer {
-            next,
-            weights: Matrix::from(weights),
-            biases: Matrix::from([biases]).transpose(),
-            data: Matrix::zeros(),
-        }
+    fn assert_fits<const MAX_BYTES: usize>(self) -> Self {
+        const { assert!(Self::RAM_BYTES <= MAX_BYTES, "the network occupies more than MAX_BYTES bytes") };
+        self
     }
 
     /// Accepts an array of data, feeding it forward down each layer, returning the predicted result based on the current learned state. 
     /// 
     /// # Parameters 
     /// * `data` The data for the prediction to be made upon, must have equal number of values as neurons in the first layer. 
-    /// * `act` The activation function to be used. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead, see [Split](crate::activations::Split) for a distinct output activation. 
     /// 
     /// # Example 
     /// ```
     /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
     /// 
     /// let inputs = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
     /// let targets = [[0.0], [0.0], [0.0], [1.0]];
     /// let mut network = make_network!(2, 3, 1);
     /// 
-    /// network.train(0.5, inputs, targets, 10_000, &SIGMOID);
+    /// network.train(0.5, inputs, targets, 10_000, &SIGMOID, &MSE);
     /// 
     /// println!("1 and 1: {:?}", network.predict([1.0, 1.0], &SIGMOID));
     /// ```
-    pub fn predict<'a>(&mut self, data: [Float; NEURONS], act: &Activation<'a>) -> [Float; END_S] {
+    fn predict<F: LayerActivation + ?Sized>(&mut self, data: [Float; NEURONS], act: &F) -> [Float; END_S] {
         self.feed_forward(Matrix::from([data]).transpose(), act)
     }
 
+    /// Predicts the class of the data, the index of the largest output, such as the most likely class given by a 
+    /// [SoftmaxLayer]. 
+    /// 
+    /// # Parameters 
+    /// * `data` The data for the prediction to be made upon. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    fn classify<F: LayerActivation + ?Sized>(&mut self, data: [Float; NEURONS], act: &F) -> usize {
+        argmax_label(&self.predict(data, act))
+    }
+
+    /// Predicts the `K` most likely classes of the data, the indices of the largest outputs along with their values, 
+    /// largest first, ties going to the lower index as in [classify](Network::classify) and NaN outputs ranking last. 
+    /// Fails to compile should `K` exceed the number of outputs. 
+    /// 
+    /// # Type Parameters
+    /// * `K` The number of classes to give. 
+    /// 
+    /// # Parameters 
+    /// * `data` The data for the prediction to be made upon. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::activations::IDENTITY;
+    /// use mynn::network::{EndLayer, Network, ProcessLayer};
+    /// 
+    /// // Scores four command words as 0.1, 0.7, 0.2 and 0.7. 
+    /// let mut network = ProcessLayer::<4, 1, 4, EndLayer<4>>::new_with(EndLayer(), [[0.0]; 4], [0.1, 0.7, 0.2, 0.7]);
+    /// 
+    /// assert_eq!(network.top_k::<2, _>([0.0], &IDENTITY), [(1, 0.7), (3, 0.7)]);
+    /// assert_eq!(network.top_k::<3, _>([0.0], &IDENTITY)[2], (2, 0.2));
+    /// ```
+    fn top_k<const K: usize, F: LayerActivation + ?Sized>(&mut self, data: [Float; NEURONS], act: &F) -> [(usize, Float); K] {
+        const { assert!(K <= END_S, "K must not exceed the number of outputs") };
+        let outputs = self.predict(data, act);
+        let rank = |value: Float| if value.is_nan() { Float::NEG_INFINITY } else { value };
+        let mut top = [(0, Float::NAN); K];
+        let mut held = 0;
+        for (i, &value) in outputs.iter().enumerate() {
+            let position = top[..held].iter().take_while(|(_, kept)| rank(*kept) >= rank(value)).count();
+            if position < K {
+                top.copy_within(position..K - 1, position + 1);
+                top[position] = (i, value);
+                held = (held + 1).min(K);
+            }
+        }
+        top
+    }
+
+    /// Predicts the reconstruction of the data by a network trained to reproduce its inputs, such as one built from 
+    /// an [AutoencoderLayer], fails to compile should the number of outputs not match the number of inputs. 
+    /// 
+    /// # Parameters 
+    /// * `data` The data to be reconstructed. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    fn reconstruct<F: LayerActivation + ?Sized>(&mut self, data: [Float; NEURONS], act: &F) -> [Float; END_S] {
+        const { assert!(NEURONS == END_S, "a reconstruction must have as many outputs as inputs") };
+        self.predict(data, act)
+    }
+
+    /// The loss between the data and its [reconstruction](Network::reconstruct), a measure of how unlike the data 
+    /// trained upon it is, used to detect anomalies. 
+    /// 
+    /// # Parameters 
+    /// * `data` The data to be reconstructed. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function measuring the difference. 
+    fn reconstruction_error<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, data: [Float; NEURONS], act: &F, loss: &L) -> Float {
+        let outputs = self.reconstruct(data, act);
+        loss.loss(&outputs, &data)
+    }
+
     /// Trains a neural network list, accepts 2 arrays of equal length with the data and expected results. 
     /// 
     /// # Parameters 
@@ -135,70 +364,4350 @@ impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS
     /// * `inputs` Array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
     /// * `targets` Array of targets, each index in this array must correspond with the same index in the `inputs`. 
     /// * `epochs` Number of epochs (feeding forward/predicting and then back propagating/learning).
-    /// * `act` The activation function. 
-    pub fn train<'a, const DATA_S: usize>(&mut self, l_rate: Float, inputs: [[Float; NEURONS]; DATA_S], targets: [[Float; END_S]; DATA_S], epochs: usize, act: &Activation<'a>) {
-        for _ in 1..=epochs {
-            for i in 0..DATA_S {
-                let outputs = self.feed_forward(Matrix::from([inputs[i]]).transpose(), act);
-                self.back_propagate(l_rate, outputs, targets[i].clone(), act);
-            }
+    /// * `act` The activation function, layers owning their own activation use theirs instead, see [Split](crate::activations::Split) for a distinct output activation. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 3, 1);
+    /// let stats = network.train(0.5, [[0.0, 1.0], [1.0, 1.0]], [[1.0], [0.0]], 1_000, &SIGMOID, &MSE);
+    /// 
+    /// assert_eq!(stats.epochs, 1_000);
+    /// assert!(stats.average_loss < 0.01);
+    /// ```
+    fn train<F: LayerActivation + ?Sized, L: Loss + ?Sized, const DATA_S: usize>(&mut self, l_rate: Float, inputs: [[Float; NEURONS]; DATA_S], targets: [[Float; END_S]; DATA_S], epochs: usize, act: &F, loss: &L) -> TrainStats {
+        self.train_with(&inputs, &targets, act, loss, TrainOptions::new(l_rate, epochs))
+    }
+
+    /// Trains a neural network list as [train](Network::train) does, with the learning rate, epochs and 
+    /// other behaviour taken from [TrainOptions]. 
+    /// 
+    /// The dataset is borrowed so large arrays kept in `static` memory are not copied onto the stack. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// * `options` The options for the training run. 
+    fn train_with<F: LayerActivation + ?Sized, L: Loss + ?Sized, const DATA_S: usize>(&mut self, inputs: &[[Float; NEURONS]; DATA_S], targets: &[[Float; END_S]; DATA_S], act: &F, loss: &L, options: TrainOptions<NEURONS, END_S>) -> TrainStats {
+        train_array(self, inputs, targets, act, loss, options, &mut ParamSets::filled([0.0; 2])).0
+    }
+
+    /// Trains a neural network list as [train_with](Network::train_with) does, the optimizer's state for every 
+    /// weight and bias kept in `state` rather than started afresh, so a long training run can be resumed such as 
+    /// after a power cycle by saving it alongside a [snapshot](Network::snapshot). 
+    /// 
+    /// # Parameters 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// * `options` The options for the training run. 
+    /// * `state` The optimizer's state, [filled](ParamSets::filled) with `[0.0; 2]` before the first run. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::loss::MSE;
+    /// use mynn::optimizers::Momentum;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::{Network, ParamSets};
+    /// 
+    /// let mut optimizer = Momentum::new(0.9);
+    /// let mut network = make_network!(2, 3, 1).with_seed(1);
+    /// let mut state = ParamSets::filled([0.0; 2]);
+    /// network.train_with_state(&[[0.0, 1.0]], &[[1.0]], &SIGMOID, &MSE, TrainOptions::new(0.5, 10).optimizer(&mut optimizer), &mut state);
+    /// let (params, mut saved) = (network.snapshot(), state.clone());
+    /// 
+    /// let mut resumed = make_network!(2, 3, 1);
+    /// resumed.restore(&params);
+    /// 
+    /// network.train_with_state(&[[0.0, 1.0]], &[[1.0]], &SIGMOID, &MSE, TrainOptions::new(0.5, 10).optimizer(&mut optimizer), &mut state);
+    /// resumed.train_with_state(&[[0.0, 1.0]], &[[1.0]], &SIGMOID, &MSE, TrainOptions::new(0.5, 10).optimizer(&mut optimizer), &mut saved);
+    /// assert_eq!(network.snapshot(), resumed.snapshot());
+    /// ```
+    fn train_with_state<F: LayerActivation + ?Sized, L: Loss + ?Sized, const DATA_S: usize>(&mut self, inputs: &[[Float; NEURONS]; DATA_S], targets: &[[Float; END_S]; DATA_S], act: &F, loss: &L, options: TrainOptions<NEURONS, END_S>, state: &mut Self::StateSnapshot) -> TrainStats {
+        train_array(self, inputs, targets, act, loss, options, state).0
+    }
+
+    /// Trains a neural network list as [train_with](Network::train_with) does, checking for NaN or infinite values, 
+    /// stopping with an error as soon as a loss or, at the end of an epoch, a weight or bias is no longer finite. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// * `options` The options for the training run. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::IDENTITY};
+    /// use mynn::loss::MSE;
+    /// use mynn::training::{TrainError, TrainOptions};
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(1, 1).with_seed(1);
+    /// 
+    /// // A far too high learning rate diverges. 
+    /// let result = network.try_train_with(&[[100.0]], &[[1.0]], &IDENTITY, &MSE, TrainOptions::new(10.0, 1_000));
+    /// 
+    /// assert!(matches!(result, Err(TrainError::NonFiniteLoss { .. } | TrainError::NonFiniteParams { .. })));
+    /// ```
+    fn try_train_with<F: LayerActivation + ?Sized, L: Loss + ?Sized, const DATA_S: usize>(&mut self, inputs: &[[Float; NEURONS]; DATA_S], targets: &[[Float; END_S]; DATA_S], act: &F, loss: &L, mut options: TrainOptions<NEURONS, END_S>) -> Result<TrainStats, TrainError> {
+        options.check_finite = true;
+        match train_array(self, inputs, targets, act, loss, options, &mut ParamSets::filled([0.0; 2])) {
+            (_, Some(error)) => Err(error),
+            (stats, None) => Ok(stats)
         }
     }
 
-}
+    /// Trains a neural network list until `until` returns `false`, it's called after each epoch with the epoch number 
+    /// and the average loss of that epoch, for time budgets, target losses or interruptible training. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// * `until` Predicate deciding whether to keep training. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// let targets = [[0.0], [0.0], [0.0], [1.0]];
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let stats = network.train_until(0.5, &inputs, &targets, &SIGMOID, &MSE, |epoch, loss| epoch < 50_000 && loss > 0.001);
+    /// 
+    /// assert!(stats.average_loss <= 0.001);
+    /// ```
+    fn train_until<F, L, U, const DATA_S: usize>(&mut self, l_rate: Float, inputs: &[[Float; NEURONS]; DATA_S], targets: &[[Float; END_S]; DATA_S], act: &F, loss: &L, mut until: U) -> TrainStats
+    where 
+        F: LayerActivation + ?Sized, 
+        L: Loss + ?Sized, 
+        U: FnMut(usize, Float) -> bool
+    {
+        self.train_with(inputs, targets, act, loss, TrainOptions::new(l_rate, usize::MAX).callback(&mut until))
+    }
 
-impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>> Layer<NEURONS, END_S> for ProcessLayer<ROWS, NEURONS, END_S, T> {
-    fn feed_forward<'a>(&mut self, feed: Matrix<NEURONS, 1>, act: &Activation<'a>) -> [Float; END_S] {
-        self.data = feed;
-        let result = self.weights.multiply(&self.data)
-            .add(&self.biases)
-            .map(act.function);
-        self.next.feed_forward(result, act)
+    /// Trains a neural network list as [train_with](Network::train_with) does, with the samples streamed from an 
+    /// iterator rather than held in arrays, such as from flash or generated on the fly. 
+    /// 
+    /// The iterator is cloned at the start of each epoch to iterate the samples again, the `shuffle` option is ignored. 
+    /// 
+    /// # Parameters 
+    /// * `samples` Iterator of references to each input and its target. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// * `options` The options for the training run. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID, Float};
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// static INPUTS: [[Float; 2]; 4] = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// static TARGETS: [[Float; 1]; 4] = [[0.0], [0.0], [0.0], [1.0]];
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let stats = network.train_iter(INPUTS.iter().zip(TARGETS.iter()), &SIGMOID, &MSE, TrainOptions::new(0.5, 2_000));
+    /// 
+    /// assert!(stats.average_loss < 0.01);
+    /// ```
+    fn train_iter<'d, I, F, L>(&mut self, samples: I, act: &F, loss: &L, options: TrainOptions<NEURONS, END_S>) -> TrainStats
+    where 
+        I: Iterator<Item = (&'d [Float; NEURONS], &'d [Float; END_S])> + Clone,
+        F: LayerActivation + ?Sized, 
+        L: Loss + ?Sized
+    {
+        let samples = |_: &mut TrainOptions<NEURONS, END_S>| samples.clone().map(|(input, target)| (input, target, 1.0));
+        train_samples(self, samples, act, loss, options, &mut ParamSets::filled([0.0; 2])).0
     }
 
-    fn back_propagate<'a>(&mut self, l_rate: Float, outputs: [Float; END_S], targets: [Float; END_S], act: &Activation<'a>) -> BackProps<NEURONS> {
-        let BackProps(errors, gradients) = self.next.back_propagate(l_rate, outputs, targets, act);
-        let gradients = gradients.dot_multiply(&errors).map(&|x| x * l_rate);
+    /// Checks the gradients found by back propagation against central finite differences for a single sample, 
+    /// catching mistakes in activation derivatives, losses and layer implementations. 
+    /// 
+    /// Writes the largest relative error of each layer's parameters into `errors`, as many layers as it holds, 
+    /// returning the largest across all layers, commonly below `1e-4` when the gradients are correct. 
+    /// 
+    /// Only meaningful with [f64], with the `f32` feature the approximate `micromath` functions leave relative errors 
+    /// as large as `0.2` even for correct gradients. 
+    /// 
+    /// # Parameters 
+    /// * `input` The sample's input. 
+    /// * `target` The target for the sample. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to check the gradients of. 
+    /// * `epsilon` The distance each parameter is moved either side to estimate its gradient, commonly `1e-5`. 
+    /// * `errors` Receives the largest relative error of each layer. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::{SIGMOID, SOFTMAX}};
+    /// use mynn::loss::{MSE, SOFTMAX_CROSS_ENTROPY};
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 4 => SIGMOID, 3 => SOFTMAX).with_seed(3);
+    /// let mut errors = [0.0; 2];
+    /// 
+    /// let max = network.grad_check([0.5, -1.0], [0.0, 1.0, 0.0], &SIGMOID, &SOFTMAX_CROSS_ENTROPY, 1e-5, &mut errors);
+    /// 
+    /// #[cfg(not(feature = "f32"))]
+    /// assert!(max < 1e-4);
+    /// assert!(errors.iter().all(|error| *error <= max));
+    /// ```
+    fn grad_check<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, input: [Float; NEURONS], target: [Float; END_S], act: &F, loss: &L, epsilon: Float, errors: &mut [Float]) -> Float {
+        let mut gradients = Self::Snapshot::filled(0.0);
+        let outputs = self.predict(input, act);
+        self.back_propagate(outputs, target, act, loss, &mut gradients);
+
+        errors.fill(0.0);
+        let mut max: Float = 0.0;
+        let (mut set, mut index) = (0, 0);
+        gradients.each_set(&mut |analytic: &[Float]| {
+            let mut layer = 0;
+            let mut seen = 0;
+            self.param_stats(|each_layer, _, _| {
+                if seen == set {
+                    layer = each_layer;
+                }
+                seen += 1;
+            });
+            for &analytic in analytic {
+                let mut original = 0.0;
+                self.visit_mut(&mut ParamAt { index, start: 0, each: |param: &mut Float| original = *param });
+                self.visit_mut(&mut ParamAt { index, start: 0, each: |param: &mut Float| *param = original + epsilon });
+                let above = loss.loss(&self.predict(input, act), &target);
+                self.visit_mut(&mut ParamAt { index, start: 0, each: |param: &mut Float| *param = original - epsilon });
+                let below = loss.loss(&self.predict(input, act), &target);
+                self.visit_mut(&mut ParamAt { index, start: 0, each: |param: &mut Float| *param = original });
 
-        self.weights = self.weights.add(&gradients.multiply(&self.data.transpose()));
-        self.biases = self.biases.add(&gradients);
+                let numeric = (above - below) / (2.0 * epsilon);
+                let scale = (analytic.abs() + numeric.abs()).max(Float::EPSILON);
+                let error = (analytic - numeric).abs() / scale;
+                if let Some(layer_error) = errors.get_mut(layer) {
+                    *layer_error = layer_error.max(error);
+                }
+                max = max.max(error);
+                index += 1;
+            }
+            set += 1;
+        });
+        max
+    }
 
-        let errors = self.weights.transpose().multiply(&errors);
-        let gradients = self.data.map(&act.derivative);
+    /// Calls `each` with the [statistics](ParamStats) of each set of parameters of each layer in turn, such as to 
+    /// choose quantization ranges or to spot exploding or vanishing weights. 
+    /// 
+    /// # Parameters 
+    /// * `each` Given the index of the layer, counting only those with parameters as in [grad_check](Network::grad_check), the name of the set such as `"weights"`, and its statistics. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::network::{Network, ParamStats};
+    /// 
+    /// let network = make_network!(2, 3, 1).with_seed(2);
+    /// let mut weights = [ParamStats::default(); 2];
+    /// 
+    /// network.param_stats(|layer, name, stats| if name == "weights" { weights[layer] = stats });
+    /// 
+    /// assert!(weights.iter().all(|stats| stats.min >= -1.0 && stats.max <= 1.0));
+    /// ```
+    fn param_stats<F: FnMut(usize, &'static str, ParamStats)>(&self, each: F) {
+        self.visit(&mut StatsVisitor { each, layers: 0, current: None });
+    }
 
-        BackProps(errors, gradients)
+    /// Whether every weight and bias of the network is within `epsilon` of the same one of `other`, such as to check 
+    /// a network survives being stored and recalled, `==` compares them exactly. 
+    /// 
+    /// # Parameters 
+    /// * `other` The network to compare against. 
+    /// * `epsilon` The largest difference allowed between two parameters. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut first = make_network!(2, 3, 1).with_seed(5);
+    /// let mut second = make_network!(2, 3, 1).with_seed(5);
+    /// first.train(0.5, [[0.0, 1.0]], [[1.0]], 100, &SIGMOID, &MSE);
+    /// second.train(0.5, [[0.0, 1.0]], [[1.0]], 100, &SIGMOID, &MSE);
+    /// 
+    /// assert!(first == second);
+    /// 
+    /// second.weights.data[0][0] += 1e-4;
+    /// assert!(first != second);
+    /// assert!(first.approx_eq(&second, 1e-3));
+    /// ```
+    fn approx_eq(&self, other: &Self, epsilon: Float) -> bool {
+        let mut compare = Compare { other, epsilon, index: 0, close: true };
+        self.visit(&mut compare);
+        compare.close
     }
-}
 
+    /// Whether every parameter of the network is finite, neither NaN nor infinite, as checked after training when 
+    /// [check_finite](TrainOptions::check_finite) is set. 
+    fn params_finite(&self) -> bool {
+        let mut finite = Finite(true);
+        self.visit(&mut finite);
+        finite.0
+    }
 
-/// The end layer, this terminates the neural network linked list, just accepts the number of neurons in the final layer. 
-/// 
-/// # Type Parameters
-/// * `END_S` Number of neurons in the end layer. 
-pub struct EndLayer<const END_S: usize>();
+    /// Replaces the weights and biases of the network with the average of those of `networks`, such as to combine 
+    /// copies of a model trained apart on several devices, left unchanged should `networks` be empty. 
+    /// 
+    /// # Parameters 
+    /// * `networks` The networks to average. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::network::Network;
+    /// 
+    /// let devices = [make_network!(2, 3, 1).with_seed(1), make_network!(2, 3, 1).with_seed(2)];
+    /// let mut merged = make_network!(2, 3, 1);
+    /// 
+    /// merged.merge(&devices);
+    /// 
+    /// let average = (devices[0].weights.data[0][0] + devices[1].weights.data[0][0]) / 2.0;
+    /// assert_eq!(merged.weights.data[0][0], average);
+    /// ```
+    fn merge(&mut self, networks: &[Self]) {
+        merge_params(self, networks, |_| 1.0);
+    }
 
-impl <const END_S: usize> Layer<END_S, END_S> for EndLayer<END_S> {
-    fn feed_forward<'a>(&mut self, feed: Matrix<END_S, 1>, _act: &Activation<'a>) -> [Float; END_S] {
-        feed.transpose().data[0]
+    /// Replaces the weights and biases of the network with the weighted average of those of `networks`, such as 
+    /// weighting each by the number of samples it was trained upon, left unchanged should the weights sum to `0` 
+    /// or there not be one for each network. 
+    /// 
+    /// # Parameters 
+    /// * `networks` The networks to average. 
+    /// * `weights` The weight of each network, the same length as `networks`. 
+    fn merge_weighted(&mut self, networks: &[Self], weights: &[Float]) {
+        if weights.len() == networks.len() {
+            merge_params(self, networks, |i| weights[i]);
+        }
+    }
+
+    /// Initializes the weights of every layer with random values uniformly distributed in `-1..1` drawn from `rng`, 
+    /// or the range the layer's own activation asks for such as a [Siren](crate::activations::Siren)'s, zeroing 
+    /// the biases, the same seed always giving the same network. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::fastrand::Rng;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut first = make_network!(2, 3, 1);
+    /// let mut second = make_network!(2, 3, 1);
+    /// first.randomize(&mut Rng::with_seed(42));
+    /// second.randomize(&mut Rng::with_seed(42));
+    /// 
+    /// assert_eq!(first.predict([1.0, 0.5], &SIGMOID), second.predict([1.0, 0.5], &SIGMOID));
+    /// ```
+    fn randomize(&mut self, rng: &mut Rng) {
+        self.init_params(rng)
     }
 
-    fn back_propagate<'a>(&mut self, _l_rate: Float, outputs: [Float; END_S], targets: [Float; END_S], act: &Activation<'a>) -> BackProps<END_S> {
-        let parsed = Matrix::from([outputs]).transpose();
-        let errors = Matrix::from([targets]).transpose().subtract(&parsed);
-        let gradients = parsed.map(&act.derivative);
-        BackProps(errors, gradients)
+    /// Clears any state carried from one prediction to the next, such as the hidden state of a [RecurrentLayer], 
+    /// before starting a new sequence. 
+    fn reset_state(&mut self) {
+        self.clear_state()
     }
-}
 
-impl <const END_S: usize> fmt::Debug for EndLayer<END_S> {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.debug_struct("null").finish()
+    /// Consumes the network, returning it with its weights randomized from a generator seeded with `seed`, see [randomize](Network::randomize). 
+    fn with_seed(mut self, seed: u64) -> Self {
+        self.randomize(&mut Rng::with_seed(seed));
+        self
     }
+
+    /// Copies all the weights and biases of the network into a [LayerSnapshot], such as to checkpoint the best model. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 3, 1);
+    /// let untrained = network.snapshot();
+    /// 
+    /// network.train(0.5, [[0.0, 1.0]], [[1.0]], 10, &SIGMOID, &MSE);
+    /// assert_ne!(network.snapshot(), untrained);
+    /// 
+    /// network.restore(&untrained);
+    /// assert_eq!(network.snapshot(), untrained);
+    /// ```
+    fn snapshot(&self) -> Self::Snapshot {
+        self.save_params()
+    }
+
+    /// Copies all the weights and biases of the network back from a [LayerSnapshot]. 
+    fn restore(&mut self, snapshot: &Self::Snapshot) {
+        self.load_params(snapshot)
+    }
+
+    /// Trains upon a single sample, feeding it forward then immediately applying the update by stochastic gradient descent, 
+    /// for continuously adapting a deployed model to live readings. 
+    /// 
+    /// Returns the loss of the prediction made before the update. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `input` The sample's input. 
+    /// * `target` The target for the sample. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function to minimise, such as [MSE](crate::loss::MSE). 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::SIGMOID};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let first = network.fit_one(0.5, [1.0, 0.0], [1.0], &SIGMOID, &MSE);
+    /// let mut last = first;
+    /// for _ in 0..100 {
+    ///     last = network.fit_one(0.5, [1.0, 0.0], [1.0], &SIGMOID, &MSE);
+    /// }
+    /// 
+    /// assert!(last < first);
+    /// ```
+    fn fit_one<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, l_rate: Float, input: [Float; NEURONS], target: [Float; END_S], act: &F, loss: &L) -> Float {
+        self.set_training(true);
+        let outputs = self.feed_forward(Matrix::from([input]).transpose(), act);
+        self.set_training(false);
+        let sample_loss = loss.loss(&outputs, &target);
+        let mut gradients = Self::Snapshot::filled(0.0);
+        self.back_propagate(outputs, target, act, loss, &mut gradients);
+        self.apply_gradients(&Sgd, &GradientStep::new(l_rate), &mut gradients, &mut ParamSets::filled([0.0; 2]));
+        sample_loss
+    }
+
+    /// Calculates the average loss of the network's predictions over a set of inputs and targets, without training. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs to predict upon. 
+    /// * `targets` The targets for each input, the same length as `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function measuring each prediction. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::{IDENTITY, TANH}};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let line = |[x]: [_; 1]| [0.5 * x + 0.25];
+    /// let inputs = [[0.0], [0.2], [0.4], [0.6], [0.8], [1.0]];
+    /// let mut network = make_network!(1, 3 => TANH, 1 => IDENTITY).with_seed(1);
+    /// network.train(0.1, inputs, inputs.map(line), 2_000, &TANH, &MSE);
+    /// 
+    /// // Measures how well the network generalises to data held out from training, drawn from the same line. 
+    /// let held_out = [[0.1], [0.5], [0.9]];
+    /// let held_out_loss = network.evaluate(&held_out, &held_out.map(line), &TANH, &MSE);
+    /// 
+    /// assert!(held_out_loss < 1e-3);
+    /// // The weights are left untouched. 
+    /// assert_eq!(network.evaluate(&held_out, &held_out.map(line), &TANH, &MSE), held_out_loss);
+    /// ```
+    fn evaluate<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, inputs: &[[Float; NEURONS]], targets: &[[Float; END_S]], act: &F, loss: &L) -> Float {
+        let mut total = 0.0;
+        for (input, target) in inputs.iter().zip(targets) {
+            total += loss.loss(&self.predict(*input, act), target);
+        }
+        total / inputs.len().max(1) as Float
+    }
+
+    /// Calculates the average loss of each of the network's outputs over a set of inputs and targets, without 
+    /// training, showing which outputs of a network predicting several values it's failing on, see [Loss::output_loss]. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs to predict upon. 
+    /// * `targets` The targets for each input, the same length as `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// * `loss` The loss function measuring each prediction. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::activations::IDENTITY;
+    /// use mynn::loss::MAE;
+    /// use mynn::network::{EndLayer, Network, ProcessLayer};
+    /// 
+    /// // Predicts the first target exactly but is always off by 0.5 on the second. 
+    /// let mut network = ProcessLayer::<2, 1, 2, EndLayer<2>>::new_with(EndLayer(), [[1.0], [1.0]], [0.0, 0.5]);
+    /// let losses = network.output_losses(&[[0.0], [1.0]], &[[0.0, 0.0], [1.0, 1.0]], &IDENTITY, &MAE);
+    /// 
+    /// assert_eq!(losses, [0.0, 0.5]);
+    /// ```
+    fn output_losses<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, inputs: &[[Float; NEURONS]], targets: &[[Float; END_S]], act: &F, loss: &L) -> [Float; END_S] {
+        let mut totals = [0.0; END_S];
+        for (input, target) in inputs.iter().zip(targets) {
+            let outputs = self.predict(*input, act);
+            for (i, total) in totals.iter_mut().enumerate() {
+                *total += loss.output_loss(&outputs, target, i);
+            }
+        }
+        totals.map(|total| total / inputs.len().max(1) as Float)
+    }
+
+    /// The fraction of a set of inputs whose [predicted class](Network::classify) matches their label, from `0` to 
+    /// `1`, without training. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs to classify. 
+    /// * `labels` The index of the class of each input, the same length as `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::{SIGMOID, SOFTMAX}};
+    /// use mynn::loss::SOFTMAX_CROSS_ENTROPY;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[1.0, 0.0], [0.9, 0.2], [0.0, 1.0], [0.1, 0.8]];
+    /// let targets = [[1.0, 0.0], [1.0, 0.0], [0.0, 1.0], [0.0, 1.0]];
+    /// let mut network = make_network!(2, 3 => SIGMOID, 2 => SOFTMAX);
+    /// 
+    /// network.train(0.5, inputs, targets, 500, &SIGMOID, &SOFTMAX_CROSS_ENTROPY);
+    /// 
+    /// assert_eq!(network.classify([0.8, 0.1], &SIGMOID), 0);
+    /// assert_eq!(network.accuracy(&inputs, &[0, 0, 1, 1], &SIGMOID), 1.0);
+    /// ```
+    fn accuracy<F: LayerActivation + ?Sized>(&mut self, inputs: &[[Float; NEURONS]], labels: &[usize], act: &F) -> Float {
+        let correct = inputs.iter().zip(labels).filter(|(input, label)| self.classify(**input, act) == **label).count();
+        correct as Float / inputs.len().min(labels.len()).max(1) as Float
+    }
+
+    /// Counts how often the [predicted class](Network::classify) of each of a set of inputs is each class given its 
+    /// label, from which the precision, recall and F1 score of each class are found, without training. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs to classify. 
+    /// * `labels` The index of the class of each input, the same length as `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::{SIGMOID, SOFTMAX}};
+    /// use mynn::loss::SOFTMAX_CROSS_ENTROPY;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[1.0, 0.0], [0.9, 0.2], [0.8, 0.1], [0.0, 1.0]];
+    /// let targets = [[1.0, 0.0], [1.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+    /// let mut network = make_network!(2, 3 => SIGMOID, 2 => SOFTMAX);
+    /// 
+    /// network.train(0.5, inputs, targets, 1_000, &SIGMOID, &SOFTMAX_CROSS_ENTROPY);
+    /// let confusion = network.confusion_matrix(&inputs, &[0, 0, 0, 1], &SIGMOID);
+    /// 
+    /// assert_eq!(confusion.counts, [[3, 0], [0, 1]]);
+    /// assert_eq!(confusion.macro_f1(), 1.0);
+    /// ```
+    fn confusion_matrix<F: LayerActivation + ?Sized>(&mut self, inputs: &[[Float; NEURONS]], labels: &[usize], act: &F) -> ConfusionMatrix<END_S> {
+        let mut confusion = ConfusionMatrix::new();
+        for (input, label) in inputs.iter().zip(labels) {
+            confusion.record(*label, self.classify(*input, act));
+        }
+        confusion
+    }
+
+    /// Measures the mean absolute error, root mean squared error and coefficient of determination (R²) of the 
+    /// network's predictions over a set of inputs and targets, without training, see [RegressionMetrics]. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs to predict upon. 
+    /// * `targets` The targets for each input, the same length as `inputs`. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, activations::{IDENTITY, TANH}};
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0], [0.25], [0.5], [0.75], [1.0]];
+    /// let targets = inputs.map(|[x]| [2.0 * x + 1.0]);
+    /// let mut network = make_network!(1, 3 => TANH, 1 => IDENTITY).with_seed(1);
+    /// 
+    /// network.train(0.1, inputs, targets, 2_000, &TANH, &MSE);
+    /// let metrics = network.regression_metrics(&inputs, &targets, &TANH);
+    /// 
+    /// assert!(metrics.r_squared() > 0.99);
+    /// assert!(metrics.rmse() < 0.1 && metrics.mae() <= metrics.rmse());
+    /// ```
+    fn regression_metrics<F: LayerActivation + ?Sized>(&mut self, inputs: &[[Float; NEURONS]], targets: &[[Float; END_S]], act: &F) -> RegressionMetrics<END_S> {
+        let mut metrics = RegressionMetrics::new();
+        for (input, target) in inputs.iter().zip(targets) {
+            metrics.record(&self.predict(*input, act), target);
+        }
+        metrics
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>> Network<NEURONS, END_S> for N {}
+
+/// Generic behaviour for inspecting the layers of any network in turn, for tooling such as logging or exporting, 
+/// see [Layer::visit]. 
+/// 
+/// Each layer is entered with [layer](Visitor::layer) then has each of its sets of parameters passed to 
+/// [params](Visitor::params), [end](Visitor::end) marks the end of the network or of a sub-chain within a layer, 
+/// such as the inner layers of a [ResidualLayer] or each head of a [BranchLayer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::network::{Layer, Visitor};
+/// 
+/// // Counts the parameters of a network. 
+/// struct Count(usize);
+/// 
+/// impl Visitor for Count {
+///     fn params(&mut self, _name: &'static str, params: &[Float]) {
+///         self.0 += params.len();
+///     }
+/// }
+/// 
+/// let network = make_network!(2, 3, 1);
+/// let mut count = Count(0);
+/// network.visit(&mut count);
+/// 
+/// assert_eq!(count.0, 2 * 3 + 3 + 3 * 1 + 1);
+/// ```
+pub trait Visitor {
+    /// Called on entering each layer, does nothing by default. 
+    /// 
+    /// # Parameters 
+    /// * `kind` A short name for the kind of layer, such as `"dense"` or `"dropout"`. 
+    /// * `inputs` The number of values the layer is fed. 
+    /// * `outputs` The number of values the layer passes on. 
+    fn layer(&mut self, _kind: &'static str, _inputs: usize, _outputs: usize) {}
+
+    /// Called after entering a layer owning its own activation, with the activation's 
+    /// [name](crate::activations::LayerActivation::name), does nothing by default. 
+    fn activation(&mut self, _name: &'static str) {}
+
+    /// Called with each set of parameters of the layer last entered, does nothing by default. 
+    /// 
+    /// # Parameters 
+    /// * `name` The name of the set, such as `"weights"` or `"biases"`. 
+    /// * `params` The parameters, flattened in row major order such as one row of weights per output. 
+    fn params(&mut self, _name: &'static str, _params: &[Float]) {}
+
+    /// Called at the end of the network or of a sub-chain within a layer, does nothing by default. 
+    fn end(&mut self) {}
+}
+
+/// Visitor giving the statistics of each set of parameters for [param_stats](Network::param_stats). 
+struct StatsVisitor<F: FnMut(usize, &'static str, ParamStats)> {
+    each: F,
+    /// The number of layers with parameters seen so far. 
+    layers: usize,
+    /// The index of the layer last entered, once it has been seen to have parameters. 
+    current: Option<usize>
+}
+
+impl<F: FnMut(usize, &'static str, ParamStats)> Visitor for StatsVisitor<F> {
+    fn layer(&mut self, _kind: &'static str, _inputs: usize, _outputs: usize) {
+        self.current = None;
+    }
+
+    fn params(&mut self, name: &'static str, params: &[Float]) {
+        let layer = *self.current.get_or_insert_with(|| {
+            self.layers += 1;
+            self.layers - 1
+        });
+        (self.each)(layer, name, ParamStats::new(params));
+    }
+}
+
+/// Visitor writing the architecture summary shown by each layer's `Display`, one line per layer with the layers 
+/// of sub-chains indented beneath the layer holding them, followed by the total parameter count. 
+struct Summary<'f, 'a> {
+    fmt: &'f mut fmt::Formatter<'a>,
+    result: fmt::Result,
+    /// The number of sub-chains the layer last entered is nested within. 
+    depth: usize,
+    /// Set at each depth whose sub-chains are the heads of a [BranchLayer], which end the chain holding the branch. 
+    branches: u64,
+    /// Set at each depth whose sub-chain is the first head of a [BranchLayer], the second head following at the same depth. 
+    heads: u64,
+    /// The number of parameters of the layer last entered, `None` once its line is finished. 
+    params: Option<usize>,
+    total: usize
+}
+
+impl<'f, 'a> Summary<'f, 'a> {
+    fn new(fmt: &'f mut fmt::Formatter<'a>) -> Summary<'f, 'a> {
+        Summary {
+            fmt,
+            result: Ok(()),
+            depth: 0,
+            branches: 0,
+            heads: 0,
+            params: None,
+            total: 0
+        }
+    }
+
+    /// Finishes the line of the layer last entered, if any. 
+    fn finish_line(&mut self) {
+        match self.params.take() {
+            Some(0) => self.write(format_args!("\n")),
+            Some(params) => self.write(format_args!(", {} params\n", params)),
+            None => {}
+        }
+    }
+
+    fn write(&mut self, args: fmt::Arguments) {
+        if self.result.is_ok() {
+            self.result = self.fmt.write_fmt(args);
+        }
+    }
+}
+
+impl<'f, 'a> Visitor for Summary<'f, 'a> {
+    fn layer(&mut self, kind: &'static str, inputs: usize, outputs: usize) {
+        self.finish_line();
+        self.write(format_args!("{:indent$}{} {} -> {}", "", kind, inputs, outputs, indent = 2 * self.depth));
+        self.params = Some(0);
+        if matches!(kind, "residual" | "autoencoder" | "branch") {
+            self.depth += 1;
+        }
+        if kind == "branch" && self.depth < 64 {
+            self.branches |= 1 << self.depth;
+            self.heads |= 1 << self.depth;
+        }
+    }
+
+    fn activation(&mut self, name: &'static str) {
+        self.write(format_args!(" ({})", name));
+    }
+
+    fn params(&mut self, _name: &'static str, params: &[Float]) {
+        if let Some(count) = self.params.as_mut() {
+            *count += params.len();
+        }
+        self.total += params.len();
+    }
+
+    fn end(&mut self) {
+        self.finish_line();
+        if self.depth == 0 {
+            let total = self.total;
+            self.write(format_args!("total {} params", total));
+        } else if self.depth < 64 && self.heads & 1 << self.depth != 0 {
+            self.heads &= !(1 << self.depth);
+        } else if self.depth < 64 && self.branches & 1 << self.depth != 0 {
+            self.branches &= !(1 << self.depth);
+            self.depth -= 1;
+            self.end();
+        } else {
+            self.depth -= 1;
+        }
+    }
+}
+
+/// Generic behaviour for modifying the parameters of any network in turn, for tooling such as quantization, 
+/// visited in the same order as a [Visitor], see [Layer::visit_mut]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::SIGMOID;
+/// use mynn::network::{Layer, Network, VisitorMut};
+/// 
+/// // Rounds every parameter to the nearest quarter. 
+/// struct Quantize;
+/// 
+/// impl VisitorMut for Quantize {
+///     fn params(&mut self, _name: &'static str, params: &mut [Float]) {
+///         params.iter_mut().for_each(|param| *param = (*param * 4.0).round() / 4.0);
+///     }
+/// }
+/// 
+/// let mut network = make_network!(2, 3, 1).with_seed(1);
+/// network.visit_mut(&mut Quantize);
+/// 
+/// let weights = network.snapshot().weights;
+/// assert!(weights.as_flattened().iter().all(|weight| (weight * 4.0).fract() == 0.0));
+/// ```
+pub trait VisitorMut {
+    /// Called on entering each layer, see [Visitor::layer], does nothing by default. 
+    fn layer(&mut self, _kind: &'static str, _inputs: usize, _outputs: usize) {}
+
+    /// Called after entering a layer owning its own activation, see [Visitor::activation], does nothing by default. 
+    fn activation(&mut self, _name: &'static str) {}
+
+    /// Called with each set of parameters of the layer last entered, see [Visitor::params], does nothing by default. 
+    fn params(&mut self, _name: &'static str, _params: &mut [Float]) {}
+
+    /// Called at the end of the network or of a sub-chain within a layer, does nothing by default. 
+    fn end(&mut self) {}
+}
+
+/// Generic behaviour for attaching a new head to the end of a network, the head taking the network's outputs as 
+/// its inputs, keeping every existing layer's weights. 
+/// 
+/// Implemented for every layer but [BranchLayer], whose heads are already the ends of the network. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of inputs to the network. 
+/// * `END_S` The number of outputs from the network, the number of inputs to the head. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::network::{AppendHead, EndLayer, Network, ProcessLayer};
+/// 
+/// let trunk = make_network!(2, 4, 3).with_seed(1);
+/// let mut network = trunk.append_head(ProcessLayer::<2, 3, 2, _>::new(EndLayer()).with_seed(2));
+/// 
+/// assert_eq!(network.predict([0.0, 1.0], &SIGMOID).len(), 2);
+/// ```
+pub trait AppendHead<const NEURONS: usize, const END_S: usize>: Layer<NEURONS, END_S> {
+    /// The network with `HEAD` attached to its end, giving `NEW_END` outputs. 
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>: Layer<NEURONS, NEW_END>;
+
+    /// Consumes the network, returning it with `head` attached to its end. 
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD>;
+}
+
+/// Generic behaviour for removing the head of a network, leaving the trunk before it with its weights intact, such 
+/// as to reuse a trained network for a task with a different number of outputs. 
+/// 
+/// The head is the final [ProcessLayer], along with a [SoftmaxLayer] should one follow it. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of inputs to the network. 
+/// * `END_S` The number of outputs from the network. 
+/// * `TRUNK_S` The number of outputs from the trunk, the number of inputs to the head. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::network::{DropHead, EndLayer, Network, ProcessLayer};
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let mut network = make_network!(2, 4, 1).with_seed(1);
+/// network.train(0.5, inputs, [[0.0], [1.0], [1.0], [0.0]], 1_000, &SIGMOID, &MSE);
+/// let trunk = network.weights.data;
+/// 
+/// // Reuse the trained trunk for two outputs, only training the new head from scratch. 
+/// let mut network = network.replace_head(ProcessLayer::<2, 4, 2, _>::new(EndLayer()).with_seed(2));
+/// assert_eq!(network.weights.data, trunk);
+/// 
+/// network.train(0.5, inputs, [[0.0, 1.0], [1.0, 0.0], [1.0, 0.0], [0.0, 1.0]], 100, &SIGMOID, &MSE);
+/// assert_eq!(network.predict([0.0, 1.0], &SIGMOID).len(), 2);
+/// ```
+pub trait DropHead<const NEURONS: usize, const END_S: usize, const TRUNK_S: usize>: Layer<NEURONS, END_S> {
+    /// The network without its head, giving `TRUNK_S` outputs. 
+    type Trunk: AppendHead<NEURONS, TRUNK_S>;
+
+    /// Consumes the network, returning it without its head. 
+    fn drop_head(self) -> Self::Trunk;
+
+    /// Consumes the network, returning it with its head replaced by `head`, keeping the weights of the trunk. 
+    fn replace_head<const NEW_END: usize, HEAD: Layer<TRUNK_S, NEW_END>>(self, head: HEAD) -> <Self::Trunk as AppendHead<NEURONS, TRUNK_S>>::Appended<NEW_END, HEAD>
+    where 
+        Self: Sized 
+    {
+        self.drop_head().append_head(head)
+    }
+}
+
+/// Sets each parameter of the network to the weighted average of the same parameter of each of `networks`. 
+fn merge_params<const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>, W: Fn(usize) -> Float>(network: &mut N, networks: &[N], weight: W) {
+    let total: Float = (0..networks.len()).map(&weight).sum();
+    if total == 0.0 {
+        return;
+    }
+    network.visit_mut(&mut Merge { networks, weight: |i| weight(i) / total, index: 0 });
+}
+
+/// Visitor replacing every parameter of a network with the weighted sum of the same one of `networks`, see [merge_params]. 
+struct Merge<'n, const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>, W: Fn(usize) -> Float> {
+    networks: &'n [N],
+    weight: W,
+    /// The index of the first parameter of the next set visited, counting across the whole network. 
+    index: usize
+}
+
+impl<'n, const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>, W: Fn(usize) -> Float> VisitorMut for Merge<'n, NEURONS, END_S, N, W> {
+    fn params(&mut self, _name: &'static str, params: &mut [Float]) {
+        params.fill(0.0);
+        for (i, network) in self.networks.iter().enumerate() {
+            network.visit(&mut Gather { params, weight: (self.weight)(i), start: self.index, index: 0 });
+        }
+        self.index += params.len();
+    }
+}
+
+/// Visitor calling `each` with the `index`th parameter of a network, counting across the whole network. 
+struct ParamAt<F: FnMut(&mut Float)> {
+    index: usize,
+    /// The index of the first parameter of the next set visited. 
+    start: usize,
+    each: F
+}
+
+impl<F: FnMut(&mut Float)> VisitorMut for ParamAt<F> {
+    fn params(&mut self, _name: &'static str, params: &mut [Float]) {
+        if let Some(param) = self.index.checked_sub(self.start).and_then(|i| params.get_mut(i)) {
+            (self.each)(param);
+        }
+        self.start += params.len();
+    }
+}
+
+/// Visitor adding the parameters of a network from the `start`th onwards, scaled by `weight`, into `params`. 
+struct Gather<'p> {
+    params: &'p mut [Float],
+    weight: Float,
+    start: usize,
+    index: usize
+}
+
+impl<'p> Visitor for Gather<'p> {
+    fn params(&mut self, _name: &'static str, params: &[Float]) {
+        for &param in params {
+            if let Some(target) = self.index.checked_sub(self.start).and_then(|i| self.params.get_mut(i)) {
+                *target += self.weight * param;
+            }
+            self.index += 1;
+        }
+    }
+}
+
+/// Visitor copying the parameters of a network from the `index`th onwards out of a snapshot, see [Layer::load_params]. 
+struct Scatter<'s, S: ParamSets<Float>> {
+    snapshot: &'s S,
+    /// The index of the first parameter of the next set visited, counting across the whole network. 
+    index: usize
+}
+
+impl<'s, S: ParamSets<Float>> VisitorMut for Scatter<'s, S> {
+    fn params(&mut self, _name: &'static str, params: &mut [Float]) {
+        let (index, end) = (self.index, self.index + params.len());
+        let mut start = 0;
+        self.snapshot.each_set(&mut |set: &[Float]| {
+            let (from, to) = (start.max(index), (start + set.len()).min(end));
+            if from < to {
+                params[from - index..to - index].copy_from_slice(&set[from - start..to - start]);
+            }
+            start += set.len();
+        });
+        self.index = end;
+    }
+}
+
+/// Visitor checking every parameter of a network is within `epsilon` of the same one of `other`, see [Network::approx_eq]. 
+struct Compare<'n, const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>> {
+    other: &'n N,
+    epsilon: Float,
+    /// The index of the first parameter of the next set visited, counting across the whole network. 
+    index: usize,
+    close: bool
+}
+
+impl<'n, const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>> Visitor for Compare<'n, NEURONS, END_S, N> {
+    fn params(&mut self, _name: &'static str, params: &[Float]) {
+        self.other.visit(&mut Close { params, epsilon: self.epsilon, start: self.index, index: 0, close: &mut self.close });
+        self.index += params.len();
+    }
+}
+
+/// Visitor checking the parameters of a network from the `start`th onwards are within `epsilon` of `params`. 
+struct Close<'p> {
+    params: &'p [Float],
+    epsilon: Float,
+    start: usize,
+    index: usize,
+    close: &'p mut bool
+}
+
+impl<'p> Visitor for Close<'p> {
+    fn params(&mut self, _name: &'static str, params: &[Float]) {
+        for &other in params {
+            if let Some(&param) = self.index.checked_sub(self.start).and_then(|i| self.params.get(i)) {
+                *self.close &= param == other || (param - other).abs() <= self.epsilon;
+            }
+            self.index += 1;
+        }
+    }
+}
+
+/// Visitor checking every parameter of a network is finite, see [Network::params_finite]. 
+struct Finite(bool);
+
+impl Visitor for Finite {
+    fn params(&mut self, _name: &'static str, params: &[Float]) {
+        self.0 &= params.iter().all(|param| param.is_finite());
+    }
+}
+
+/// Trains upon arrays of samples, shuffling their order each epoch should the options ask for it. 
+fn train_array<const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>, F: LayerActivation + ?Sized, L: Loss + ?Sized, const DATA_S: usize>(network: &mut N, inputs: &[[Float; NEURONS]; DATA_S], targets: &[[Float; END_S]; DATA_S], act: &F, loss: &L, mut options: TrainOptions<NEURONS, END_S>, state: &mut N::StateSnapshot) -> (TrainStats, Option<TrainError>) {
+    let mut order: [usize; DATA_S] = core::array::from_fn(|i| i);
+    let weights = options.sample_weights;
+    let mut stages: &[usize] = &[];
+    if let Some(difficulty) = options.difficulty.take() {
+        let scores: [Float; DATA_S] = core::array::from_fn(|i| difficulty(&inputs[i], &targets[i]));
+        order.sort_unstable_by(|&a, &b| scores[a].total_cmp(&scores[b]));
+        stages = options.stages;
+    }
+    let mut epoch = 0;
+    train_samples(network, |options| {
+        let pool = curriculum_pool(stages, epoch, DATA_S);
+        epoch += 1;
+        if let (true, Some(rng)) = (options.shuffle, options.rng.as_deref_mut()) {
+            rng.shuffle(&mut order[..pool]);
+        }
+        order.into_iter().take(pool).map(move |i| (&inputs[i], &targets[i], weights.and_then(|weights| weights.get(i).copied()).unwrap_or(1.0)))
+    }, act, loss, options, state)
+}
+
+/// The number of the easiest samples trained upon in a zero based epoch of a curriculum, growing by an equal share 
+/// each stage, the whole dataset once the stages have passed. 
+fn curriculum_pool(stages: &[usize], epoch: usize, samples: usize) -> usize {
+    let mut end = 0;
+    for (stage, epochs) in stages.iter().enumerate() {
+        end += epochs;
+        if epoch < end {
+            return (samples * (stage + 1)).div_ceil(stages.len());
+        }
+    }
+    samples
+}
+
+/// The training loop shared by each way of training, `epoch_samples` gives the samples to iterate each epoch along 
+/// with the weight of each, returning the error that stopped training should `check_finite` be set. The gradients 
+/// are gathered here for the length of the run, the optimizer's state is kept in `state`. 
+fn train_samples<'d, const NEURONS: usize, const END_S: usize, N, D, I, F, L>(network: &mut N, mut epoch_samples: D, act: &F, loss: &L, mut options: TrainOptions<NEURONS, END_S>, state: &mut N::StateSnapshot) -> (TrainStats, Option<TrainError>)
+where 
+    N: Layer<NEURONS, END_S>,
+    D: FnMut(&mut TrainOptions<NEURONS, END_S>) -> I,
+    I: Iterator<Item = (&'d [Float; NEURONS], &'d [Float; END_S], Float)>,
+    F: LayerActivation + ?Sized, 
+    L: Loss + ?Sized
+{
+    let mut sgd = Sgd;
+    let optimizer: &mut dyn Optimizer = options.optimizer.take().unwrap_or(&mut sgd);
+    let mut stats = TrainStats::default();
+    let mut updates = 0;
+    let mut stale = 0;
+    let mut best = None;
+    let mut error = None;
+    let mut trained: usize = 0;
+    let mut gradients = N::Snapshot::filled(0.0);
+    let mut average: Option<N::Snapshot> = None;
+    for epoch in 1..=options.epochs {
+        let l_rate = options.schedule.map_or(options.l_rate, |schedule| schedule.rate(epoch - 1));
+        let step = GradientStep {
+            l_rate,
+            scale: 1.0,
+            weight_decay: options.weight_decay,
+            clip_value: options.clip_value,
+            clip_norm: options.clip_norm,
+            max_norm: options.max_norm
+        };
+        let mut total = 0.0;
+        let mut batch_total = 0.0;
+        let mut pending = 0;
+        let mut seen = 0;
+        let mut running = true;
+        let mut samples = epoch_samples(&mut options).peekable();
+        network.set_training(true);
+        while let Some((input, target, mut weight)) = samples.next() {
+            if let Some(class_weights) = options.class_weights {
+                weight *= class_weights.iter().zip(target).map(|(class_weight, target)| class_weight * target).sum::<Float>();
+            }
+            let mut input = *input;
+            if let (Some(augment), Some(rng)) = (options.augment.as_mut(), options.rng.as_mut()) {
+                augment(&mut input, rng);
+            }
+            let outputs = network.feed_forward(Matrix::from([input]).transpose(), act);
+            stats.final_loss = loss.loss(&outputs, target);
+            if options.check_finite && !stats.final_loss.is_finite() {
+                error = Some(TrainError::NonFiniteLoss { epoch });
+                break;
+            }
+            total += stats.final_loss;
+            batch_total += stats.final_loss;
+            seen += 1;
+            trained += 1;
+            if options.yield_every > 0 && trained.is_multiple_of(options.yield_every) {
+                if let Some(hook) = options.yield_hook.as_mut() {
+                    hook();
+                }
+            }
+            if weight == 1.0 {
+                network.back_propagate(outputs, *target, act, loss, &mut gradients);
+            } else {
+                network.back_propagate(outputs, *target, act, &Weighted { loss, weight }, &mut gradients);
+            }
+            pending += 1;
+            if pending == options.accumulate || samples.peek().is_none() {
+                optimize(network, optimizer, GradientStep { scale: 1.0 / pending as Float, ..step }, &mut options, updates, &mut gradients, state);
+                updates += 1;
+                if let Some(callback) = options.callback.as_mut() {
+                    running = callback.on_batch_end(epoch, updates, batch_total / pending as Float);
+                }
+                batch_total = 0.0;
+                pending = 0;
+                if !running {
+                    break;
+                }
+            }
+        }
+        network.set_training(false);
+        if options.check_finite && error.is_none() && !network.params_finite() {
+            error = Some(TrainError::NonFiniteParams { epoch });
+        }
+        if error.is_some() {
+            break;
+        }
+        stats.average_loss = total / seen.max(1) as Float;
+        stats.epochs += 1;
+        if let Some(recorder) = options.recorder.as_mut() {
+            recorder.record(epoch, stats.average_loss);
+        }
+        if let Some(start) = options.average_from.filter(|&start| epoch > start) {
+            let params = network.save_params();
+            let rate = 1.0 / (epoch - start) as Float;
+            let average = average.get_or_insert_with(|| params.clone());
+            zip_sets(average, &params, |average, param| *average += (param - *average) * rate);
+        }
+        if let Some((inputs, targets)) = options.validation {
+            let validation_loss = network.evaluate(inputs, targets, act, loss);
+            stats.validation_loss = Some(validation_loss);
+            if stats.best_validation_loss.is_none_or(|lowest| validation_loss < lowest) {
+                stats.best_validation_loss = Some(validation_loss);
+                if options.restore_best {
+                    best = Some(network.save_params());
+                }
+                stale = 0;
+            } else {
+                stale += 1;
+            }
+            if options.patience.is_some_and(|patience| stale >= patience) {
+                break;
+            }
+        }
+        if options.yield_every == 0 {
+            if let Some(hook) = options.yield_hook.as_mut() {
+                hook();
+            }
+        }
+        if let Some(callback) = options.callback.as_mut() {
+            running &= callback.on_epoch_end(epoch, stats.average_loss);
+        }
+        if !running {
+            break;
+        }
+    }
+    let averaged = average.is_some();
+    if let Some(average) = average {
+        network.load_params(&average);
+    }
+    if let Some(best) = best {
+        // The average is only kept over the best snapshot should its validation loss be lower. 
+        let average_better = averaged && options.validation.is_some_and(|(inputs, targets)| {
+            stats.best_validation_loss.is_some_and(|lowest| network.evaluate(inputs, targets, act, loss) < lowest)
+        });
+        if !average_better {
+            network.load_params(&best);
+        }
+    }
+    (stats, error)
+}
+
+/// Applies the gathered gradients with the optimizer, first adding any noise and scaling them down should their 
+/// norm exceed the limit. 
+fn optimize<const NEURONS: usize, const END_S: usize, N: Layer<NEURONS, END_S>>(network: &mut N, optimizer: &mut dyn Optimizer, mut step: GradientStep, options: &mut TrainOptions<NEURONS, END_S>, update: usize, gradients: &mut N::Snapshot, state: &mut N::StateSnapshot) {
+    if let (Some(noise), Some(rng)) = (options.gradient_noise, options.rng.as_deref_mut()) {
+        // The gathered gradients are summed, so the noise is scaled up to keep its deviation once averaged. 
+        let std_dev = noise.std_dev(update) / step.scale;
+        gradients.each_set_mut(&mut |set: &mut [Float]| set.iter_mut().for_each(|gradient| *gradient += std_dev * gaussian(rng)));
+    }
+    if let Some(clip_norm) = step.clip_norm {
+        let mut sum = 0.0;
+        gradients.each_set(&mut |set: &[Float]| sum += set.iter().map(|gradient| gradient * gradient).sum::<Float>());
+        let norm = sqrt(sum) * step.scale;
+        if norm > clip_norm {
+            step.scale *= clip_norm / norm;
+        }
+    }
+    optimizer.step();
+    network.apply_gradients(optimizer, &step, gradients, state);
+}
+
+
+/// Helper for [make_network](crate::make_network), fixes the number of values a layer is fed so it can be inferred 
+/// for layers such as [Conv2dLayer] that can't infer it from the following layers. 
+#[doc(hidden)]
+pub fn fed<const NEURONS: usize, const END_S: usize, L: Layer<NEURONS, END_S>>(layer: L) -> L {
+    layer
+}
+
+/// Scales, clips and decays a set of gradients as the step asks, applying them to the parameters with the optimizer 
+/// then clearing them, for use by a [Layer] in [apply_gradients](Layer::apply_gradients). 
+/// 
+/// # Parameters 
+/// * `optimizer` The update rule, see [Optimizer]. 
+/// * `step` The learning rate and other settings for this update. 
+/// * `params` The parameters to update. 
+/// * `gradients` The gradient gathered for each parameter, the same length as `params`, zeroed afterwards. 
+/// * `state` The optimizer's state for each parameter, the same length as `params`. 
+/// * `decay` Whether the step's weight decay applies to these parameters, commonly `false` for biases. 
+pub fn update_params<O: Optimizer + ?Sized>(optimizer: &O, step: &GradientStep, params: &mut [Float], gradients: &mut [Float], state: &mut [OptimizerState], decay: bool) {
+    for (gradient, param) in gradients.iter_mut().zip(params.iter()) {
+        *gradient *= step.scale;
+        if let Some(clip) = step.clip_value {
+            *gradient = gradient.clamp(-clip, clip);
+        }
+        if decay && step.weight_decay != 0.0 {
+            *gradient += param * step.weight_decay;
+        }
+    }
+    optimizer.update(step.l_rate, params, gradients, state);
+    gradients.iter_mut().for_each(|gradient| *gradient = 0.0);
+}
+
+/// Subtracts each of `values` from the gradient at the same index, gathering the gradients of a sample. 
+fn subtract_gradients(gradients: &mut [Float], values: &[Float]) {
+    for (gradient, value) in gradients.iter_mut().zip(values) {
+        *gradient -= value;
+    }
+}
+
+/// Scales the parameters down so their norm is no greater than `max_norm`, for use by a [Layer] honouring 
+/// [GradientStep::max_norm]. 
+pub fn limit_norm(params: &mut [Float], max_norm: Float) {
+    let norm = sqrt(params.iter().map(|x| x * x).sum());
+    if norm > max_norm {
+        params.iter_mut().for_each(|x| *x *= max_norm / norm);
+    }
+}
+
+
+/// Type for an active (I.E. containing neurons) layer. 
+/// 
+/// Has type bounds to ensure the next layer must have equal number of neurons as there are rows in the weights and biases matrices. 
+/// 
+/// # Type Parameters
+/// * `ROWS` The number of rows in the weights, biases, and number of neurons that must be in the next layer. 
+/// * `NEURONS` The number of neurons (number of columns in the weights matrix) in this layer. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// * `A` The activation function applied to this layer's output, defaults to [Inherit] using the one passed to [predict](Network::predict)/[train](Network::train). 
+pub struct ProcessLayer<const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A = Inherit> {
+    /// The next layer. 
+    pub next: T,
+    /// The activation function applied to the output of this layer, see [StoredActivation]. 
+    pub activation: A,
+    pub weights: Matrix<ROWS, NEURONS>,
+    pub biases: Matrix<ROWS, 1>,
+    /// The data that was last passed in during a feed forward, used to make corrections during back propagation. 
+    pub data: Matrix<NEURONS, 1>,
+    /// The values of the next layer's neurons before the activation function was applied in the last feed forward, 
+    /// the activation's derivative is taken of these during back propagation. 
+    pub pre_activation: Matrix<ROWS, 1>
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A> fmt::Debug for ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"weights\"", &self.weights)
+            .field("\"biases\"", &self.biases)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A: StoredActivation> PartialEq for ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+/// Summarises the architecture of the network from this layer on, one line per layer giving its kind, inputs, 
+/// outputs, activation and parameter count, followed by the total parameter count. 
+/// 
+/// Layers whose activation is passed to [predict](Network::predict) show none, use `Debug` to see the weights. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{IDENTITY, RELU};
+/// 
+/// let network = make_network!(2, 3 => RELU, 1 => IDENTITY);
+/// 
+/// assert_eq!(network.to_string(), "dense 2 -> 3 (relu), 9 params\ndense 3 -> 1 (identity), 4 params\ntotal 13 params");
+/// ```
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A: StoredActivation> fmt::Display for ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>> ProcessLayer<ROWS, NEURONS, END_S, T, Inherit> {
+
+    /// Instantiates a new layer, accepts the next layer in the linked list as a parameter. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::network::{ProcessLayer, EndLayer};
+    /// 
+    /// let network: ProcessLayer::<3, 2, 1, ProcessLayer<1, 3, 1, EndLayer<1>>> = ProcessLayer::new(ProcessLayer::new(EndLayer()));
+    /// ```
+    pub fn new(next: T) -> ProcessLayer<ROWS, NEURONS, END_S, T> {
+        ProcessLayer {
+            next,
+            activation: Inherit,
+            weights: Matrix::zeros(),
+            biases: Matrix::zeros(),
+            data: Matrix::zeros(),
+            pre_activation: Matrix::zeros(),
+        }
+    }
+
+    /// Instantiates a new layer, accepts the next layer in the linked list as a parameter and also the weights and biases to be used. 
+    /// 
+    /// Useful for instantiating pre-trained networks, will likely be used in later revisions to easily store-and-recall models.  
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::network::{EndLayer, Network, ProcessLayer};
+    /// use mynn::activations::SIGMOID;
+    /// 
+    /// let first_layer_weights = [[-8.086764, -8.086563],[-10.876657, -10.877184],[10.14248, 10.143111]];
+    /// let first_layer_biases = [3.3848374, 4.80076, -15.381532];
+    /// let second_layer_weights = [[-2.4123971, -6.627293, -8.613715]];
+    /// let second_layer_biases = [4.3186426];
+    /// 
+    /// let mut network: ProcessLayer<3, 2, 1, ProcessLayer<1, 3, 1, EndLayer<1>>> = 
+    ///     ProcessLayer::new_with(
+    ///         ProcessLayer::new_with(EndLayer(), second_layer_weights, second_layer_biases), 
+    ///         first_layer_weights, 
+    ///         first_layer_biases
+    ///     );
+    /// 
+    /// network.predict([1.0, 1.0], &SIGMOID);
+    /// ```
+    pub fn new_with(next: T, weights: [[Float; NEURONS]; ROWS], biases: [Float; ROWS]) -> ProcessLayer<ROWS, NEURONS, END_S, T> {
+        ProcessLayer {
+            next,
+            activation: Inherit,
+            weights: Matrix::from(weights),
+            biases: Matrix::from([biases]).transpose(),
+            data: Matrix::zeros(),
+            pre_activation: Matrix::zeros(),
+        }
+    }
+
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A> ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+
+    /// Consumes the layer, returning it with the given activation function applied to its output in place of the current one. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::network::{EndLayer, Network, ProcessLayer};
+    /// use mynn::activations::{RELU, SIGMOID};
+    /// 
+    /// let mut network = ProcessLayer::<3, 2, 1, _>::new(
+    ///     ProcessLayer::<1, 3, 1, EndLayer<1>>::new(EndLayer()).with_activation(SIGMOID)
+    /// ).with_activation(RELU);
+    /// 
+    /// network.predict([1.0, 0.0], &SIGMOID);
+    /// ```
+    pub fn with_activation<B>(self, activation: B) -> ProcessLayer<ROWS, NEURONS, END_S, T, B> {
+        ProcessLayer {
+            next: self.next,
+            activation,
+            weights: self.weights,
+            biases: self.biases,
+            data: self.data,
+            pre_activation: self.pre_activation,
+        }
+    }
+
+    /// Makes a single step of unsupervised Hebbian learning, strengthening the weights between the inputs and the 
+    /// outputs they excite, returning the outputs, the weighted sums of the data without the biases or activation. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `data` The data to learn from, which should be centred around zero. 
+    /// * `rule` The rule normalising the weights. 
+    pub fn hebbian_step(&mut self, l_rate: Float, data: [Float; NEURONS], rule: HebbianRule) -> [Float; ROWS] {
+        let outputs: [Float; ROWS] = core::array::from_fn(|i| {
+            self.weights.data[i].iter().zip(data).map(|(weight, x)| weight * x).sum()
+        });
+        let mut residual = data;
+        for (weights, &output) in self.weights.data.iter_mut().zip(outputs.iter()) {
+            for (weight, residual) in weights.iter_mut().zip(residual.iter_mut()) {
+                let remaining = match rule {
+                    HebbianRule::Oja => *residual - output * *weight,
+                    HebbianRule::Sanger => {
+                        *residual -= output * *weight;
+                        *residual
+                    }
+                };
+                *weight += l_rate * output * remaining;
+            }
+        }
+        outputs
+    }
+
+    /// Trains the weights of this layer alone without targets, learning feature detectors such as from unlabelled 
+    /// sensor readings, by [Hebbian learning](ProcessLayer::hebbian_step) upon each sample in turn. 
+    /// 
+    /// The biases and the following layers are left untouched, for a head to be trained upon the features after. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate, commonly small such as `0.01`. 
+    /// * `inputs` Reference to an array of samples, which should be centred around zero. 
+    /// * `epochs` Number of passes over the samples. 
+    /// * `rule` The rule normalising the weights. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::network::{EndLayer, Network, ProcessLayer};
+    /// use mynn::training::HebbianRule;
+    /// 
+    /// // Readings varying most along the first axis and less along the second. 
+    /// let inputs = [[3.0, 1.0], [-3.0, 1.0], [3.0, -1.0], [-3.0, -1.0]];
+    /// let mut layer = ProcessLayer::<2, 2, 2, _>::new(EndLayer()).with_seed(1);
+    /// 
+    /// layer.train_hebbian(0.01, &inputs, 2_000, HebbianRule::Sanger);
+    /// 
+    /// let [first, second] = layer.weights.data;
+    /// assert!((first[0].abs() - 1.0).abs() < 0.05 && first[1].abs() < 0.05);
+    /// assert!((second[1].abs() - 1.0).abs() < 0.05 && second[0].abs() < 0.05);
+    /// ```
+    pub fn train_hebbian<const DATA_S: usize>(&mut self, l_rate: Float, inputs: &[[Float; NEURONS]; DATA_S], epochs: usize, rule: HebbianRule) {
+        for _ in 0..epochs {
+            for &data in inputs {
+                self.hebbian_step(l_rate, data, rule);
+            }
+        }
+    }
+
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A: StoredActivation> Layer<NEURONS, END_S> for ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    const OUTPUTS: usize = ROWS;
+    const PARAMS: usize = ROWS * NEURONS + ROWS + A::PARAMS + T::PARAMS;
+
+    fn feed_forward<F: LayerActivation + ?Sized>(&mut self, feed: Matrix<NEURONS, 1>, act: &F) -> [Float; END_S] {
+        self.data = feed;
+        self.pre_activation = self.weights.multiply(&self.data)
+            .add(&self.biases);
+        let mut result = self.pre_activation.clone();
+        self.activation.activate_or(act, T::IS_END, result.data.as_flattened_mut());
+        self.next.feed_forward(result, act)
+    }
+
+    fn back_propagate<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &F, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.next);
+
+        self.activation.gather_gradients(self.pre_activation.data.as_flattened(), errors.data.as_flattened(), gradients.activation.as_mut());
+        let mut deltas = errors;
+        if !(T::IS_END && loss.fused()) {
+            let mut derivatives = self.pre_activation.clone();
+            self.activation.derive_errors_or(act, T::IS_END, derivatives.data.as_flattened_mut(), deltas.data.as_flattened_mut());
+        }
+        let back_errors = self.weights.transpose().multiply(&deltas);
+
+        subtract_gradients(gradients.weights.as_flattened_mut(), deltas.multiply(&self.data.transpose()).data.as_flattened());
+        subtract_gradients(&mut gradients.biases, deltas.data.as_flattened());
+
+        BackProps(back_errors)
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        update_params(
+            optimizer, 
+            step, 
+            self.weights.data.as_flattened_mut(), 
+            gradients.weights.as_flattened_mut(), 
+            state.weights.as_flattened_mut(), 
+            true
+        );
+        update_params(
+            optimizer, 
+            step, 
+            self.biases.data.as_flattened_mut(), 
+            &mut gradients.biases, 
+            &mut state.biases, 
+            false
+        );
+        update_params(optimizer, step, self.activation.params_mut(), gradients.activation.as_mut(), state.activation.as_mut(), false);
+        if let Some(max_norm) = step.max_norm {
+            for row in self.weights.data.iter_mut() {
+                limit_norm(row, max_norm);
+            }
+        }
+        self.next.apply_gradients(optimizer, step, &mut gradients.next, &mut state.next);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.weights = Matrix::random_with(rng);
+        if let Some(limit) = self.activation.weight_limit(NEURONS) {
+            self.weights = self.weights.map(&|x| x * limit);
+        }
+        self.biases = Matrix::zeros();
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("dense", NEURONS, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("weights", self.weights.data.as_flattened());
+        visitor.params("biases", self.biases.data.as_flattened());
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params());
+        }
+        self.next.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("dense", NEURONS, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("weights", self.weights.data.as_flattened_mut());
+        visitor.params("biases", self.biases.data.as_flattened_mut());
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params_mut());
+        }
+        self.next.visit_mut(visitor);
+    }
+
+    type Snapshot = LayerSnapshot<Float, ROWS, NEURONS, A::Params<Float>, T::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        LayerSnapshot {
+            weights: self.weights.data,
+            biases: self.biases.transpose().data[0],
+            activation: self.activation.save_params(),
+            next: self.next.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.weights = Matrix::from(snapshot.weights);
+        self.biases = Matrix::from([snapshot.biases]).transpose();
+        self.activation.load_params(&snapshot.activation);
+        self.next.load_params(&snapshot.next);
+    }
+
+    type StateSnapshot = LayerSnapshot<OptimizerState, ROWS, NEURONS, A::Params<OptimizerState>, T::StateSnapshot>;
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: Layer<ROWS, END_S>, A> ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<ROWS, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> ProcessLayer<ROWS, NEURONS, NEW_END, U, A> {
+        ProcessLayer {
+            next: map(self.next),
+            activation: self.activation,
+            weights: self.weights,
+            biases: self.biases,
+            data: self.data,
+            pre_activation: self.pre_activation
+        }
+    }
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, T: AppendHead<ROWS, END_S>, A: StoredActivation> AppendHead<NEURONS, END_S> for ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = ProcessLayer<ROWS, NEURONS, NEW_END, T::Appended<NEW_END, HEAD>, A>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const ROWS: usize, const NEURONS: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<ROWS, END_S, TRUNK_S>, A: StoredActivation> DropHead<NEURONS, END_S, TRUNK_S> for ProcessLayer<ROWS, NEURONS, END_S, T, A> {
+    type Trunk = ProcessLayer<ROWS, NEURONS, TRUNK_S, T::Trunk, A>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, A: StoredActivation> DropHead<NEURONS, END_S, NEURONS> for ProcessLayer<END_S, NEURONS, END_S, EndLayer<END_S>, A> {
+    type Trunk = EndLayer<NEURONS>;
+
+    fn drop_head(self) -> Self::Trunk {
+        EndLayer()
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, A: StoredActivation> DropHead<NEURONS, END_S, NEURONS> for ProcessLayer<END_S, NEURONS, END_S, SoftmaxLayer<END_S, END_S, EndLayer<END_S>>, A> {
+    type Trunk = EndLayer<NEURONS>;
+
+    fn drop_head(self) -> Self::Trunk {
+        EndLayer()
+    }
+}
+
+
+/// Implements the [Layer] methods dealing with parameters for a layer without any, passing each on to `self.next`, 
+/// visiting it as `$kind` fed `$inputs` values. 
+macro_rules! pass_params {
+    ($kind:expr, $inputs:expr) => {
+        fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+            self.next.apply_gradients(optimizer, step, gradients, state);
+        }
+
+        fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+            visitor.layer($kind, $inputs, Self::OUTPUTS);
+            self.next.visit(visitor);
+        }
+
+        fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+            visitor.layer($kind, $inputs, Self::OUTPUTS);
+            self.next.visit_mut(visitor);
+        }
+
+        type Snapshot = T::Snapshot;
+
+        fn save_params(&self) -> Self::Snapshot {
+            self.next.save_params()
+        }
+
+        fn load_params(&mut self, snapshot: &Self::Snapshot) {
+            self.next.load_params(snapshot);
+        }
+
+        type StateSnapshot = T::StateSnapshot;
+    };
+}
+
+/// Layer randomly zeroing each value it's fed with probability `rate` while training, scaling the rest up so their 
+/// expected sum is unchanged, during prediction the values are passed on untouched. 
+/// 
+/// Keeps neurons from relying on one another, reducing overfitting, commonly placed after a hidden layer. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of values passed through this layer. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{RELU, SIGMOID};
+/// use mynn::loss::MSE;
+/// use mynn::network::{DropoutLayer, Network};
+/// 
+/// let mut network = make_network!(2, 8 => RELU, [|next| DropoutLayer::new(next, 0.25)], 1 => SIGMOID).with_seed(1);
+/// network.train(0.1, [[0.0, 1.0], [1.0, 0.0]], [[1.0], [0.0]], 500, &SIGMOID, &MSE);
+/// 
+/// // Nothing is dropped when predicting. 
+/// assert_eq!(network.predict([0.0, 1.0], &SIGMOID), network.predict([0.0, 1.0], &SIGMOID));
+/// ```
+pub struct DropoutLayer<const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The probability of each value being zeroed while training, between `0` and `1`. 
+    pub rate: Float,
+    /// Whether the network is being trained, values are only dropped while it is. 
+    pub training: bool,
+    /// The random number generator choosing which values are dropped, reseeded when the network is 
+    /// [randomized](Network::randomize). 
+    pub rng: Rng,
+    /// The scale applied to each value in the last feed forward, `0` for those dropped. 
+    pub mask: Matrix<NEURONS, 1>
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> DropoutLayer<NEURONS, END_S, T> {
+
+    /// Instantiates a new dropout layer, accepts the next layer in the linked list and the probability of each 
+    /// value being dropped. 
+    pub fn new(next: T, rate: Float) -> DropoutLayer<NEURONS, END_S, T> {
+        DropoutLayer {
+            next,
+            rate,
+            training: false,
+            rng: Rng::with_seed(0),
+            mask: Matrix::zeros()
+        }
+    }
+
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> fmt::Debug for DropoutLayer<NEURONS, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"rate\"", &self.rate)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> PartialEq for DropoutLayer<NEURONS, END_S, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> fmt::Display for DropoutLayer<NEURONS, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> Layer<NEURONS, END_S> for DropoutLayer<NEURONS, END_S, T> {
+    const IS_END: bool = T::IS_END;
+    const OUTPUTS: usize = NEURONS;
+    const PARAMS: usize = T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<NEURONS, 1>, act: &A) -> [Float; END_S] {
+        for scale in self.mask.data.as_flattened_mut() {
+            *scale = if !self.training {
+                1.0
+            } else if uniform(&mut self.rng) < self.rate {
+                0.0
+            } else {
+                1.0 / (1.0 - self.rate)
+            };
+        }
+        self.next.feed_forward(feed.dot_multiply(&self.mask), act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, gradients);
+        BackProps(errors.dot_multiply(&self.mask))
+    }
+
+    pass_params!("dropout", NEURONS);
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.rng = rng.fork();
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> DropoutLayer<NEURONS, END_S, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<NEURONS, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> DropoutLayer<NEURONS, NEW_END, U> {
+        DropoutLayer {
+            next: map(self.next),
+            rate: self.rate,
+            training: self.training,
+            rng: self.rng,
+            mask: self.mask
+        }
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: AppendHead<NEURONS, END_S>> AppendHead<NEURONS, END_S> for DropoutLayer<NEURONS, END_S, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = DropoutLayer<NEURONS, NEW_END, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<NEURONS, END_S, TRUNK_S>> DropHead<NEURONS, END_S, TRUNK_S> for DropoutLayer<NEURONS, END_S, T> {
+    type Trunk = DropoutLayer<NEURONS, TRUNK_S, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Generic behaviour for a layer without parameters, a function of the values it's fed and of what it cached from 
+/// them, placed in a network within a [TransformLayer] which does the rest of the work of a [Layer]. 
+/// 
+/// # Type Parameters
+/// * `IN` The number of values it's fed. 
+/// * `OUT` The number of values it passes on. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::{IDENTITY, SIGMOID};
+/// use mynn::loss::MSE;
+/// use mynn::network::{Network, Transform, TransformLayer};
+/// 
+/// // Squares each value, keeping the values fed for the backward pass. 
+/// #[derive(Debug)]
+/// struct Square<const N: usize>([Float; N]);
+/// 
+/// impl<const N: usize> Transform<N, N> for Square<N> {
+///     const KIND: &'static str = "square";
+/// 
+///     fn forward(&mut self, inputs: [Float; N]) -> [Float; N] {
+///         self.0 = inputs;
+///         inputs.map(|x| x * x)
+///     }
+/// 
+///     fn backward(&mut self, errors: [Float; N]) -> [Float; N] {
+///         core::array::from_fn(|i| 2.0 * self.0[i] * errors[i])
+///     }
+/// }
+/// 
+/// let inputs = [[0.0, 0.5], [0.5, 0.0], [0.5, 0.5], [1.0, 0.0], [0.0, 1.0]];
+/// let targets = inputs.map(|[x, y]| [x * x + y * y]);
+/// let mut network = make_network!(2, [|next| TransformLayer::new(next, Square([0.0; 2]))], 1 => IDENTITY).with_seed(1);
+/// network.train(0.1, inputs, targets, 2_000, &SIGMOID, &MSE);
+/// 
+/// assert!((network.predict([1.0, 1.0], &SIGMOID)[0] - 2.0).abs() < 1e-2);
+/// ```
+pub trait Transform<const IN: usize, const OUT: usize>: fmt::Debug {
+    /// A short name for the kind of layer given to [visitors](Visitor::layer), `"transform"` by default. 
+    const KIND: &'static str = "transform";
+
+    /// Gives the values passed on for the values fed. 
+    fn forward(&mut self, inputs: [Float; IN]) -> [Float; OUT];
+
+    /// Gives the errors with respect to the values last fed from the errors with respect to the values passed on, 
+    /// each the sum of the errors it led to times the derivative of [forward](Transform::forward). 
+    fn backward(&mut self, errors: [Float; OUT]) -> [Float; IN];
+}
+
+/// Layer without parameters passing the values it's fed through a [Transform], so a custom layer such as a fixed 
+/// scaling or a reshaping need only give its forward and backward passes. 
+/// 
+/// # Type Parameters
+/// * `IN` The number of values this layer is fed. 
+/// * `OUT` The number of values this layer passes on, the number of neurons in the next layer. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `X` The type of the transform, must implement [Transform]. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+pub struct TransformLayer<const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The transform applied to the values fed. 
+    pub transform: X
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> TransformLayer<IN, OUT, END_S, X, T> {
+
+    /// Instantiates a new transform layer, accepts the next layer in the linked list and the transform. 
+    pub fn new(next: T, transform: X) -> TransformLayer<IN, OUT, END_S, X, T> {
+        TransformLayer {
+            next,
+            transform
+        }
+    }
+
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> fmt::Debug for TransformLayer<IN, OUT, END_S, X, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"transform\"", &self.transform)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> PartialEq for TransformLayer<IN, OUT, END_S, X, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> fmt::Display for TransformLayer<IN, OUT, END_S, X, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> Layer<IN, END_S> for TransformLayer<IN, OUT, END_S, X, T> {
+    const OUTPUTS: usize = OUT;
+    const PARAMS: usize = T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &A) -> [Float; END_S] {
+        let outputs = self.transform.forward(feed.transpose().data[0]);
+        self.next.feed_forward(Matrix::from([outputs]).transpose(), act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, gradients);
+        BackProps(Matrix::from([self.transform.backward(errors.transpose().data[0])]).transpose())
+    }
+
+    pass_params!(X::KIND, IN);
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: Layer<OUT, END_S>> TransformLayer<IN, OUT, END_S, X, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<OUT, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> TransformLayer<IN, OUT, NEW_END, X, U> {
+        TransformLayer {
+            next: map(self.next),
+            transform: self.transform
+        }
+    }
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, X: Transform<IN, OUT>, T: AppendHead<OUT, END_S>> AppendHead<IN, END_S> for TransformLayer<IN, OUT, END_S, X, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = TransformLayer<IN, OUT, NEW_END, X, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const IN: usize, const OUT: usize, const END_S: usize, const TRUNK_S: usize, X: Transform<IN, OUT>, T: DropHead<OUT, END_S, TRUNK_S>> DropHead<IN, END_S, TRUNK_S> for TransformLayer<IN, OUT, END_S, X, T> {
+    type Trunk = TransformLayer<IN, OUT, TRUNK_S, X, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Classifier head normalising the values it's fed into class probabilities that sum to `1`, placed after the final 
+/// layer, which should give raw scores such as with an [IDENTITY](crate::activations::IDENTITY) activation. 
+/// 
+/// With [SOFTMAX_CROSS_ENTROPY](crate::loss::SOFTMAX_CROSS_ENTROPY) the gradient is passed straight back to the scores, 
+/// otherwise it's taken through the full softmax Jacobian. [classify](Network::classify) gives the most likely class. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of classes. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::{IDENTITY, SIGMOID};
+/// use mynn::loss::SOFTMAX_CROSS_ENTROPY;
+/// use mynn::network::{Network, SoftmaxLayer};
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+/// let targets = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+/// let mut network = make_network!(2, 4 => SIGMOID, 3 => IDENTITY, [SoftmaxLayer::new]).with_seed(5);
+/// 
+/// network.train(0.1, inputs, targets, 1_000, &SIGMOID, &SOFTMAX_CROSS_ENTROPY);
+/// 
+/// let probabilities = network.predict([0.0, 1.0], &SIGMOID);
+/// assert!((probabilities.iter().sum::<Float>() - 1.0).abs() < 1e-5);
+/// assert_eq!(network.classify([0.0, 1.0], &SIGMOID), 1);
+/// ```
+pub struct SoftmaxLayer<const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The probabilities given in the last feed forward. 
+    pub probabilities: Matrix<NEURONS, 1>
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> SoftmaxLayer<NEURONS, END_S, T> {
+
+    /// Instantiates a new softmax layer, accepts the next layer in the linked list as a parameter. 
+    pub fn new(next: T) -> SoftmaxLayer<NEURONS, END_S, T> {
+        SoftmaxLayer {
+            next,
+            probabilities: Matrix::zeros()
+        }
+    }
+
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> fmt::Debug for SoftmaxLayer<NEURONS, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"softmax\"", &NEURONS)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> PartialEq for SoftmaxLayer<NEURONS, END_S, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> fmt::Display for SoftmaxLayer<NEURONS, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> Layer<NEURONS, END_S> for SoftmaxLayer<NEURONS, END_S, T> {
+    const OUTPUTS: usize = NEURONS;
+    const PARAMS: usize = T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<NEURONS, 1>, act: &A) -> [Float; END_S] {
+        self.probabilities = feed;
+        Softmax.activate(self.probabilities.data.as_flattened_mut());
+        self.next.feed_forward(self.probabilities.clone(), act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, gradients);
+        if T::IS_END && loss.fused() {
+            return BackProps(errors);
+        }
+        let weighted = errors.dot_multiply(&self.probabilities).data.as_flattened().iter().sum::<Float>();
+        BackProps(errors.map(&|x| x - weighted).dot_multiply(&self.probabilities))
+    }
+
+    pass_params!("softmax", NEURONS);
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: Layer<NEURONS, END_S>> SoftmaxLayer<NEURONS, END_S, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<NEURONS, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> SoftmaxLayer<NEURONS, NEW_END, U> {
+        SoftmaxLayer {
+            next: map(self.next),
+            probabilities: self.probabilities
+        }
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, T: AppendHead<NEURONS, END_S>> AppendHead<NEURONS, END_S> for SoftmaxLayer<NEURONS, END_S, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = SoftmaxLayer<NEURONS, NEW_END, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<NEURONS, END_S, TRUNK_S>> DropHead<NEURONS, END_S, TRUNK_S> for SoftmaxLayer<NEURONS, END_S, T> {
+    type Trunk = SoftmaxLayer<NEURONS, TRUNK_S, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// The kernels of a [Conv2dLayer], or a value kept for each of their parameters, indexed by output channel, row, 
+/// column then input channel. 
+pub type Kernels<P, const C_IN: usize, const C_OUT: usize, const K: usize> = [[[[P; C_IN]; K]; K]; C_OUT];
+
+/// Two dimensional convolution layer, sliding `C_OUT` square kernels of size `K` over an image `H` by `W` with 
+/// `C_IN` channels, without padding and with a stride of `1`. 
+/// 
+/// Images are flattened row by row with the channels of each pixel next to one another, as is the output, 
+/// `H - K + 1` by `W - K + 1` with `C_OUT` channels. Const generics can't yet compute these sizes, so they're given 
+/// as `IN` and `OUT` and checked when the layer is instantiated, within [make_network](crate::make_network) they 
+/// can be left as `_` to be inferred. 
+/// 
+/// # Type Parameters
+/// * `H` The height of the image. 
+/// * `W` The width of the image. 
+/// * `C_IN` The number of channels of the image. 
+/// * `C_OUT` The number of kernels, and channels of the output. 
+/// * `K` The height and width of each kernel. 
+/// * `IN` The number of values this layer is fed, `H * W * C_IN`. 
+/// * `OUT` The number of values this layer passes on, `(H - K + 1) * (W - K + 1) * C_OUT`. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// * `A` The activation function applied to this layer's output, defaults to [Inherit]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::network::{Conv2dLayer, Network};
+/// 
+/// // 4x4 images of a vertical or horizontal line. 
+/// let vertical = [
+///     0.0, 1.0, 0.0, 0.0, 
+///     0.0, 1.0, 0.0, 0.0, 
+///     0.0, 1.0, 0.0, 0.0, 
+///     0.0, 1.0, 0.0, 0.0
+/// ];
+/// let horizontal = [
+///     0.0, 0.0, 0.0, 0.0, 
+///     0.0, 0.0, 0.0, 0.0, 
+///     1.0, 1.0, 1.0, 1.0, 
+///     0.0, 0.0, 0.0, 0.0
+/// ];
+/// let mut network = make_network!(16, 8 => [Conv2dLayer::<4, 4, 1, 2, 3, _, _, _, _>::new], 1 => SIGMOID).with_seed(2);
+/// 
+/// network.train(0.5, [vertical, horizontal], [[1.0], [0.0]], 1_000, &SIGMOID, &MSE);
+/// 
+/// assert!(network.predict(vertical, &SIGMOID)[0] > 0.9);
+/// assert!(network.predict(horizontal, &SIGMOID)[0] < 0.1);
+/// ```
+pub struct Conv2dLayer<const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A = Inherit> {
+    /// The next layer. 
+    pub next: T,
+    /// The activation function applied to the output of this layer, see [StoredActivation]. 
+    pub activation: A,
+    pub kernels: Kernels<Float, C_IN, C_OUT, K>,
+    /// The bias added to each output channel. 
+    pub biases: [Float; C_OUT],
+    /// The image that was last passed in during a feed forward, used to make corrections during back propagation. 
+    pub data: [Float; IN],
+    /// The outputs before the activation function was applied in the last feed forward. 
+    pub pre_activation: [Float; OUT]
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, Inherit> {
+
+    /// Instantiates a new convolution layer with zeroed kernels, accepts the next layer in the linked list as a parameter. 
+    /// 
+    /// Fails to compile should the kernel not fit within the image, or `IN` and `OUT` not match the image and kernel sizes. 
+    pub fn new(next: T) -> Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T> {
+        const { assert!(K > 0 && K <= H && K <= W, "the kernel must fit within the image") };
+        const { assert!(IN == H * W * C_IN, "IN must be H * W * C_IN") };
+        const { assert!(OUT == (H - K + 1) * (W - K + 1) * C_OUT, "OUT must be (H - K + 1) * (W - K + 1) * C_OUT") };
+        Conv2dLayer {
+            next,
+            activation: Inherit,
+            kernels: [[[[0.0; C_IN]; K]; K]; C_OUT],
+            biases: [0.0; C_OUT],
+            data: [0.0; IN],
+            pre_activation: [0.0; OUT]
+        }
+    }
+
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A> Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+
+    /// Consumes the layer, returning it with the given activation function applied to its output in place of the current one. 
+    pub fn with_activation<B>(self, activation: B) -> Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, B> {
+        Conv2dLayer {
+            next: self.next,
+            activation,
+            kernels: self.kernels,
+            biases: self.biases,
+            data: self.data,
+            pre_activation: self.pre_activation
+        }
+    }
+
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A> fmt::Debug for Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"kernels\"", &self.kernels)
+            .field("\"biases\"", &self.biases)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A: StoredActivation> PartialEq for Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A: StoredActivation> fmt::Display for Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A: StoredActivation> Layer<IN, END_S> for Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    const OUTPUTS: usize = OUT;
+    const PARAMS: usize = C_OUT * K * K * C_IN + C_OUT + A::PARAMS + T::PARAMS;
+
+    fn feed_forward<F: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &F) -> [Float; END_S] {
+        self.data.copy_from_slice(feed.data.as_flattened());
+        for y in 0..H - K + 1 {
+            for x in 0..W - K + 1 {
+                for out in 0..C_OUT {
+                    let mut sum = self.biases[out];
+                    for ky in 0..K {
+                        for kx in 0..K {
+                            let pixel = ((y + ky) * W + x + kx) * C_IN;
+                            for channel in 0..C_IN {
+                                sum += self.kernels[out][ky][kx][channel] * self.data[pixel + channel];
+                            }
+                        }
+                    }
+                    self.pre_activation[(y * (W - K + 1) + x) * C_OUT + out] = sum;
+                }
+            }
+        }
+        let mut result = Matrix::<OUT, 1>::zeros();
+        result.data.as_flattened_mut().copy_from_slice(&self.pre_activation);
+        self.activation.activate_or(act, T::IS_END, result.data.as_flattened_mut());
+        self.next.feed_forward(result, act)
+    }
+
+    fn back_propagate<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &F, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.next);
+        let errors = errors.data.as_flattened();
+
+        self.activation.gather_gradients(&self.pre_activation, errors, gradients.activation.as_mut());
+        let mut deltas = [0.0; OUT];
+        deltas.copy_from_slice(errors);
+        if !(T::IS_END && loss.fused()) {
+            let mut derivatives = self.pre_activation;
+            self.activation.derive_errors_or(act, T::IS_END, &mut derivatives, &mut deltas);
+        }
+
+        let mut back_errors = Matrix::<IN, 1>::zeros();
+        for y in 0..H - K + 1 {
+            for x in 0..W - K + 1 {
+                for out in 0..C_OUT {
+                    let index = (y * (W - K + 1) + x) * C_OUT + out;
+                    let delta = deltas[index];
+                    gradients.biases[out] -= delta;
+                    for ky in 0..K {
+                        for kx in 0..K {
+                            let pixel = ((y + ky) * W + x + kx) * C_IN;
+                            for channel in 0..C_IN {
+                                gradients.kernels[out][ky][kx][channel] -= delta * self.data[pixel + channel];
+                                back_errors.data[pixel + channel][0] += self.kernels[out][ky][kx][channel] * delta;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        BackProps(back_errors)
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        update_params(
+            optimizer, 
+            step, 
+            self.kernels.as_flattened_mut().as_flattened_mut().as_flattened_mut(), 
+            gradients.kernels.as_flattened_mut().as_flattened_mut().as_flattened_mut(), 
+            state.kernels.as_flattened_mut().as_flattened_mut().as_flattened_mut(), 
+            true
+        );
+        update_params(optimizer, step, &mut self.biases, &mut gradients.biases, &mut state.biases, false);
+        update_params(optimizer, step, self.activation.params_mut(), gradients.activation.as_mut(), state.activation.as_mut(), false);
+        if let Some(max_norm) = step.max_norm {
+            for kernel in self.kernels.iter_mut() {
+                limit_norm(kernel.as_flattened_mut().as_flattened_mut(), max_norm);
+            }
+        }
+        self.next.apply_gradients(optimizer, step, &mut gradients.next, &mut state.next);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        for param in self.kernels.as_flattened_mut().as_flattened_mut().as_flattened_mut() {
+            *param = uniform(rng) * 2.0 - 1.0;
+        }
+        self.biases = [0.0; C_OUT];
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("conv2d", IN, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("kernels", self.kernels.as_flattened().as_flattened().as_flattened());
+        visitor.params("biases", &self.biases);
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params());
+        }
+        self.next.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("conv2d", IN, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("kernels", self.kernels.as_flattened_mut().as_flattened_mut().as_flattened_mut());
+        visitor.params("biases", &mut self.biases);
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params_mut());
+        }
+        self.next.visit_mut(visitor);
+    }
+
+    type Snapshot = KernelSnapshot<Float, C_IN, C_OUT, K, A::Params<Float>, T::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        KernelSnapshot {
+            kernels: self.kernels,
+            biases: self.biases,
+            activation: self.activation.save_params(),
+            next: self.next.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.kernels = snapshot.kernels;
+        self.biases = snapshot.biases;
+        self.activation.load_params(&snapshot.activation);
+        self.next.load_params(&snapshot.next);
+    }
+
+    type StateSnapshot = KernelSnapshot<OptimizerState, C_IN, C_OUT, K, A::Params<OptimizerState>, T::StateSnapshot>;
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>, A> Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<OUT, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, NEW_END, U, A> {
+        Conv2dLayer {
+            next: map(self.next),
+            activation: self.activation,
+            kernels: self.kernels,
+            biases: self.biases,
+            data: self.data,
+            pre_activation: self.pre_activation
+        }
+    }
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, T: AppendHead<OUT, END_S>, A: StoredActivation> AppendHead<IN, END_S> for Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, NEW_END, T::Appended<NEW_END, HEAD>, A>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const H: usize, const W: usize, const C_IN: usize, const C_OUT: usize, const K: usize, const IN: usize, const OUT: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<OUT, END_S, TRUNK_S>, A: StoredActivation> DropHead<IN, END_S, TRUNK_S> for Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, END_S, T, A> {
+    type Trunk = Conv2dLayer<H, W, C_IN, C_OUT, K, IN, OUT, TRUNK_S, T::Trunk, A>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// One dimensional max pooling layer, passing on the largest of each `P` consecutive values of a sequence of length 
+/// `L` with `C` channels, each channel pooled separately, shrinking the feature maps of a convolution. 
+/// 
+/// Sequences are flattened with the channels of each step next to one another, as is the output, `L / P` long with 
+/// any remaining steps left out. `IN` and `OUT` are checked when the layer is instantiated, within 
+/// [make_network](crate::make_network) they can be left as `_` to be inferred. 
+/// 
+/// # Type Parameters
+/// * `L` The length of the sequence. 
+/// * `C` The number of channels. 
+/// * `P` The size of each pooling window, also its stride. 
+/// * `IN` The number of values this layer is fed, `L * C`. 
+/// * `OUT` The number of values this layer passes on, `L / P * C`. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::IDENTITY;
+/// use mynn::network::{MaxPool1dLayer, Network};
+/// 
+/// let mut network = make_network!(6, 3 => [MaxPool1dLayer::<6, 1, 2, _, _, _, _>::new]);
+/// 
+/// assert_eq!(network.predict([1.0, 3.0, -2.0, -1.0, 5.0, 4.0], &IDENTITY), [3.0, -1.0, 5.0]);
+/// ```
+pub struct MaxPool1dLayer<const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The index of the value each output was taken from in the last feed forward, used to route the errors back 
+    /// during back propagation. 
+    pub indices: [usize; OUT]
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+
+    /// Instantiates a new pooling layer, accepts the next layer in the linked list as a parameter. 
+    /// 
+    /// Fails to compile should the window not fit within the sequence, or `IN` and `OUT` not match its size. 
+    pub fn new(next: T) -> MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+        const { assert!(P > 0 && P <= L, "the window must fit within the sequence") };
+        const { assert!(IN == L * C, "IN must be L * C") };
+        const { assert!(OUT == L / P * C, "OUT must be L / P * C") };
+        MaxPool1dLayer {
+            next,
+            indices: [0; OUT]
+        }
+    }
+
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> fmt::Debug for MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"max_pool\"", &P)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> PartialEq for MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> fmt::Display for MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> Layer<IN, END_S> for MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    const OUTPUTS: usize = OUT;
+    const PARAMS: usize = T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &A) -> [Float; END_S] {
+        let feed = feed.data.as_flattened();
+        let mut result = Matrix::<OUT, 1>::zeros();
+        for step in 0..L / P {
+            for channel in 0..C {
+                let out = step * C + channel;
+                self.indices[out] = (step * P..step * P + P)
+                    .map(|i| i * C + channel)
+                    .fold(step * P * C + channel, |max, i| if feed[i] > feed[max] { i } else { max });
+                result.data[out][0] = feed[self.indices[out]];
+            }
+        }
+        self.next.feed_forward(result, act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, F: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &F, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, gradients);
+        let mut back_errors = Matrix::<IN, 1>::zeros();
+        for (index, error) in self.indices.iter().zip(errors.data.as_flattened()) {
+            back_errors.data[*index][0] += error;
+        }
+        BackProps(back_errors)
+    }
+
+    pass_params!("max_pool1d", IN);
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<OUT, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> MaxPool1dLayer<L, C, P, IN, OUT, NEW_END, U> {
+        MaxPool1dLayer {
+            next: map(self.next),
+            indices: self.indices
+        }
+    }
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: AppendHead<OUT, END_S>> AppendHead<IN, END_S> for MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = MaxPool1dLayer<L, C, P, IN, OUT, NEW_END, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const L: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<OUT, END_S, TRUNK_S>> DropHead<IN, END_S, TRUNK_S> for MaxPool1dLayer<L, C, P, IN, OUT, END_S, T> {
+    type Trunk = MaxPool1dLayer<L, C, P, IN, OUT, TRUNK_S, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Two dimensional max pooling layer, passing on the largest value of each `P` by `P` window of an image `H` by `W` 
+/// with `C` channels, each channel pooled separately, shrinking the feature maps of a [Conv2dLayer]. 
+/// 
+/// Images are flattened row by row with the channels of each pixel next to one another, as is the output, 
+/// `H / P` by `W / P` with any remaining rows or columns left out. `IN` and `OUT` are checked when the layer is 
+/// instantiated, within [make_network](crate::make_network) they can be left as `_` to be inferred. 
+/// 
+/// # Type Parameters
+/// * `H` The height of the image. 
+/// * `W` The width of the image. 
+/// * `C` The number of channels. 
+/// * `P` The height and width of each pooling window, also its stride. 
+/// * `IN` The number of values this layer is fed, `H * W * C`. 
+/// * `OUT` The number of values this layer passes on, `H / P * (W / P) * C`. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{RELU, SIGMOID};
+/// use mynn::network::{Conv2dLayer, MaxPool2dLayer, Network};
+/// 
+/// let mut network = make_network!(
+///     36, 
+///     32 => [|next| Conv2dLayer::<6, 6, 1, 2, 3, _, _, _, _>::new(next).with_activation(RELU)], 
+///     8 => [MaxPool2dLayer::<4, 4, 2, 2, _, _, _, _>::new], 
+///     1 => SIGMOID
+/// ).with_seed(4);
+/// 
+/// network.predict([0.5; 36], &SIGMOID);
+/// ```
+pub struct MaxPool2dLayer<const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The index of the value each output was taken from in the last feed forward, used to route the errors back 
+    /// during back propagation. 
+    pub indices: [usize; OUT]
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+
+    /// Instantiates a new pooling layer, accepts the next layer in the linked list as a parameter. 
+    /// 
+    /// Fails to compile should the window not fit within the image, or `IN` and `OUT` not match its size. 
+    pub fn new(next: T) -> MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+        const { assert!(P > 0 && P <= H && P <= W, "the window must fit within the image") };
+        const { assert!(IN == H * W * C, "IN must be H * W * C") };
+        const { assert!(OUT == H / P * (W / P) * C, "OUT must be H / P * (W / P) * C") };
+        MaxPool2dLayer {
+            next,
+            indices: [0; OUT]
+        }
+    }
+
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> fmt::Debug for MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"max_pool\"", &P)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> PartialEq for MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> fmt::Display for MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> Layer<IN, END_S> for MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    const OUTPUTS: usize = OUT;
+    const PARAMS: usize = T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &A) -> [Float; END_S] {
+        let feed = feed.data.as_flattened();
+        let mut result = Matrix::<OUT, 1>::zeros();
+        for y in 0..H / P {
+            for x in 0..W / P {
+                for channel in 0..C {
+                    let out = (y * (W / P) + x) * C + channel;
+                    let mut max = (y * P * W + x * P) * C + channel;
+                    for py in y * P..y * P + P {
+                        for px in x * P..x * P + P {
+                            let i = (py * W + px) * C + channel;
+                            if feed[i] > feed[max] {
+                                max = i;
+                            }
+                        }
+                    }
+                    self.indices[out] = max;
+                    result.data[out][0] = feed[max];
+                }
+            }
+        }
+        self.next.feed_forward(result, act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, F: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &F, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, gradients);
+        let mut back_errors = Matrix::<IN, 1>::zeros();
+        for (index, error) in self.indices.iter().zip(errors.data.as_flattened()) {
+            back_errors.data[*index][0] += error;
+        }
+        BackProps(back_errors)
+    }
+
+    pass_params!("max_pool2d", IN);
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<OUT, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> MaxPool2dLayer<H, W, C, P, IN, OUT, NEW_END, U> {
+        MaxPool2dLayer {
+            next: map(self.next),
+            indices: self.indices
+        }
+    }
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, T: AppendHead<OUT, END_S>> AppendHead<IN, END_S> for MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = MaxPool2dLayer<H, W, C, P, IN, OUT, NEW_END, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const H: usize, const W: usize, const C: usize, const P: usize, const IN: usize, const OUT: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<OUT, END_S, TRUNK_S>> DropHead<IN, END_S, TRUNK_S> for MaxPool2dLayer<H, W, C, P, IN, OUT, END_S, T> {
+    type Trunk = MaxPool2dLayer<H, W, C, P, IN, OUT, TRUNK_S, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Elman recurrent layer, fed a sequence of `S` steps of `F` features one step at a time, each step's hidden state 
+/// found from the step's features and the hidden state before it, passing on the hidden state after the last step. 
+/// 
+/// Trained with back propagation through time over the `S` steps, each sample a sequence of its own starting from a 
+/// cleared hidden state. Once made [stateful](RecurrentLayer::stateful) the hidden state instead carries on from one 
+/// prediction to the next, so a sequence can be streamed in as it arrives, [reset_state](Network::reset_state) 
+/// clearing it. 
+/// 
+/// Sequences are flattened step by step, the features of each step next to one another. `IN` is checked when the 
+/// layer is instantiated, within [make_network](crate::make_network) it can be left as `_` to be inferred. 
+/// 
+/// # Type Parameters
+/// * `F` The number of features of each step. 
+/// * `HIDDEN` The number of values in the hidden state, passed on to the next layer. 
+/// * `S` The number of steps fed at once. 
+/// * `IN` The number of values this layer is fed, `S * F`. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// * `A` The activation function applied to the hidden state, defaults to [Inherit]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{SIGMOID, TANH};
+/// use mynn::loss::MSE;
+/// use mynn::network::{Network, RecurrentLayer};
+/// 
+/// // Whether a sequence of 4 readings is rising. 
+/// let inputs = [[0.1, 0.4, 0.6, 0.9], [0.8, 0.5, 0.3, 0.1], [0.2, 0.3, 0.5, 0.6], [0.9, 0.6, 0.5, 0.2]];
+/// let targets = [[1.0], [0.0], [1.0], [0.0]];
+/// let mut network = make_network!(
+///     4, 
+///     3 => [|next| RecurrentLayer::<1, 3, 4, _, _, _>::new(next).with_activation(TANH)], 
+///     1 => SIGMOID
+/// ).with_seed(1);
+/// 
+/// network.train(0.1, inputs, targets, 2_000, &SIGMOID, &MSE);
+/// 
+/// assert!(network.predict([0.0, 0.3, 0.5, 0.8], &SIGMOID)[0] > 0.5);
+/// assert!(network.predict([0.7, 0.6, 0.2, 0.0], &SIGMOID)[0] < 0.5);
+/// ```
+pub struct RecurrentLayer<const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A = Inherit> {
+    /// The next layer. 
+    pub next: T,
+    /// The activation function applied to the hidden state, see [StoredActivation]. 
+    pub activation: A,
+    /// The weights applied to each step's features. 
+    pub input_weights: Matrix<HIDDEN, F>,
+    /// The weights applied to the hidden state of the step before. 
+    pub recurrent_weights: Matrix<HIDDEN, HIDDEN>,
+    pub biases: Matrix<HIDDEN, 1>,
+    /// The hidden state after the last step. 
+    pub state: Matrix<HIDDEN, 1>,
+    /// Whether the hidden state carries on from one prediction to the next rather than being cleared. 
+    pub stateful: bool,
+    /// Whether the network is being trained, the hidden state is always cleared before each sample while it is. 
+    pub training: bool,
+    /// The features of each step in the last feed forward. 
+    pub inputs: [Matrix<F, 1>; S],
+    /// The hidden state before each step in the last feed forward. 
+    pub states: [Matrix<HIDDEN, 1>; S],
+    /// The hidden state of each step before the activation function was applied in the last feed forward. 
+    pub pre_activations: [Matrix<HIDDEN, 1>; S]
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>> RecurrentLayer<F, HIDDEN, S, IN, END_S, T, Inherit> {
+
+    /// Instantiates a new recurrent layer with zeroed weights, accepts the next layer in the linked list as a parameter. 
+    /// 
+    /// Fails to compile should there be no steps, or `IN` not match the sequence size. 
+    pub fn new(next: T) -> RecurrentLayer<F, HIDDEN, S, IN, END_S, T> {
+        const { assert!(S > 0, "the sequence must have at least one step") };
+        const { assert!(IN == S * F, "IN must be S * F") };
+        RecurrentLayer {
+            next,
+            activation: Inherit,
+            input_weights: Matrix::zeros(),
+            recurrent_weights: Matrix::zeros(),
+            biases: Matrix::zeros(),
+            state: Matrix::zeros(),
+            stateful: false,
+            training: false,
+            inputs: core::array::from_fn(|_| Matrix::zeros()),
+            states: core::array::from_fn(|_| Matrix::zeros()),
+            pre_activations: core::array::from_fn(|_| Matrix::zeros())
+        }
+    }
+
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A> RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+
+    /// Consumes the layer, returning it with its hidden state carried on from one prediction to the next until 
+    /// [reset](Network::reset_state), for streaming in a sequence a step or few at a time. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::TANH;
+    /// use mynn::network::{Network, RecurrentLayer};
+    /// 
+    /// let mut network = make_network!(1, 2 => [|next| RecurrentLayer::<1, 2, 1, _, _, _>::new(next).stateful()]).with_seed(3);
+    /// 
+    /// let first = network.predict([1.0], &TANH);
+    /// assert_ne!(network.predict([1.0], &TANH), first);
+    /// 
+    /// network.reset_state();
+    /// assert_eq!(network.predict([1.0], &TANH), first);
+    /// ```
+    pub fn stateful(mut self) -> RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+        self.stateful = true;
+        self
+    }
+
+    /// Consumes the layer, returning it with the given activation function applied to its hidden state in place of the current one. 
+    pub fn with_activation<B>(self, activation: B) -> RecurrentLayer<F, HIDDEN, S, IN, END_S, T, B> {
+        RecurrentLayer {
+            next: self.next,
+            activation,
+            input_weights: self.input_weights,
+            recurrent_weights: self.recurrent_weights,
+            biases: self.biases,
+            state: self.state,
+            stateful: self.stateful,
+            training: self.training,
+            inputs: self.inputs,
+            states: self.states,
+            pre_activations: self.pre_activations
+        }
+    }
+
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A> fmt::Debug for RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"input_weights\"", &self.input_weights)
+            .field("\"recurrent_weights\"", &self.recurrent_weights)
+            .field("\"biases\"", &self.biases)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A: StoredActivation> PartialEq for RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A: StoredActivation> fmt::Display for RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A: StoredActivation> Layer<IN, END_S> for RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    const OUTPUTS: usize = HIDDEN;
+    const PARAMS: usize = HIDDEN * F + HIDDEN * HIDDEN + HIDDEN + A::PARAMS + T::PARAMS;
+
+    fn feed_forward<B: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &B) -> [Float; END_S] {
+        if self.training || !self.stateful {
+            self.state = Matrix::zeros();
+        }
+        for (step, features) in feed.data.chunks_exact(F).enumerate() {
+            self.inputs[step].data.copy_from_slice(features);
+            self.states[step] = self.state.clone();
+            self.pre_activations[step] = self.input_weights.multiply(&self.inputs[step])
+                .add(&self.recurrent_weights.multiply(&self.state))
+                .add(&self.biases);
+            self.state = self.pre_activations[step].clone();
+            self.activation.activate_or(act, T::IS_END, self.state.data.as_flattened_mut());
+        }
+        self.next.feed_forward(self.state.clone(), act)
+    }
+
+    fn back_propagate<B: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &B, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(mut errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.next);
+
+        let mut back_errors = Matrix::<IN, 1>::zeros();
+        for step in (0..S).rev() {
+            self.activation.gather_gradients(self.pre_activations[step].data.as_flattened(), errors.data.as_flattened(), gradients.activation.as_mut());
+            let mut deltas = errors;
+            if !(step == S - 1 && T::IS_END && loss.fused()) {
+                let mut derivatives = self.pre_activations[step].clone();
+                self.activation.derive_errors_or(act, T::IS_END, derivatives.data.as_flattened_mut(), deltas.data.as_flattened_mut());
+            }
+
+            let input_errors = self.input_weights.transpose().multiply(&deltas);
+            back_errors.data[step * F..step * F + F].copy_from_slice(&input_errors.data);
+            errors = self.recurrent_weights.transpose().multiply(&deltas);
+
+            subtract_gradients(gradients.input_weights.as_flattened_mut(), deltas.multiply(&self.inputs[step].transpose()).data.as_flattened());
+            subtract_gradients(gradients.recurrent_weights.as_flattened_mut(), deltas.multiply(&self.states[step].transpose()).data.as_flattened());
+            subtract_gradients(&mut gradients.biases, deltas.data.as_flattened());
+        }
+        BackProps(back_errors)
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        update_params(
+            optimizer, 
+            step, 
+            self.input_weights.data.as_flattened_mut(), 
+            gradients.input_weights.as_flattened_mut(), 
+            state.input_weights.as_flattened_mut(), 
+            true
+        );
+        update_params(
+            optimizer, 
+            step, 
+            self.recurrent_weights.data.as_flattened_mut(), 
+            gradients.recurrent_weights.as_flattened_mut(), 
+            state.recurrent_weights.as_flattened_mut(), 
+            true
+        );
+        update_params(
+            optimizer, 
+            step, 
+            self.biases.data.as_flattened_mut(), 
+            &mut gradients.biases, 
+            &mut state.biases, 
+            false
+        );
+        update_params(optimizer, step, self.activation.params_mut(), gradients.activation.as_mut(), state.activation.as_mut(), false);
+        if let Some(max_norm) = step.max_norm {
+            for (input, recurrent) in self.input_weights.data.iter_mut().zip(self.recurrent_weights.data.iter_mut()) {
+                let norm = sqrt(input.iter().chain(recurrent.iter()).map(|x| x * x).sum());
+                if norm > max_norm {
+                    input.iter_mut().chain(recurrent.iter_mut()).for_each(|x| *x *= max_norm / norm);
+                }
+            }
+        }
+        self.next.apply_gradients(optimizer, step, &mut gradients.next, &mut state.next);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.input_weights = Matrix::random_with(rng);
+        self.recurrent_weights = Matrix::random_with(rng);
+        self.biases = Matrix::zeros();
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.training = training;
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.state = Matrix::zeros();
+        self.next.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("recurrent", IN, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("input_weights", self.input_weights.data.as_flattened());
+        visitor.params("recurrent_weights", self.recurrent_weights.data.as_flattened());
+        visitor.params("biases", self.biases.data.as_flattened());
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params());
+        }
+        self.next.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("recurrent", IN, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("input_weights", self.input_weights.data.as_flattened_mut());
+        visitor.params("recurrent_weights", self.recurrent_weights.data.as_flattened_mut());
+        visitor.params("biases", self.biases.data.as_flattened_mut());
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params_mut());
+        }
+        self.next.visit_mut(visitor);
+    }
+
+    type Snapshot = RecurrentSnapshot<Float, F, HIDDEN, A::Params<Float>, T::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        RecurrentSnapshot {
+            input_weights: self.input_weights.data,
+            recurrent_weights: self.recurrent_weights.data,
+            biases: self.biases.transpose().data[0],
+            activation: self.activation.save_params(),
+            next: self.next.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.input_weights = Matrix::from(snapshot.input_weights);
+        self.recurrent_weights = Matrix::from(snapshot.recurrent_weights);
+        self.biases = Matrix::from([snapshot.biases]).transpose();
+        self.activation.load_params(&snapshot.activation);
+        self.next.load_params(&snapshot.next);
+    }
+
+    type StateSnapshot = RecurrentSnapshot<OptimizerState, F, HIDDEN, A::Params<OptimizerState>, T::StateSnapshot>;
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: Layer<HIDDEN, END_S>, A> RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<HIDDEN, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> RecurrentLayer<F, HIDDEN, S, IN, NEW_END, U, A> {
+        RecurrentLayer {
+            next: map(self.next),
+            activation: self.activation,
+            input_weights: self.input_weights,
+            recurrent_weights: self.recurrent_weights,
+            biases: self.biases,
+            state: self.state,
+            stateful: self.stateful,
+            training: self.training,
+            inputs: self.inputs,
+            states: self.states,
+            pre_activations: self.pre_activations
+        }
+    }
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, T: AppendHead<HIDDEN, END_S>, A: StoredActivation> AppendHead<IN, END_S> for RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = RecurrentLayer<F, HIDDEN, S, IN, NEW_END, T::Appended<NEW_END, HEAD>, A>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const F: usize, const HIDDEN: usize, const S: usize, const IN: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<HIDDEN, END_S, TRUNK_S>, A: StoredActivation> DropHead<IN, END_S, TRUNK_S> for RecurrentLayer<F, HIDDEN, S, IN, END_S, T, A> {
+    type Trunk = RecurrentLayer<F, HIDDEN, S, IN, TRUNK_S, T::Trunk, A>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Embedding layer, fed a sequence of integer token IDs below `VOCAB` as [Float]s, passing on the learned `DIM` long 
+/// vector of each in turn, a far smaller model than one fed each token one-hot encoded. 
+/// 
+/// IDs outside the vocabulary are given a zeroed vector. `OUT` is checked when the layer is instantiated, within 
+/// [make_network](crate::make_network) it and `IN` can be left as `_` to be inferred. 
+/// 
+/// # Type Parameters
+/// * `VOCAB` The number of distinct tokens. 
+/// * `DIM` The length of each token's vector. 
+/// * `IN` The number of tokens this layer is fed. 
+/// * `OUT` The number of values this layer passes on, `IN * DIM`. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::network::{EmbeddingLayer, Network};
+/// 
+/// // Two word commands from a vocabulary of 4, "lights on" (0, 2) and "fan on" (1, 2) are wanted, "lights off" (0, 3) isn't. 
+/// let inputs = [[0.0, 2.0], [1.0, 2.0], [0.0, 3.0], [1.0, 3.0]];
+/// let targets = [[1.0], [1.0], [0.0], [0.0]];
+/// let mut network = make_network!(2, 6 => [EmbeddingLayer::<4, 3, _, _, _, _>::new], 1 => SIGMOID).with_seed(7);
+/// 
+/// network.train(0.5, inputs, targets, 500, &SIGMOID, &MSE);
+/// 
+/// assert!(network.predict([1.0, 2.0], &SIGMOID)[0] > 0.9);
+/// assert!(network.predict([1.0, 3.0], &SIGMOID)[0] < 0.1);
+/// ```
+pub struct EmbeddingLayer<const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The vector of each token, one per row. 
+    pub embeddings: Matrix<VOCAB, DIM>,
+    /// The tokens that were last passed in during a feed forward, `None` for those outside the vocabulary. 
+    pub tokens: [Option<usize>; IN]
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+
+    /// Instantiates a new embedding layer with zeroed vectors, accepts the next layer in the linked list as a parameter. 
+    /// 
+    /// Fails to compile should `OUT` not match the number of tokens and length of each vector. 
+    pub fn new(next: T) -> EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+        const { assert!(OUT == IN * DIM, "OUT must be IN * DIM") };
+        EmbeddingLayer {
+            next,
+            embeddings: Matrix::zeros(),
+            tokens: [None; IN]
+        }
+    }
+
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> fmt::Debug for EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"embeddings\"", &self.embeddings)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> PartialEq for EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> fmt::Display for EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> Layer<IN, END_S> for EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    const OUTPUTS: usize = OUT;
+    const PARAMS: usize = VOCAB * DIM + T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &A) -> [Float; END_S] {
+        let mut result = Matrix::<OUT, 1>::zeros();
+        for ((token, [id]), vector) in self.tokens.iter_mut().zip(feed.data).zip(result.data.chunks_exact_mut(DIM)) {
+            *token = (id >= 0.0 && id < VOCAB as Float).then_some(id as usize);
+            if let Some(token) = *token {
+                vector.copy_from_slice(&Matrix::from([self.embeddings.data[token]]).transpose().data);
+            }
+        }
+        self.next.feed_forward(result, act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.next);
+        for (token, errors) in self.tokens.iter().zip(errors.data.as_flattened().chunks_exact(DIM)) {
+            if let Some(token) = *token {
+                subtract_gradients(&mut gradients.embeddings[token], errors);
+            }
+        }
+        // Token IDs can't be corrected, so no errors are passed back. 
+        BackProps(Matrix::zeros())
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        update_params(
+            optimizer, 
+            step, 
+            self.embeddings.data.as_flattened_mut(), 
+            gradients.embeddings.as_flattened_mut(), 
+            state.embeddings.as_flattened_mut(), 
+            true
+        );
+        if let Some(max_norm) = step.max_norm {
+            for vector in self.embeddings.data.iter_mut() {
+                limit_norm(vector, max_norm);
+            }
+        }
+        self.next.apply_gradients(optimizer, step, &mut gradients.next, &mut state.next);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.embeddings = Matrix::random_with(rng);
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.next.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("embedding", IN, Self::OUTPUTS);
+        visitor.params("embeddings", self.embeddings.data.as_flattened());
+        self.next.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("embedding", IN, Self::OUTPUTS);
+        visitor.params("embeddings", self.embeddings.data.as_flattened_mut());
+        self.next.visit_mut(visitor);
+    }
+
+    type Snapshot = EmbeddingSnapshot<Float, VOCAB, DIM, T::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        EmbeddingSnapshot {
+            embeddings: self.embeddings.data,
+            next: self.next.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.embeddings = Matrix::from(snapshot.embeddings);
+        self.next.load_params(&snapshot.next);
+    }
+
+    type StateSnapshot = EmbeddingSnapshot<OptimizerState, VOCAB, DIM, T::StateSnapshot>;
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: Layer<OUT, END_S>> EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<OUT, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> EmbeddingLayer<VOCAB, DIM, IN, OUT, NEW_END, U> {
+        EmbeddingLayer {
+            next: map(self.next),
+            embeddings: self.embeddings,
+            tokens: self.tokens
+        }
+    }
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, T: AppendHead<OUT, END_S>> AppendHead<IN, END_S> for EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = EmbeddingLayer<VOCAB, DIM, IN, OUT, NEW_END, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const VOCAB: usize, const DIM: usize, const IN: usize, const OUT: usize, const END_S: usize, const TRUNK_S: usize, T: DropHead<OUT, END_S, TRUNK_S>> DropHead<IN, END_S, TRUNK_S> for EmbeddingLayer<VOCAB, DIM, IN, OUT, END_S, T> {
+    type Trunk = EmbeddingLayer<VOCAB, DIM, IN, OUT, TRUNK_S, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Skip connection wrapping an inner sub-chain of layers, passing on the sum of the values it's fed and the inner 
+/// layers' outputs, so each block only has to learn a correction to its input, letting far deeper stacks train. 
+/// 
+/// The inner sub-chain is a network of its own, fed and ending with `NEURONS` values, its last layer uses the hidden 
+/// activation rather than the output one. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of values passed through this layer. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `I` The type of the inner sub-chain, must implement [Layer] and end with `NEURONS` values. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{SIGMOID, TANH};
+/// use mynn::loss::MSE;
+/// use mynn::network::{Network, ResidualLayer};
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [[0.0], [1.0], [1.0], [0.0]];
+/// let mut network = make_network!(
+///     2, 
+///     4 => TANH, 
+///     [|next| ResidualLayer::new(make_network!(4, 4 => TANH), next)], 
+///     [|next| ResidualLayer::new(make_network!(4, 4 => TANH), next)], 
+///     1 => SIGMOID
+/// ).with_seed(3);
+/// 
+/// network.train(0.5, inputs, targets, 2_000, &SIGMOID, &MSE);
+/// 
+/// assert!(network.predict([0.0, 1.0], &SIGMOID)[0] > 0.9);
+/// assert!(network.predict([1.0, 1.0], &SIGMOID)[0] < 0.1);
+/// ```
+pub struct ResidualLayer<const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> {
+    /// The next layer. 
+    pub next: T,
+    /// The inner sub-chain whose outputs are added to the values this layer is fed. 
+    pub inner: I,
+    /// The outputs of the inner sub-chain from the last feed forward. 
+    pub outputs: [Float; NEURONS]
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> ResidualLayer<NEURONS, END_S, I, T> {
+
+    /// Instantiates a new residual layer, accepts the inner sub-chain and the next layer in the linked list as parameters. 
+    pub fn new(inner: I, next: T) -> ResidualLayer<NEURONS, END_S, I, T> {
+        ResidualLayer {
+            next,
+            inner,
+            outputs: [0.0; NEURONS]
+        }
+    }
+
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> fmt::Debug for ResidualLayer<NEURONS, END_S, I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"inner\"", &self.inner)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> PartialEq for ResidualLayer<NEURONS, END_S, I, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> fmt::Display for ResidualLayer<NEURONS, END_S, I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> Layer<NEURONS, END_S> for ResidualLayer<NEURONS, END_S, I, T> {
+    const OUTPUTS: usize = NEURONS;
+    const PARAMS: usize = I::PARAMS + T::PARAMS;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<NEURONS, 1>, act: &A) -> [Float; END_S] {
+        self.outputs = self.inner.feed_forward(feed.clone(), &Hidden(act));
+        self.next.feed_forward(feed.add(&Matrix::from([self.outputs]).transpose()), act)
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &A, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.next);
+        // The inner sub-chain's end layer negates the gradients back into errors. 
+        let upstream = errors.transpose().data[0].map(|x| -x);
+        let BackProps(inner_errors) = self.inner.back_propagate(self.outputs, self.outputs, &Hidden(act), &Upstream { gradients: &upstream }, &mut gradients.inner);
+        BackProps(errors.add(&inner_errors))
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        self.inner.apply_gradients(optimizer, step, &mut gradients.inner, &mut state.inner);
+        self.next.apply_gradients(optimizer, step, &mut gradients.next, &mut state.next);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.inner.init_params(rng);
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.inner.set_training(training);
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.inner.clear_state();
+        self.next.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("residual", NEURONS, Self::OUTPUTS);
+        self.inner.visit(visitor);
+        self.next.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("residual", NEURONS, Self::OUTPUTS);
+        self.inner.visit_mut(visitor);
+        self.next.visit_mut(visitor);
+    }
+
+    type Snapshot = ResidualSnapshot<I::Snapshot, T::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        ResidualSnapshot {
+            inner: self.inner.save_params(),
+            next: self.next.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.inner.load_params(&snapshot.inner);
+        self.next.load_params(&snapshot.next);
+    }
+
+    type StateSnapshot = ResidualSnapshot<I::StateSnapshot, T::StateSnapshot>;
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: Layer<NEURONS, END_S>> ResidualLayer<NEURONS, END_S, I, T> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<NEURONS, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> ResidualLayer<NEURONS, NEW_END, I, U> {
+        ResidualLayer {
+            next: map(self.next),
+            inner: self.inner,
+            outputs: self.outputs
+        }
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, T: AppendHead<NEURONS, END_S>> AppendHead<NEURONS, END_S> for ResidualLayer<NEURONS, END_S, I, T> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = ResidualLayer<NEURONS, NEW_END, I, T::Appended<NEW_END, HEAD>>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const NEURONS: usize, const END_S: usize, I: Layer<NEURONS, NEURONS>, const TRUNK_S: usize, T: DropHead<NEURONS, END_S, TRUNK_S>> DropHead<NEURONS, END_S, TRUNK_S> for ResidualLayer<NEURONS, END_S, I, T> {
+    type Trunk = ResidualLayer<NEURONS, TRUNK_S, I, T::Trunk>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Autoencoder layer with tied weights, encoding the `IN` values it's fed into a code of `CODE` values, passing that 
+/// through an inner sub-chain then decoding it back to `IN` values with the transpose of the encoder's weights. 
+/// 
+/// Tying the weights halves the parameters of a symmetric encoder and decoder, fitting tiny models such as on-device 
+/// anomaly detectors, train it with the inputs as the targets then use [reconstruction_error](Network::reconstruction_error). 
+/// The inner sub-chain is commonly an [EndLayer], or another autoencoder layer to stack them, its last layer uses the 
+/// hidden activation. The code uses the hidden activation, the reconstruction uses the output activation when this 
+/// is the final layer. 
+/// 
+/// # Type Parameters
+/// * `IN` The number of values this layer is fed and passes on. 
+/// * `CODE` The number of values in the code. 
+/// * `END_S` The number of neurons in the final layer, used when passing back an array of predictions. 
+/// * `I` The type of the inner sub-chain, must implement [Layer] and end with `CODE` values. 
+/// * `T` The type of the next layer, must implement [Layer]. 
+/// * `A` The activation function applied to the code and reconstruction, see [StoredActivation]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::network::{AutoencoderLayer, EndLayer, Network};
+/// 
+/// let normal = [[1.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 1.0]];
+/// let mut network = make_network!(4, [|next| AutoencoderLayer::new(EndLayer::<2>(), next)]).with_seed(4);
+/// 
+/// network.train(1.0, normal, normal, 2_000, &SIGMOID, &MSE);
+/// 
+/// let normal_error = network.reconstruction_error([1.0, 1.0, 0.0, 0.0], &SIGMOID, &MSE);
+/// let anomaly_error = network.reconstruction_error([1.0, 0.0, 1.0, 0.0], &SIGMOID, &MSE);
+/// assert!(normal_error < anomaly_error);
+/// ```
+pub struct AutoencoderLayer<const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A = Inherit> {
+    /// The next layer. 
+    pub next: T,
+    /// The inner sub-chain the code is passed through before being decoded. 
+    pub inner: I,
+    /// The activation function applied to the code and reconstruction, see [StoredActivation]. 
+    pub activation: A,
+    /// The encoder's weights, the decoder uses their transpose. 
+    pub weights: Matrix<CODE, IN>,
+    pub encode_biases: Matrix<CODE, 1>,
+    pub decode_biases: Matrix<IN, 1>,
+    /// The data that was last passed in during a feed forward, used to make corrections during back propagation. 
+    pub data: Matrix<IN, 1>,
+    /// The code before the activation function was applied in the last feed forward. 
+    pub encode_pre_activation: Matrix<CODE, 1>,
+    /// The outputs of the inner sub-chain from the last feed forward, the values decoded. 
+    pub code: Matrix<CODE, 1>,
+    /// The reconstruction before the activation function was applied in the last feed forward. 
+    pub decode_pre_activation: Matrix<IN, 1>
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>> AutoencoderLayer<IN, CODE, END_S, I, T, Inherit> {
+
+    /// Instantiates a new autoencoder layer with zeroed weights, accepts the inner sub-chain and the next layer in the 
+    /// linked list as parameters. 
+    pub fn new(inner: I, next: T) -> AutoencoderLayer<IN, CODE, END_S, I, T> {
+        AutoencoderLayer {
+            next,
+            inner,
+            activation: Inherit,
+            weights: Matrix::zeros(),
+            encode_biases: Matrix::zeros(),
+            decode_biases: Matrix::zeros(),
+            data: Matrix::zeros(),
+            encode_pre_activation: Matrix::zeros(),
+            code: Matrix::zeros(),
+            decode_pre_activation: Matrix::zeros()
+        }
+    }
+
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A> AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+
+    /// Consumes the layer, returning it with the given activation function applied to the code and reconstruction in 
+    /// place of the current one. 
+    pub fn with_activation<B>(self, activation: B) -> AutoencoderLayer<IN, CODE, END_S, I, T, B> {
+        AutoencoderLayer {
+            next: self.next,
+            inner: self.inner,
+            activation,
+            weights: self.weights,
+            encode_biases: self.encode_biases,
+            decode_biases: self.decode_biases,
+            data: self.data,
+            encode_pre_activation: self.encode_pre_activation,
+            code: self.code,
+            decode_pre_activation: self.decode_pre_activation
+        }
+    }
+
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A> fmt::Debug for AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"weights\"", &self.weights)
+            .field("\"encode_biases\"", &self.encode_biases)
+            .field("\"decode_biases\"", &self.decode_biases)
+            .field("\"inner\"", &self.inner)
+            .field("\"next\"", &self.next)
+            .finish()
+    }
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A: StoredActivation> PartialEq for AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A: StoredActivation> fmt::Display for AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A: StoredActivation> Layer<IN, END_S> for AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    const OUTPUTS: usize = IN;
+    const PARAMS: usize = CODE * IN + CODE + IN + A::PARAMS + I::PARAMS + T::PARAMS;
+
+    fn feed_forward<F: LayerActivation + ?Sized>(&mut self, feed: Matrix<IN, 1>, act: &F) -> [Float; END_S] {
+        self.data = feed;
+        self.encode_pre_activation = self.weights.multiply(&self.data)
+            .add(&self.encode_biases);
+        let mut code = self.encode_pre_activation.clone();
+        self.activation.activate_or(act, false, code.data.as_flattened_mut());
+
+        self.code = Matrix::from([self.inner.feed_forward(code, &Hidden(act))]).transpose();
+        self.decode_pre_activation = self.weights.transpose().multiply(&self.code)
+            .add(&self.decode_biases);
+        let mut result = self.decode_pre_activation.clone();
+        self.activation.activate_or(act, T::IS_END, result.data.as_flattened_mut());
+        self.next.feed_forward(result, act)
+    }
+
+    fn back_propagate<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &F, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<IN> {
+        let BackProps(errors) = self.next.back_propagate(outputs, targets, act, loss, &mut gradients.next);
+
+        self.activation.gather_gradients(self.decode_pre_activation.data.as_flattened(), errors.data.as_flattened(), gradients.activation.as_mut());
+        let mut deltas = errors;
+        if !(T::IS_END && loss.fused()) {
+            let mut derivatives = self.decode_pre_activation.clone();
+            self.activation.derive_errors_or(act, T::IS_END, derivatives.data.as_flattened_mut(), deltas.data.as_flattened_mut());
+        }
+        subtract_gradients(gradients.weights.as_flattened_mut(), self.code.multiply(&deltas.transpose()).data.as_flattened());
+        subtract_gradients(&mut gradients.decode_biases, deltas.data.as_flattened());
+
+        // The inner sub-chain's end layer negates the gradients back into errors. 
+        let upstream = self.weights.multiply(&deltas).transpose().data[0].map(|x| -x);
+        let code = self.code.transpose().data[0];
+        let BackProps(errors) = self.inner.back_propagate(code, code, &Hidden(act), &Upstream { gradients: &upstream }, &mut gradients.inner);
+
+        self.activation.gather_gradients(self.encode_pre_activation.data.as_flattened(), errors.data.as_flattened(), gradients.activation.as_mut());
+        let mut deltas = errors;
+        let mut derivatives = self.encode_pre_activation.clone();
+        self.activation.derive_errors_or(act, false, derivatives.data.as_flattened_mut(), deltas.data.as_flattened_mut());
+        subtract_gradients(gradients.weights.as_flattened_mut(), deltas.multiply(&self.data.transpose()).data.as_flattened());
+        subtract_gradients(&mut gradients.encode_biases, deltas.data.as_flattened());
+
+        BackProps(self.weights.transpose().multiply(&deltas))
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        update_params(
+            optimizer, 
+            step, 
+            self.weights.data.as_flattened_mut(), 
+            gradients.weights.as_flattened_mut(), 
+            state.weights.as_flattened_mut(), 
+            true
+        );
+        update_params(
+            optimizer, 
+            step, 
+            self.encode_biases.data.as_flattened_mut(), 
+            &mut gradients.encode_biases, 
+            &mut state.encode_biases, 
+            false
+        );
+        update_params(
+            optimizer, 
+            step, 
+            self.decode_biases.data.as_flattened_mut(), 
+            &mut gradients.decode_biases, 
+            &mut state.decode_biases, 
+            false
+        );
+        update_params(optimizer, step, self.activation.params_mut(), gradients.activation.as_mut(), state.activation.as_mut(), false);
+        if let Some(max_norm) = step.max_norm {
+            for row in self.weights.data.iter_mut() {
+                limit_norm(row, max_norm);
+            }
+        }
+        self.inner.apply_gradients(optimizer, step, &mut gradients.inner, &mut state.inner);
+        self.next.apply_gradients(optimizer, step, &mut gradients.next, &mut state.next);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.weights = Matrix::random_with(rng);
+        self.encode_biases = Matrix::zeros();
+        self.decode_biases = Matrix::zeros();
+        self.inner.init_params(rng);
+        self.next.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.inner.set_training(training);
+        self.next.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.inner.clear_state();
+        self.next.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("autoencoder", IN, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("weights", self.weights.data.as_flattened());
+        visitor.params("encode_biases", self.encode_biases.data.as_flattened());
+        visitor.params("decode_biases", self.decode_biases.data.as_flattened());
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params());
+        }
+        self.inner.visit(visitor);
+        self.next.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("autoencoder", IN, Self::OUTPUTS);
+        if let Some(name) = self.activation.name() {
+            visitor.activation(name);
+        }
+        visitor.params("weights", self.weights.data.as_flattened_mut());
+        visitor.params("encode_biases", self.encode_biases.data.as_flattened_mut());
+        visitor.params("decode_biases", self.decode_biases.data.as_flattened_mut());
+        if A::PARAMS > 0 {
+            visitor.params("activation", self.activation.params_mut());
+        }
+        self.inner.visit_mut(visitor);
+        self.next.visit_mut(visitor);
+    }
+
+    type Snapshot = AutoencoderSnapshot<Float, IN, CODE, A::Params<Float>, I::Snapshot, T::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        AutoencoderSnapshot {
+            weights: self.weights.data,
+            encode_biases: self.encode_biases.transpose().data[0],
+            decode_biases: self.decode_biases.transpose().data[0],
+            activation: self.activation.save_params(),
+            inner: self.inner.save_params(),
+            next: self.next.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.weights = Matrix::from(snapshot.weights);
+        self.encode_biases = Matrix::from([snapshot.encode_biases]).transpose();
+        self.decode_biases = Matrix::from([snapshot.decode_biases]).transpose();
+        self.activation.load_params(&snapshot.activation);
+        self.inner.load_params(&snapshot.inner);
+        self.next.load_params(&snapshot.next);
+    }
+
+    type StateSnapshot = AutoencoderSnapshot<OptimizerState, IN, CODE, A::Params<OptimizerState>, I::StateSnapshot, T::StateSnapshot>;
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: Layer<IN, END_S>, A> AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    /// Consumes the layer, returning it with the next layer replaced by `map` of it, used to change the network's head. 
+    fn map_next<const NEW_END: usize, U: Layer<IN, NEW_END>, M: FnOnce(T) -> U>(self, map: M) -> AutoencoderLayer<IN, CODE, NEW_END, I, U, A> {
+        AutoencoderLayer {
+            next: map(self.next),
+            inner: self.inner,
+            activation: self.activation,
+            weights: self.weights,
+            encode_biases: self.encode_biases,
+            decode_biases: self.decode_biases,
+            data: self.data,
+            encode_pre_activation: self.encode_pre_activation,
+            code: self.code,
+            decode_pre_activation: self.decode_pre_activation
+        }
+    }
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, T: AppendHead<IN, END_S>, A: StoredActivation> AppendHead<IN, END_S> for AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = AutoencoderLayer<IN, CODE, NEW_END, I, T::Appended<NEW_END, HEAD>, A>;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> Self::Appended<NEW_END, HEAD> {
+        self.map_next(|next| next.append_head(head))
+    }
+}
+
+impl <const IN: usize, const CODE: usize, const END_S: usize, I: Layer<CODE, CODE>, const TRUNK_S: usize, T: DropHead<IN, END_S, TRUNK_S>, A: StoredActivation> DropHead<IN, END_S, TRUNK_S> for AutoencoderLayer<IN, CODE, END_S, I, T, A> {
+    type Trunk = AutoencoderLayer<IN, CODE, TRUNK_S, I, T::Trunk, A>;
+
+    fn drop_head(self) -> Self::Trunk {
+        self.map_next(|next| next.drop_head())
+    }
+}
+
+/// Layer splitting the network into two heads fed the same values, such as a classification head beside a 
+/// regression head, their outputs joined one after the other as the network's outputs. 
+/// 
+/// It ends the network in place of an [EndLayer], each head being a network of its own ending with an [EndLayer]. 
+/// Train with a [Heads](crate::loss::Heads) loss to give each head its own loss, the targets joined in the same 
+/// order as the outputs. Nest another branch layer as `second` for more heads, its sizes given such as 
+/// `BranchLayer::<8, 2, 1, 3, _, _>::new` as they can't be inferred. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of values fed to each head. 
+/// * `FIRST` The number of outputs of the first head. 
+/// * `SECOND` The number of outputs of the second head. 
+/// * `END_S` The number of outputs of the network, `FIRST + SECOND`. 
+/// * `A` The type of the first head, must implement [Layer]. 
+/// * `B` The type of the second head, must implement [Layer]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{IDENTITY, RELU, SIGMOID};
+/// use mynn::loss::{Heads, MSE, SOFTMAX_CROSS_ENTROPY};
+/// use mynn::network::{BranchLayer, Network, SoftmaxLayer};
+/// 
+/// // Classifies which input is larger, and regresses their sum. 
+/// let inputs = [[0.2, 0.8], [0.9, 0.1], [0.6, 0.3], [0.1, 0.4]];
+/// let targets = [[0.0, 1.0, 1.0], [1.0, 0.0, 1.0], [1.0, 0.0, 0.9], [0.0, 1.0, 0.5]];
+/// let mut network = make_network!(
+///     2, 
+///     8 => RELU, 
+///     3 => [|_| BranchLayer::new(
+///         make_network!(8, 2 => IDENTITY, [SoftmaxLayer::new]), 
+///         make_network!(8, 1 => IDENTITY)
+///     )]
+/// ).with_seed(6);
+/// let loss = Heads::new(SOFTMAX_CROSS_ENTROPY, 2, MSE);
+/// 
+/// network.train(0.05, inputs, targets, 2_000, &SIGMOID, &loss);
+/// 
+/// let [_, second_larger, sum] = network.predict([0.2, 0.8], &SIGMOID);
+/// assert!(second_larger > 0.9);
+/// assert!((sum - 1.0).abs() < 0.1);
+/// ```
+pub struct BranchLayer<const NEURONS: usize, const FIRST: usize, const SECOND: usize, const END_S: usize, A: Layer<NEURONS, FIRST>, B: Layer<NEURONS, SECOND>> {
+    /// The first head. 
+    pub first: A,
+    /// The second head. 
+    pub second: B
+}
+
+impl <const NEURONS: usize, const FIRST: usize, const SECOND: usize, const END_S: usize, A: Layer<NEURONS, FIRST>, B: Layer<NEURONS, SECOND>> BranchLayer<NEURONS, FIRST, SECOND, END_S, A, B> {
+
+    /// Instantiates a new branch layer, accepts the two heads as parameters. 
+    /// 
+    /// Fails to compile should `END_S` not match the number of outputs of both heads. 
+    pub fn new(first: A, second: B) -> BranchLayer<NEURONS, FIRST, SECOND, END_S, A, B> {
+        const { assert!(END_S == FIRST + SECOND, "END_S must be FIRST + SECOND") };
+        BranchLayer {
+            first,
+            second
+        }
+    }
+
+}
+
+impl <const NEURONS: usize, const FIRST: usize, const SECOND: usize, const END_S: usize, A: Layer<NEURONS, FIRST>, B: Layer<NEURONS, SECOND>> fmt::Debug for BranchLayer<NEURONS, FIRST, SECOND, END_S, A, B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("")
+            .field("\"first\"", &self.first)
+            .field("\"second\"", &self.second)
+            .finish()
+    }
+}
+
+impl <const NEURONS: usize, const FIRST: usize, const SECOND: usize, const END_S: usize, A: Layer<NEURONS, FIRST>, B: Layer<NEURONS, SECOND>> PartialEq for BranchLayer<NEURONS, FIRST, SECOND, END_S, A, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const NEURONS: usize, const FIRST: usize, const SECOND: usize, const END_S: usize, A: Layer<NEURONS, FIRST>, B: Layer<NEURONS, SECOND>> fmt::Display for BranchLayer<NEURONS, FIRST, SECOND, END_S, A, B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+impl <const NEURONS: usize, const FIRST: usize, const SECOND: usize, const END_S: usize, A: Layer<NEURONS, FIRST>, B: Layer<NEURONS, SECOND>> Layer<NEURONS, END_S> for BranchLayer<NEURONS, FIRST, SECOND, END_S, A, B> {
+    const OUTPUTS: usize = END_S;
+    const PARAMS: usize = A::PARAMS + B::PARAMS;
+
+    fn feed_forward<F: LayerActivation + ?Sized>(&mut self, feed: Matrix<NEURONS, 1>, act: &F) -> [Float; END_S] {
+        let first = self.first.feed_forward(feed.clone(), act);
+        let second = self.second.feed_forward(feed, act);
+        let mut result = [0.0; END_S];
+        result[..FIRST].copy_from_slice(&first);
+        result[FIRST..].copy_from_slice(&second);
+        result
+    }
+
+    fn back_propagate<F: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], act: &F, loss: &L, gradients: &mut Self::Snapshot) -> BackProps<NEURONS> {
+        let mut loss_gradients = [0.0; END_S];
+        loss.gradient(&outputs, &targets, &mut loss_gradients);
+        let (first_gradients, second_gradients) = loss_gradients.split_at(FIRST);
+
+        let BackProps(first_errors) = self.first.back_propagate(
+            core::array::from_fn(|i| outputs[i]), 
+            core::array::from_fn(|i| targets[i]), 
+            act, 
+            &Head { loss, gradients: first_gradients, offset: 0 }, 
+            &mut gradients.first
+        );
+        let BackProps(second_errors) = self.second.back_propagate(
+            core::array::from_fn(|i| outputs[FIRST + i]), 
+            core::array::from_fn(|i| targets[FIRST + i]), 
+            act, 
+            &Head { loss, gradients: second_gradients, offset: FIRST }, 
+            &mut gradients.second
+        );
+        BackProps(first_errors.add(&second_errors))
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, optimizer: &O, step: &GradientStep, gradients: &mut Self::Snapshot, state: &mut Self::StateSnapshot) {
+        self.first.apply_gradients(optimizer, step, &mut gradients.first, &mut state.first);
+        self.second.apply_gradients(optimizer, step, &mut gradients.second, &mut state.second);
+    }
+
+    fn init_params(&mut self, rng: &mut Rng) {
+        self.first.init_params(rng);
+        self.second.init_params(rng);
+    }
+
+    fn set_training(&mut self, training: bool) {
+        self.first.set_training(training);
+        self.second.set_training(training);
+    }
+
+    fn clear_state(&mut self) {
+        self.first.clear_state();
+        self.second.clear_state();
+    }
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("branch", NEURONS, Self::OUTPUTS);
+        self.first.visit(visitor);
+        self.second.visit(visitor);
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("branch", NEURONS, Self::OUTPUTS);
+        self.first.visit_mut(visitor);
+        self.second.visit_mut(visitor);
+    }
+
+    type Snapshot = BranchSnapshot<A::Snapshot, B::Snapshot>;
+
+    fn save_params(&self) -> Self::Snapshot {
+        BranchSnapshot {
+            first: self.first.save_params(),
+            second: self.second.save_params()
+        }
+    }
+
+    fn load_params(&mut self, snapshot: &Self::Snapshot) {
+        self.first.load_params(&snapshot.first);
+        self.second.load_params(&snapshot.second);
+    }
+
+    type StateSnapshot = BranchSnapshot<A::StateSnapshot, B::StateSnapshot>;
+}
+
+/// The end layer, this terminates the neural network linked list, just accepts the number of neurons in the final layer. 
+/// 
+/// # Type Parameters
+/// * `END_S` Number of neurons in the end layer. 
+pub struct EndLayer<const END_S: usize>();
+
+impl <const END_S: usize> Layer<END_S, END_S> for EndLayer<END_S> {
+    const IS_END: bool = true;
+    const OUTPUTS: usize = END_S;
+    const PARAMS: usize = 0;
+
+    fn feed_forward<A: LayerActivation + ?Sized>(&mut self, feed: Matrix<END_S, 1>, _act: &A) -> [Float; END_S] {
+        feed.transpose().data[0]
+    }
+
+    fn back_propagate<A: LayerActivation + ?Sized, L: Loss + ?Sized>(&mut self, outputs: [Float; END_S], targets: [Float; END_S], _act: &A, loss: &L, _gradients: &mut Self::Snapshot) -> BackProps<END_S> {
+        let mut gradients = [0.0; END_S];
+        loss.gradient(&outputs, &targets, &mut gradients);
+        let errors = Matrix::from([gradients]).transpose().map(&|x| -x);
+        BackProps(errors)
+    }
+
+    fn apply_gradients<O: Optimizer + ?Sized>(&mut self, _optimizer: &O, _step: &GradientStep, _gradients: &mut Self::Snapshot, _state: &mut Self::StateSnapshot) {}
+
+    fn init_params(&mut self, _rng: &mut Rng) {}
+
+    fn set_training(&mut self, _training: bool) {}
+
+    fn clear_state(&mut self) {}
+
+    fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.end();
+    }
+
+    fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.end();
+    }
+
+    type Snapshot = ();
+
+    fn save_params(&self) -> Self::Snapshot {}
+
+    fn load_params(&mut self, _snapshot: &Self::Snapshot) {}
+
+    type StateSnapshot = ();
+}
+
+impl <const END_S: usize> AppendHead<END_S, END_S> for EndLayer<END_S> {
+    type Appended<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>> = HEAD;
+
+    fn append_head<const NEW_END: usize, HEAD: Layer<END_S, NEW_END>>(self, head: HEAD) -> HEAD {
+        head
+    }
+}
+
+impl <const END_S: usize> fmt::Debug for EndLayer<END_S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("null").finish()
+    }
+}
+
+impl <const END_S: usize> PartialEq for EndLayer<END_S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, 0.0)
+    }
+}
+
+impl <const END_S: usize> fmt::Display for EndLayer<END_S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut summary = Summary::new(fmt);
+        self.visit(&mut summary);
+        summary.result
+    }
+}
+
+/// Summary statistics of a set of parameters, given by [param_stats](Network::param_stats). 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ParamStats {
+    /// The smallest parameter. 
+    pub min: Float,
+    /// The largest parameter. 
+    pub max: Float,
+    /// The mean of the parameters. 
+    pub mean: Float,
+    /// The standard deviation of the parameters. 
+    pub std_dev: Float
+}
+
+impl ParamStats {
+    /// Calculates the statistics of a set of parameters, all zero should it be empty. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::network::ParamStats;
+    /// 
+    /// let stats = ParamStats::new(&[1.0, 3.0]);
+    /// 
+    /// assert_eq!(stats, ParamStats { min: 1.0, max: 3.0, mean: 2.0, std_dev: 1.0 });
+    /// ```
+    pub fn new(params: &[Float]) -> ParamStats {
+        if params.is_empty() {
+            return ParamStats::default();
+        }
+        let count = params.len() as Float;
+        let mean = params.iter().sum::<Float>() / count;
+        let variance = params.iter().map(|param| (param - mean) * (param - mean)).sum::<Float>() / count;
+        ParamStats {
+            min: params.iter().fold(Float::INFINITY, |min, param| min.min(*param)),
+            max: params.iter().fold(Float::NEG_INFINITY, |max, param| max.max(*param)),
+            mean,
+            std_dev: sqrt(variance)
+        }
+    }
+}
+
+/// Statistics gathered while [training](Network::train) a network, losses are measured on the forward pass 
+/// before each sample's weight update. 
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TrainStats {
+    /// The loss of the last sample trained upon. 
+    pub final_loss: Float,
+    /// The average loss over the samples of the last epoch. 
+    pub average_loss: Float,
+    /// The number of epochs executed. 
+    pub epochs: usize,
+    /// The average loss over the validation set after the last epoch, if one was given. 
+    pub validation_loss: Option<Float>,
+    /// The lowest average loss over the validation set after any epoch, if one was given. 
+    pub best_validation_loss: Option<Float>
+}
+
+/// Plain copy of the weights and biases of a layer, or of their gradients or optimizer state, nesting the copy of 
+/// the following layers in `next`. 
+/// 
+/// Taken by [snapshot](Network::snapshot) and given back by [restore](Network::restore). 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerSnapshot<P, const ROWS: usize, const NEURONS: usize, A, N> {
+    pub weights: [[P; NEURONS]; ROWS],
+    pub biases: [P; ROWS],
+    /// The copy of the learnable parameters of the layer's activation, see [StoredActivation::Params]. 
+    pub activation: A,
+    /// The copy of the following layers, `()` after the last. 
+    pub next: N
+}
+
+/// Plain copy of the kernels and biases of a [Conv2dLayer], or of their gradients or optimizer state, nesting the 
+/// copy of the following layers in `next`. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KernelSnapshot<P, const C_IN: usize, const C_OUT: usize, const K: usize, A, N> {
+    pub kernels: Kernels<P, C_IN, C_OUT, K>,
+    pub biases: [P; C_OUT],
+    /// The copy of the learnable parameters of the layer's activation, see [StoredActivation::Params]. 
+    pub activation: A,
+    /// The copy of the following layers, `()` after the last. 
+    pub next: N
+}
+
+/// Plain copy of the weights and biases of a [RecurrentLayer], or of their gradients or optimizer state, nesting the 
+/// copy of the following layers in `next`. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RecurrentSnapshot<P, const F: usize, const HIDDEN: usize, A, N> {
+    pub input_weights: [[P; F]; HIDDEN],
+    pub recurrent_weights: [[P; HIDDEN]; HIDDEN],
+    pub biases: [P; HIDDEN],
+    /// The copy of the learnable parameters of the layer's activation, see [StoredActivation::Params]. 
+    pub activation: A,
+    /// The copy of the following layers, `()` after the last. 
+    pub next: N
+}
+
+/// Plain copy of the vectors of an [EmbeddingLayer], or of their gradients or optimizer state, nesting the copy of 
+/// the following layers in `next`. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EmbeddingSnapshot<P, const VOCAB: usize, const DIM: usize, N> {
+    pub embeddings: [[P; DIM]; VOCAB],
+    /// The copy of the following layers, `()` after the last. 
+    pub next: N
+}
+
+/// Plain copy of the parameters of a [ResidualLayer]'s inner sub-chain, or of their gradients or optimizer state, 
+/// nesting the copy of the following layers in `next`. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ResidualSnapshot<I, N> {
+    /// The copy of the inner sub-chain. 
+    pub inner: I,
+    /// The copy of the following layers, `()` after the last. 
+    pub next: N
+}
+
+/// Plain copy of the weights and biases of an [AutoencoderLayer], or of their gradients or optimizer state, nesting 
+/// the copy of its inner sub-chain in `inner` and of the following layers in `next`. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AutoencoderSnapshot<P, const IN: usize, const CODE: usize, A, I, N> {
+    pub weights: [[P; IN]; CODE],
+    pub encode_biases: [P; CODE],
+    pub decode_biases: [P; IN],
+    /// The copy of the learnable parameters of the layer's activation, see [StoredActivation::Params]. 
+    pub activation: A,
+    /// The copy of the inner sub-chain. 
+    pub inner: I,
+    /// The copy of the following layers, `()` after the last. 
+    pub next: N
+}
+
+/// Plain copy of the parameters of both heads of a [BranchLayer], or of their gradients or optimizer state. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BranchSnapshot<A, B> {
+    /// The copy of the first head. 
+    pub first: A,
+    /// The copy of the second head. 
+    pub second: B
+}
+
+/// A copy of a network's parameters or of a value kept for each of them, such as its gradient or optimizer state, 
+/// split into sets in the order the network [visits](Visitor::params) them. 
+/// 
+/// Implemented by each [Layer::Snapshot] and [Layer::StateSnapshot] so the trainer can hold them apart from the 
+/// network, `()` for none, `[P; N]` for a single set unless empty and `(A, B)` for the sets of `A` followed by 
+/// those of `B`. 
+/// 
+/// # Type Parameters
+/// * `P` The value kept for each parameter. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::network::{Network, ParamSets};
+/// 
+/// let network = make_network!(2, 3, 1);
+/// let mut count = 0;
+/// network.snapshot().each_set(&mut |set: &[Float]| count += set.len());
+/// 
+/// assert_eq!(count, 2 * 3 + 3 + 3 * 1 + 1);
+/// ```
+pub trait ParamSets<P: Copy>: Clone {
+    /// A copy with every value set to `value`, such as zeroed gradients. 
+    fn filled(value: P) -> Self;
+
+    /// Calls `each` with each set of values in turn. 
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F);
+
+    /// Calls `each` with each set of values in turn, allowing them to be changed. 
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F);
+}
+
+impl<P: Copy> ParamSets<P> for () {
+    fn filled(_value: P) -> Self {}
+
+    fn each_set<F: FnMut(&[P])>(&self, _each: &mut F) {}
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, _each: &mut F) {}
+}
+
+impl<P: Copy, const N: usize> ParamSets<P> for [P; N] {
+    fn filled(value: P) -> Self {
+        [value; N]
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        if N > 0 {
+            each(self);
+        }
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        if N > 0 {
+            each(self);
+        }
+    }
+}
+
+impl<P: Copy, A: ParamSets<P>, B: ParamSets<P>> ParamSets<P> for (A, B) {
+    fn filled(value: P) -> Self {
+        (A::filled(value), B::filled(value))
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        self.0.each_set(each);
+        self.1.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        self.0.each_set_mut(each);
+        self.1.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, const ROWS: usize, const NEURONS: usize, A: ParamSets<P>, N: ParamSets<P>> ParamSets<P> for LayerSnapshot<P, ROWS, NEURONS, A, N> {
+    fn filled(value: P) -> Self {
+        LayerSnapshot {
+            weights: [[value; NEURONS]; ROWS],
+            biases: [value; ROWS],
+            activation: A::filled(value),
+            next: N::filled(value)
+        }
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        each(self.weights.as_flattened());
+        each(&self.biases);
+        self.activation.each_set(each);
+        self.next.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        each(self.weights.as_flattened_mut());
+        each(&mut self.biases);
+        self.activation.each_set_mut(each);
+        self.next.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, const C_IN: usize, const C_OUT: usize, const K: usize, A: ParamSets<P>, N: ParamSets<P>> ParamSets<P> for KernelSnapshot<P, C_IN, C_OUT, K, A, N> {
+    fn filled(value: P) -> Self {
+        KernelSnapshot {
+            kernels: [[[[value; C_IN]; K]; K]; C_OUT],
+            biases: [value; C_OUT],
+            activation: A::filled(value),
+            next: N::filled(value)
+        }
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        each(self.kernels.as_flattened().as_flattened().as_flattened());
+        each(&self.biases);
+        self.activation.each_set(each);
+        self.next.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        each(self.kernels.as_flattened_mut().as_flattened_mut().as_flattened_mut());
+        each(&mut self.biases);
+        self.activation.each_set_mut(each);
+        self.next.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, const F: usize, const HIDDEN: usize, A: ParamSets<P>, N: ParamSets<P>> ParamSets<P> for RecurrentSnapshot<P, F, HIDDEN, A, N> {
+    fn filled(value: P) -> Self {
+        RecurrentSnapshot {
+            input_weights: [[value; F]; HIDDEN],
+            recurrent_weights: [[value; HIDDEN]; HIDDEN],
+            biases: [value; HIDDEN],
+            activation: A::filled(value),
+            next: N::filled(value)
+        }
+    }
+
+    fn each_set<E: FnMut(&[P])>(&self, each: &mut E) {
+        each(self.input_weights.as_flattened());
+        each(self.recurrent_weights.as_flattened());
+        each(&self.biases);
+        self.activation.each_set(each);
+        self.next.each_set(each);
+    }
+
+    fn each_set_mut<E: FnMut(&mut [P])>(&mut self, each: &mut E) {
+        each(self.input_weights.as_flattened_mut());
+        each(self.recurrent_weights.as_flattened_mut());
+        each(&mut self.biases);
+        self.activation.each_set_mut(each);
+        self.next.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, const VOCAB: usize, const DIM: usize, N: ParamSets<P>> ParamSets<P> for EmbeddingSnapshot<P, VOCAB, DIM, N> {
+    fn filled(value: P) -> Self {
+        EmbeddingSnapshot {
+            embeddings: [[value; DIM]; VOCAB],
+            next: N::filled(value)
+        }
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        each(self.embeddings.as_flattened());
+        self.next.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        each(self.embeddings.as_flattened_mut());
+        self.next.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, I: ParamSets<P>, N: ParamSets<P>> ParamSets<P> for ResidualSnapshot<I, N> {
+    fn filled(value: P) -> Self {
+        ResidualSnapshot {
+            inner: I::filled(value),
+            next: N::filled(value)
+        }
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        self.inner.each_set(each);
+        self.next.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        self.inner.each_set_mut(each);
+        self.next.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, const IN: usize, const CODE: usize, A: ParamSets<P>, I: ParamSets<P>, N: ParamSets<P>> ParamSets<P> for AutoencoderSnapshot<P, IN, CODE, A, I, N> {
+    fn filled(value: P) -> Self {
+        AutoencoderSnapshot {
+            weights: [[value; IN]; CODE],
+            encode_biases: [value; CODE],
+            decode_biases: [value; IN],
+            activation: A::filled(value),
+            inner: I::filled(value),
+            next: N::filled(value)
+        }
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        each(self.weights.as_flattened());
+        each(&self.encode_biases);
+        each(&self.decode_biases);
+        self.activation.each_set(each);
+        self.inner.each_set(each);
+        self.next.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        each(self.weights.as_flattened_mut());
+        each(&mut self.encode_biases);
+        each(&mut self.decode_biases);
+        self.activation.each_set_mut(each);
+        self.inner.each_set_mut(each);
+        self.next.each_set_mut(each);
+    }
+}
+
+impl<P: Copy, A: ParamSets<P>, B: ParamSets<P>> ParamSets<P> for BranchSnapshot<A, B> {
+    fn filled(value: P) -> Self {
+        BranchSnapshot {
+            first: A::filled(value),
+            second: B::filled(value)
+        }
+    }
+
+    fn each_set<F: FnMut(&[P])>(&self, each: &mut F) {
+        self.first.each_set(each);
+        self.second.each_set(each);
+    }
+
+    fn each_set_mut<F: FnMut(&mut [P])>(&mut self, each: &mut F) {
+        self.first.each_set_mut(each);
+        self.second.each_set_mut(each);
+    }
+}
+
+/// Calls `each` with every value of `sets` and the value in the same place of `other`, the two of the same shape. 
+pub(crate) fn zip_sets<P: Copy, Q: Copy, S: ParamSets<P>, O: ParamSets<Q>, F: FnMut(&mut P, Q)>(sets: &mut S, other: &O, mut each: F) {
+    let mut set = 0;
+    sets.each_set_mut(&mut |params: &mut [P]| {
+        let mut seen = 0;
+        other.each_set(&mut |values: &[Q]| {
+            if seen == set {
+                params.iter_mut().zip(values).for_each(|(param, value)| each(param, *value));
+            }
+            seen += 1;
+        });
+        set += 1;
+    });
 }
 
 /// Helper type for passing parameters back through the the neural network during back propagation. 
-/// `(errors, gradients)`
-pub struct BackProps<const COLS: usize>(Matrix<COLS, 1>, Matrix<COLS, 1>);
+/// `(errors)`, the errors with respect to the values a layer was fed, the negative of the loss gradient. 
+pub struct BackProps<const COLS: usize>(pub Matrix<COLS, 1>);
 
 
 
@@ -0,0 +1,254 @@
+use super::Float;
+use super::math::sqrt;
+
+
+/// The per-parameter state an [Optimizer] may keep between updates, such as a running average of the gradient. 
+/// 
+/// Every weight and bias in a layer has one, zeroed when the layer is created. 
+pub type OptimizerState = [Float; 2];
+
+/// Generic behaviour for an update rule, turning the gradients found during back propagation into changes to 
+/// the weights and biases of each layer. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::optimizers::{Optimizer, OptimizerState};
+/// use mynn::training::TrainOptions;
+/// use mynn::Float;
+/// use mynn::network::Network;
+/// 
+/// // Plain gradient descent with the learning rate halved.
+/// struct HalfSgd;
+/// 
+/// impl Optimizer for HalfSgd {
+///     fn update(&self, l_rate: Float, params: &mut [Float], gradients: &[Float], _state: &mut [OptimizerState]) {
+///         for (param, gradient) in params.iter_mut().zip(gradients) {
+///             *param -= 0.5 * l_rate * gradient;
+///         }
+///     }
+/// }
+/// 
+/// let mut optimizer = HalfSgd;
+/// let mut network = make_network!(2, 3, 1);
+/// 
+/// let options = TrainOptions::new(0.5, 100).optimizer(&mut optimizer);
+/// network.train_with(&[[0.0, 1.0]], &[[1.0]], &SIGMOID, &MSE, options);
+/// ```
+pub trait Optimizer {
+    /// Called once before each update is applied across the network, letting the optimizer advance any 
+    /// state shared by all parameters, such as a step count. 
+    fn step(&mut self) {}
+
+    /// Applies the gradients to a set of parameters, the weights or biases of a single layer. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `params` The parameters to update. 
+    /// * `gradients` The gradient of the loss with respect to each parameter, the same length as `params`. 
+    /// * `state` The optimizer's state for each parameter, the same length as `params`. 
+    fn update(&self, l_rate: Float, params: &mut [Float], gradients: &[Float], state: &mut [OptimizerState]);
+}
+
+/// Stochastic gradient descent, stepping each parameter against its gradient scaled by the learning rate. 
+/// 
+/// This is the update rule used when no other optimizer is given. 
+#[derive(Clone, Copy)]
+pub struct Sgd;
+
+impl Optimizer for Sgd {
+    fn update(&self, l_rate: Float, params: &mut [Float], gradients: &[Float], _state: &mut [OptimizerState]) {
+        for (param, gradient) in params.iter_mut().zip(gradients) {
+            *param -= l_rate * gradient;
+        }
+    }
+}
+
+/// Stochastic gradient descent, see [Sgd]. 
+pub const SGD: Sgd = Sgd;
+
+/// Gradient descent with momentum, each parameter keeps a velocity accumulating past gradients so updates 
+/// build speed along consistent directions. 
+/// 
+/// The [Nesterov](Momentum::nesterov) variant steps using the velocity it is about to have, looking ahead 
+/// which often converges faster on deeper networks. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::optimizers::Momentum;
+/// use mynn::training::TrainOptions;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [[0.0], [0.0], [0.0], [1.0]];
+/// let mut optimizer = Momentum::nesterov(0.9);
+/// let mut network = make_network!(2, 3, 1);
+/// 
+/// let options = TrainOptions::new(0.1, 1_000).optimizer(&mut optimizer);
+/// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+/// 
+/// assert!(stats.average_loss < 0.01);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Momentum {
+    /// The fraction of the velocity kept each update, commonly `0.9`. 
+    pub momentum: Float,
+    /// Whether to use the Nesterov accelerated gradient look-ahead update. 
+    pub nesterov: bool
+}
+
+impl Momentum {
+    /// Instantiates classical momentum with the given fraction of velocity kept each update. 
+    pub const fn new(momentum: Float) -> Momentum {
+        Momentum {
+            momentum,
+            nesterov: false
+        }
+    }
+
+    /// Instantiates Nesterov accelerated gradient with the given fraction of velocity kept each update. 
+    pub const fn nesterov(momentum: Float) -> Momentum {
+        Momentum {
+            momentum,
+            nesterov: true
+        }
+    }
+}
+
+impl Optimizer for Momentum {
+    fn update(&self, l_rate: Float, params: &mut [Float], gradients: &[Float], state: &mut [OptimizerState]) {
+        for ((param, gradient), state) in params.iter_mut().zip(gradients).zip(state) {
+            let velocity = self.momentum * state[0] - l_rate * gradient;
+            state[0] = velocity;
+            *param += if self.nesterov {
+                self.momentum * velocity - l_rate * gradient
+            } else {
+                velocity
+            };
+        }
+    }
+}
+
+/// Adagrad, scaling each parameter's learning rate down by the root of its accumulated squared gradients, 
+/// so rarely updated parameters, such as those of sparse input features, keep larger steps. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::optimizers::Adagrad;
+/// use mynn::training::TrainOptions;
+/// use mynn::network::Network;
+/// 
+/// let mut optimizer = Adagrad::new(1e-8);
+/// let mut network = make_network!(4, 3, 1);
+/// 
+/// let options = TrainOptions::new(0.5, 100).optimizer(&mut optimizer);
+/// network.train_with(&[[1.0, 0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Adagrad {
+    /// Small value added to the denominator to avoid dividing by zero, commonly `1e-8`. 
+    pub epsilon: Float
+}
+
+impl Adagrad {
+    /// Instantiates Adagrad with the given `epsilon`. 
+    pub const fn new(epsilon: Float) -> Adagrad {
+        Adagrad {
+            epsilon
+        }
+    }
+}
+
+impl Optimizer for Adagrad {
+    fn update(&self, l_rate: Float, params: &mut [Float], gradients: &[Float], state: &mut [OptimizerState]) {
+        for ((param, gradient), state) in params.iter_mut().zip(gradients).zip(state) {
+            state[0] += gradient * gradient;
+            *param -= l_rate * gradient / (sqrt(state[0]) + self.epsilon);
+        }
+    }
+}
+
+/// Resilient back propagation (iRprop-), stepping each parameter by its own step size in the direction of its 
+/// gradient's sign, growing the step while the sign holds and shrinking it when the sign flips. 
+/// 
+/// Only the sign of the gradients is used, making it robust to the choice of learning rate, which instead sets the 
+/// initial step size. Intended for [full batch](crate::training::TrainOptions::full_batch) training. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::optimizers::RPROP;
+/// use mynn::training::TrainOptions;
+/// use mynn::network::Network;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let targets = [[0.0], [0.0], [0.0], [1.0]];
+/// let mut optimizer = RPROP;
+/// let mut network = make_network!(2, 3, 1);
+/// 
+/// let options = TrainOptions::new(0.1, 200).optimizer(&mut optimizer).full_batch();
+/// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+/// 
+/// assert!(stats.average_loss < 0.01);
+/// ```
+#[derive(Clone, Copy)]
+pub struct RProp {
+    /// Factor a step size grows by while its gradient keeps the same sign, commonly `1.2`. 
+    pub increase: Float,
+    /// Factor a step size shrinks by when its gradient changes sign, commonly `0.5`. 
+    pub decrease: Float,
+    /// The smallest step size. 
+    pub min_step: Float,
+    /// The largest step size. 
+    pub max_step: Float
+}
+
+impl RProp {
+    /// Instantiates RProp with the given step size factors and limits. 
+    pub const fn new(increase: Float, decrease: Float, min_step: Float, max_step: Float) -> RProp {
+        RProp {
+            increase,
+            decrease,
+            min_step,
+            max_step
+        }
+    }
+}
+
+impl Optimizer for RProp {
+    fn update(&self, l_rate: Float, params: &mut [Float], gradients: &[Float], state: &mut [OptimizerState]) {
+        for ((param, &gradient), state) in params.iter_mut().zip(gradients).zip(state) {
+            let [previous, step] = *state;
+            let step = if step == 0.0 { l_rate } else { step };
+            let change = gradient * previous;
+
+            let (step, gradient) = if change > 0.0 {
+                ((step * self.increase).min(self.max_step), gradient)
+            } else if change < 0.0 {
+                ((step * self.decrease).max(self.min_step), 0.0)
+            } else {
+                (step, gradient)
+            };
+
+            if gradient > 0.0 {
+                *param -= step;
+            } else if gradient < 0.0 {
+                *param += step;
+            }
+            *state = [gradient, step];
+        }
+    }
+}
+
+/// RProp with the common step size factors of `1.2` and `0.5`, limited to between `1e-6` and `50`, see [RProp]. 
+pub const RPROP: RProp = RProp::new(1.2, 0.5, 1e-6, 50.0);
@@ -0,0 +1,107 @@
+use super::{activations::LayerActivation, network::Network};
+use super::Float;
+
+
+/// Turns each output of a network into a binary decision, on once it reaches a threshold, such as to switch a relay 
+/// from a sigmoid output. 
+/// 
+/// With hysteresis an output switches on once it reaches the `on` threshold but only switches back off once it falls 
+/// below the lower `off` threshold, so a stream of predictions hovering around a single threshold doesn't flicker. 
+/// 
+/// # Type Parameters
+/// * `OUT` The number of outputs decided upon. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::postprocessing::Threshold;
+/// 
+/// let readings = [0.2, 0.55, 0.45, 0.62, 0.48, 0.35, 0.5];
+/// let mut plain = Threshold::new(0.5);
+/// let mut hysteresis = Threshold::hysteresis(0.6, 0.4);
+/// 
+/// assert_eq!(readings.map(|reading| plain.decide([reading])[0]), [false, true, false, true, false, false, true]);
+/// assert_eq!(readings.map(|reading| hysteresis.decide([reading])[0]), [false, false, false, true, true, false, false]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Threshold<const OUT: usize> {
+    /// The value at or above which an output switches on. 
+    pub on: Float,
+    /// The value below which an output switches back off, at most `on`. 
+    pub off: Float,
+    /// The decision last made for each output. 
+    pub state: [bool; OUT]
+}
+
+impl <const OUT: usize> Threshold<OUT> {
+
+    /// Instantiates a decision on once each output is at or above the threshold, off otherwise. 
+    pub const fn new(threshold: Float) -> Threshold<OUT> {
+        Threshold::hysteresis(threshold, threshold)
+    }
+
+    /// Instantiates a decision switching each output on once it reaches `on` and back off once it falls below `off`, 
+    /// starting off. 
+    /// 
+    /// # Parameters 
+    /// * `on` The value at or above which an output switches on. 
+    /// * `off` The value below which an output switches back off, at most `on`. 
+    pub const fn hysteresis(on: Float, off: Float) -> Threshold<OUT> {
+        Threshold {
+            on,
+            off,
+            state: [false; OUT]
+        }
+    }
+
+    /// Decides upon each of the outputs, an output between the thresholds, or `NaN`, keeping its last decision. 
+    pub fn decide(&mut self, outputs: [Float; OUT]) -> [bool; OUT] {
+        for (state, output) in self.state.iter_mut().zip(outputs) {
+            if output >= self.on {
+                *state = true;
+            } else if output < self.off {
+                *state = false;
+            }
+        }
+        self.state
+    }
+
+    /// Decides upon each of the outputs as [decide](Threshold::decide) does, as `1` for on and `0` for off, such as 
+    /// for binary labels. 
+    pub fn binarize(&mut self, outputs: [Float; OUT]) -> [Float; OUT] {
+        self.decide(outputs).map(|state| state as u8 as Float)
+    }
+
+    /// Makes a [prediction](Network::predict) from the data then decides upon its outputs. 
+    /// 
+    /// # Parameters 
+    /// * `network` The network making the prediction. 
+    /// * `data` The data for the prediction to be made upon. 
+    /// * `act` The activation function to be used, layers owning their own activation use theirs instead. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::network::Network;
+    /// use mynn::postprocessing::Threshold;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// let targets = [[0.0], [0.0], [0.0], [1.0]];
+    /// let mut network = make_network!(2, 3, 1).with_seed(1);
+    /// let mut relay = Threshold::hysteresis(0.6, 0.4);
+    /// 
+    /// network.train(0.5, inputs, targets, 10_000, &SIGMOID, &MSE);
+    /// 
+    /// assert_eq!(relay.predict(&mut network, [1.0, 1.0], &SIGMOID), [true]);
+    /// assert_eq!(relay.predict(&mut network, [0.0, 1.0], &SIGMOID), [false]);
+    /// ```
+    pub fn predict<const NEURONS: usize, N: Network<NEURONS, OUT>, F: LayerActivation + ?Sized>(&mut self, network: &mut N, data: [Float; NEURONS], act: &F) -> [bool; OUT] {
+        self.decide(network.predict(data, act))
+    }
+
+    /// Switches every output back off, such as between separate streams of predictions. 
+    pub fn reset(&mut self) {
+        self.state = [false; OUT];
+    }
+}
@@ -0,0 +1,440 @@
+use super::network::{Visitor, VisitorMut};
+use super::math::sqrt;
+use super::Float;
+use fastrand::Rng;
+
+
+/// A dataset split at random into samples to train upon and samples held out to test the trained network upon, 
+/// measuring how well it generalises to data it hasn't seen. 
+/// 
+/// # Type Parameters
+/// * `IN` The number of values in each input. 
+/// * `OUT` The number of values in each target. 
+/// * `TRAIN` The number of samples to train upon. 
+/// * `TEST` The number of samples held out, together with `TRAIN` checked at compile time to be the size of the dataset. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::fastrand::Rng;
+/// use mynn::preprocessing::TrainTestSplit;
+/// 
+/// let inputs = [[0.0], [1.0], [2.0], [3.0], [4.0]];
+/// let targets = [[0.0], [2.0], [4.0], [6.0], [8.0]];
+/// let split = TrainTestSplit::<1, 1, 4, 1>::new(&inputs, &targets, &mut Rng::with_seed(1));
+/// 
+/// let [[test]] = split.test_inputs;
+/// assert_eq!(split.test_targets, [[2.0 * test]]);
+/// assert!(!split.train_inputs.contains(&[test]));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrainTestSplit<const IN: usize, const OUT: usize, const TRAIN: usize, const TEST: usize> {
+    /// The inputs to train upon. 
+    pub train_inputs: [[Float; IN]; TRAIN],
+    /// The targets of the inputs to train upon. 
+    pub train_targets: [[Float; OUT]; TRAIN],
+    /// The inputs held out for testing. 
+    pub test_inputs: [[Float; IN]; TEST],
+    /// The targets of the inputs held out for testing. 
+    pub test_targets: [[Float; OUT]; TEST]
+}
+
+impl <const IN: usize, const OUT: usize, const TRAIN: usize, const TEST: usize> TrainTestSplit<IN, OUT, TRAIN, TEST> {
+
+    /// Splits a dataset at random, shuffling it with `rng`. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs of the dataset, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` The targets of the dataset, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `rng` The random number generator the dataset is shuffled with. 
+    pub fn new<const DATA_S: usize>(inputs: &[[Float; IN]; DATA_S], targets: &[[Float; OUT]; DATA_S], rng: &mut Rng) -> TrainTestSplit<IN, OUT, TRAIN, TEST> {
+        const { assert!(TRAIN + TEST == DATA_S, "TRAIN and TEST must add up to the size of the dataset") };
+        let mut order: [usize; DATA_S] = core::array::from_fn(|i| i);
+        rng.shuffle(&mut order);
+        let mut held_out = [false; DATA_S];
+        for &i in &order[..TEST] {
+            held_out[i] = true;
+        }
+        Self::from_order(inputs, targets, &order, &held_out)
+    }
+
+    /// Splits a classification dataset at random as [new](TrainTestSplit::new) does, keeping the share of each 
+    /// class in both parts as close as possible to its share of the whole, so rare classes aren't left out of one. 
+    /// 
+    /// The class of a target is the index of its largest value, as for one-hot targets, or with a single target 
+    /// whether it's at least `0.5`. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` The inputs of the dataset, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` The targets of the dataset, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `rng` The random number generator the dataset is shuffled with. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::fastrand::Rng;
+    /// use mynn::preprocessing::TrainTestSplit;
+    /// 
+    /// // Eight good parts and two defects. 
+    /// let inputs = [[0.1], [0.2], [0.3], [0.4], [0.5], [0.6], [0.7], [0.8], [0.9], [1.0]];
+    /// let targets = [[0.0], [0.0], [0.0], [0.0], [0.0], [0.0], [0.0], [0.0], [1.0], [1.0]];
+    /// 
+    /// for seed in 0..10 {
+    ///     let split = TrainTestSplit::<1, 1, 5, 5>::stratified(&inputs, &targets, &mut Rng::with_seed(seed));
+    ///     assert_eq!(split.test_targets.iter().filter(|[target]| *target == 1.0).count(), 1);
+    /// }
+    /// ```
+    pub fn stratified<const DATA_S: usize>(inputs: &[[Float; IN]; DATA_S], targets: &[[Float; OUT]; DATA_S], rng: &mut Rng) -> TrainTestSplit<IN, OUT, TRAIN, TEST> {
+        const { assert!(TRAIN + TEST == DATA_S, "TRAIN and TEST must add up to the size of the dataset") };
+        let mut order: [usize; DATA_S] = core::array::from_fn(|i| i);
+        rng.shuffle(&mut order);
+        let class = |target: &[Float; OUT]| match OUT {
+            1 => (target[0] >= 0.5) as usize,
+            _ => argmax_label(target)
+        };
+        // Grouped by class, each in its shuffled order, taking evenly spaced samples takes each class in proportion. 
+        let mut grouped: [(usize, usize); DATA_S] = core::array::from_fn(|k| (class(&targets[order[k]]), k));
+        grouped.sort_unstable();
+        let mut held_out = [false; DATA_S];
+        for k in 0..TEST {
+            held_out[order[grouped[(2 * k + 1) * DATA_S / (2 * TEST)].1]] = true;
+        }
+        Self::from_order(inputs, targets, &order, &held_out)
+    }
+
+    /// Copies the samples of the dataset into the two parts in the given order. 
+    fn from_order<const DATA_S: usize>(inputs: &[[Float; IN]; DATA_S], targets: &[[Float; OUT]; DATA_S], order: &[usize; DATA_S], held_out: &[bool; DATA_S]) -> TrainTestSplit<IN, OUT, TRAIN, TEST> {
+        let mut split = TrainTestSplit {
+            train_inputs: [[0.0; IN]; TRAIN],
+            train_targets: [[0.0; OUT]; TRAIN],
+            test_inputs: [[0.0; IN]; TEST],
+            test_targets: [[0.0; OUT]; TEST]
+        };
+        let (mut train, mut test) = (0, 0);
+        for &i in order {
+            if held_out[i] {
+                split.test_inputs[test] = inputs[i];
+                split.test_targets[test] = targets[i];
+                test += 1;
+            } else {
+                split.train_inputs[train] = inputs[i];
+                split.train_targets[train] = targets[i];
+                train += 1;
+            }
+        }
+        split
+    }
+}
+
+/// Scales each feature of the inputs to between `0` and `1` by the smallest and largest values it was fitted to, 
+/// so features in large or differing units, such as raw sensor readings, don't saturate the activations. 
+/// 
+/// Outside of training the parameters are kept alongside the network's, such as exported by the same [Visitor] 
+/// with [visit](MinMaxScaler::visit) and written back with [new](MinMaxScaler::new) or 
+/// [visit_mut](MinMaxScaler::visit_mut), so inputs are scaled the same way the network was trained on. 
+/// 
+/// # Type Parameters
+/// * `N` The number of features in each input. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::preprocessing::MinMaxScaler;
+/// 
+/// // Temperatures in °C and pressures in Pa. 
+/// let inputs = [[15.0, 101_000.0], [20.0, 101_500.0], [25.0, 102_000.0]];
+/// let scaler = MinMaxScaler::fit(&inputs);
+/// 
+/// assert_eq!(inputs.map(|input| scaler.transform(input)), [[0.0, 0.0], [0.5, 0.5], [1.0, 1.0]]);
+/// assert_eq!(scaler.inverse_transform([0.5, 0.5]), [20.0, 101_500.0]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MinMaxScaler<const N: usize> {
+    /// The smallest value of each feature, scaled to `0`. 
+    pub min: [Float; N],
+    /// The largest value of each feature, scaled to `1`. 
+    pub max: [Float; N]
+}
+
+impl <const N: usize> MinMaxScaler<N> {
+
+    /// Instantiates a scaler with known parameters, such as saved from one fitted before. 
+    /// 
+    /// # Parameters 
+    /// * `min` The smallest value of each feature, scaled to `0`. 
+    /// * `max` The largest value of each feature, scaled to `1`. 
+    pub const fn new(min: [Float; N], max: [Float; N]) -> MinMaxScaler<N> {
+        MinMaxScaler {
+            min,
+            max
+        }
+    }
+
+    /// Instantiates a scaler fitted to the smallest and largest value of each feature of the inputs, scaling 
+    /// nothing should there be none. 
+    pub fn fit(inputs: &[[Float; N]]) -> MinMaxScaler<N> {
+        if inputs.is_empty() {
+            return MinMaxScaler::new([0.0; N], [1.0; N]);
+        }
+        let mut scaler = MinMaxScaler::new([Float::INFINITY; N], [Float::NEG_INFINITY; N]);
+        for input in inputs {
+            for ((min, max), value) in scaler.min.iter_mut().zip(scaler.max.iter_mut()).zip(input) {
+                *min = min.min(*value);
+                *max = max.max(*value);
+            }
+        }
+        scaler
+    }
+
+    /// Scales an input, features that didn't vary when fitted being scaled to `0`. Values outside of those fitted 
+    /// to fall outside of `0` to `1`. 
+    pub fn transform(&self, input: [Float; N]) -> [Float; N] {
+        core::array::from_fn(|i| {
+            let range = self.max[i] - self.min[i];
+            if range == 0.0 { 0.0 } else { (input[i] - self.min[i]) / range }
+        })
+    }
+
+    /// Reverses [transform](MinMaxScaler::transform), giving a scaled input back in its original units. 
+    pub fn inverse_transform(&self, scaled: [Float; N]) -> [Float; N] {
+        core::array::from_fn(|i| self.min[i] + scaled[i] * (self.max[i] - self.min[i]))
+    }
+
+    /// Passes the parameters to a [Visitor] as a layer of its own named `"min max scaler"`, the sets named `"min"` 
+    /// and `"max"`, for exporting them as a network's are with [Layer::visit](crate::network::Layer::visit). 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, Float};
+    /// use mynn::network::{Layer, Visitor};
+    /// use mynn::preprocessing::MinMaxScaler;
+    /// 
+    /// // Collects every parameter in turn. 
+    /// struct Export(Vec<Float>);
+    /// 
+    /// impl Visitor for Export {
+    ///     fn params(&mut self, _name: &'static str, params: &[Float]) {
+    ///         self.0.extend_from_slice(params);
+    ///     }
+    /// }
+    /// 
+    /// let scaler = MinMaxScaler::fit(&[[15.0, 101_000.0], [25.0, 102_000.0]]);
+    /// let network = make_network!(2, 3, 1);
+    /// let mut export = Export(Vec::new());
+    /// scaler.visit(&mut export);
+    /// network.visit(&mut export);
+    /// 
+    /// assert_eq!(export.0[..4], [15.0, 101_000.0, 25.0, 102_000.0]);
+    /// assert_eq!(export.0.len(), 4 + 2 * 3 + 3 + 3 * 1 + 1);
+    /// ```
+    pub fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("min max scaler", N, N);
+        visitor.params("min", &self.min);
+        visitor.params("max", &self.max);
+        visitor.end();
+    }
+
+    /// Passes the parameters to a [VisitorMut] in the same order as [visit](MinMaxScaler::visit), for loading them 
+    /// as a network's are with [Layer::visit_mut](crate::network::Layer::visit_mut). 
+    pub fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("min max scaler", N, N);
+        visitor.params("min", &mut self.min);
+        visitor.params("max", &mut self.max);
+        visitor.end();
+    }
+}
+
+/// Standardises each feature of the inputs to a mean of `0` and a standard deviation of `1` by the mean and standard 
+/// deviation it was fitted to, so features in very different units, such as temperatures, pressures and counts, 
+/// weigh alike and don't saturate the activations. Unlike a [MinMaxScaler] a single outlier doesn't squash the rest. 
+/// 
+/// Outside of training the parameters are kept alongside the network's as for a [MinMaxScaler], exported with 
+/// [visit](StandardScaler::visit) and written back with [new](StandardScaler::new) or 
+/// [visit_mut](StandardScaler::visit_mut). 
+/// 
+/// # Type Parameters
+/// * `N` The number of features in each input. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::Float;
+/// use mynn::preprocessing::StandardScaler;
+/// 
+/// // Temperatures in °C and pressures in Pa. 
+/// let inputs = [[10.0, 101_000.0], [30.0, 101_000.0], [10.0, 102_000.0], [30.0, 102_000.0]];
+/// let scaler = StandardScaler::fit(&inputs);
+/// 
+/// // The square root is approximate with the `f32` feature. 
+/// let close = |a: [Float; 2], b: [Float; 2]| a.iter().zip(b).all(|(a, b)| (a - b).abs() <= 0.05 * b.abs().max(1.0));
+/// 
+/// assert_eq!(scaler.mean, [20.0, 101_500.0]);
+/// assert!(close(scaler.std_dev, [10.0, 500.0]));
+/// assert!(close(scaler.transform([30.0, 102_500.0]), [1.0, 2.0]));
+/// assert!(close(scaler.inverse_transform([0.0, 0.0]), [20.0, 101_500.0]));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StandardScaler<const N: usize> {
+    /// The mean of each feature, scaled to `0`. 
+    pub mean: [Float; N],
+    /// The standard deviation of each feature, scaled to `1`. 
+    pub std_dev: [Float; N]
+}
+
+impl <const N: usize> StandardScaler<N> {
+
+    /// Instantiates a scaler with known parameters, such as saved from one fitted before. 
+    /// 
+    /// # Parameters 
+    /// * `mean` The mean of each feature, scaled to `0`. 
+    /// * `std_dev` The standard deviation of each feature, scaled to `1`. 
+    pub const fn new(mean: [Float; N], std_dev: [Float; N]) -> StandardScaler<N> {
+        StandardScaler {
+            mean,
+            std_dev
+        }
+    }
+
+    /// Instantiates a scaler fitted to the mean and standard deviation of each feature of the inputs, scaling 
+    /// nothing should there be none. 
+    pub fn fit(inputs: &[[Float; N]]) -> StandardScaler<N> {
+        if inputs.is_empty() {
+            return StandardScaler::new([0.0; N], [1.0; N]);
+        }
+        let count = inputs.len() as Float;
+        let mut scaler = StandardScaler::new([0.0; N], [0.0; N]);
+        for input in inputs {
+            for (mean, value) in scaler.mean.iter_mut().zip(input) {
+                *mean += value / count;
+            }
+        }
+        // Summing the squared deviations from the mean found first avoids the cancellation of summing squares. 
+        for input in inputs {
+            for ((std_dev, mean), value) in scaler.std_dev.iter_mut().zip(scaler.mean).zip(input) {
+                let deviation = value - mean;
+                *std_dev += deviation * deviation / count;
+            }
+        }
+        scaler.std_dev = scaler.std_dev.map(sqrt);
+        scaler
+    }
+
+    /// Scales an input, features that didn't vary when fitted being scaled to `0`. 
+    pub fn transform(&self, input: [Float; N]) -> [Float; N] {
+        core::array::from_fn(|i| if self.std_dev[i] == 0.0 { 0.0 } else { (input[i] - self.mean[i]) / self.std_dev[i] })
+    }
+
+    /// Reverses [transform](StandardScaler::transform), giving a scaled input back in its original units. 
+    pub fn inverse_transform(&self, scaled: [Float; N]) -> [Float; N] {
+        core::array::from_fn(|i| self.mean[i] + scaled[i] * self.std_dev[i])
+    }
+
+    /// Passes the parameters to a [Visitor] as a layer of its own named `"standard scaler"`, the sets named `"mean"` 
+    /// and `"std_dev"`, as [MinMaxScaler::visit] does. 
+    pub fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.layer("standard scaler", N, N);
+        visitor.params("mean", &self.mean);
+        visitor.params("std_dev", &self.std_dev);
+        visitor.end();
+    }
+
+    /// Passes the parameters to a [VisitorMut] in the same order as [visit](StandardScaler::visit), as 
+    /// [MinMaxScaler::visit_mut] does. 
+    pub fn visit_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.layer("standard scaler", N, N);
+        visitor.params("mean", &mut self.mean);
+        visitor.params("std_dev", &mut self.std_dev);
+        visitor.end();
+    }
+}
+
+/// Encodes a class as a one-hot target, `1` at the index of the class and `0` at the others, every value being `0` 
+/// should the class be out of range. 
+/// 
+/// # Type Parameters
+/// * `C` The number of classes. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::preprocessing::{argmax_label, one_hot};
+/// 
+/// let labels = [0, 2, 1];
+/// let targets = labels.map(one_hot::<3>);
+/// 
+/// assert_eq!(targets, [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]]);
+/// assert_eq!(targets.map(|target| argmax_label(&target)), labels);
+/// ```
+pub fn one_hot<const C: usize>(label: usize) -> [Float; C] {
+    core::array::from_fn(|i| (i == label) as u8 as Float)
+}
+
+/// Decodes outputs or a one-hot target back into a class, the index of the largest value, the first should there be 
+/// a tie, the reverse of [one_hot]. 
+/// 
+/// # Type Parameters
+/// * `C` The number of classes. 
+pub fn argmax_label<const C: usize>(outputs: &[Float; C]) -> usize {
+    (0..C).fold(0, |best, i| if outputs[i] > outputs[best] { i } else { best })
+}
+
+/// The number of features [polynomial_features] expands `inputs` features into, every product of up to `degree` 
+/// of them, for sizing the first layer of the network. 
+pub const fn polynomial_count(inputs: usize, degree: usize) -> usize {
+    // The number of monomials of up to the degree, less the constant one, is the binomial (inputs + degree, degree) - 1. 
+    let mut count = 1;
+    let mut i = 1;
+    while i <= degree {
+        count = count * (inputs + i) / i;
+        i += 1;
+    }
+    count - 1
+}
+
+/// Expands an input into every product of up to `DEGREE` of its features, the features themselves followed by the 
+/// products of each higher degree, such as `x`, `y`, `x²`, `xy` then `y²`, so a small network fits curves and 
+/// interactions between features without extra neurons, often cheaper on microcontrollers. 
+/// 
+/// # Type Parameters
+/// * `DEGREE` The highest degree of the products, such as `2` or `3`. 
+/// * `IN` The number of features in the input. 
+/// * `OUT` The number of features expanded into, checked at compile time to be [polynomial_count] of `IN` and `DEGREE`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::network::Network;
+/// use mynn::preprocessing::{polynomial_count, polynomial_features};
+/// 
+/// const FEATURES: usize = polynomial_count(2, 2);
+/// 
+/// // Exclusive or, which a network without hidden layers can't learn from the inputs alone. 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]].map(polynomial_features::<2, 2, FEATURES>);
+/// let targets = [[0.0], [1.0], [1.0], [0.0]];
+/// let mut network = make_network!(FEATURES, 1);
+/// 
+/// network.train(1.0, inputs, targets, 5_000, &SIGMOID, &MSE);
+/// 
+/// assert_eq!(inputs[3], [1.0, 1.0, 1.0, 1.0, 1.0]);
+/// assert_eq!(inputs.map(|input| network.predict(input, &SIGMOID)[0].round()), [0.0, 1.0, 1.0, 0.0]);
+/// ```
+pub fn polynomial_features<const DEGREE: usize, const IN: usize, const OUT: usize>(input: [Float; IN]) -> [Float; OUT] {
+    const { assert!(OUT == polynomial_count(IN, DEGREE), "OUT must be polynomial_count(IN, DEGREE)") };
+    let mut features = [0.0; OUT];
+    // The index of the last input multiplied into each product, extended only by it or later inputs to avoid repeats. 
+    let mut last = [0; OUT];
+    for i in 0..IN.min(OUT) {
+        features[i] = input[i];
+        last[i] = i;
+    }
+    let (mut start, mut len) = (0, IN.min(OUT));
+    for _ in 1..DEGREE {
+        let end = len;
+        for term in start..end {
+            for (i, value) in input.iter().enumerate().skip(last[term]) {
+                features[len] = features[term] * value;
+                last[len] = i;
+                len += 1;
+            }
+        }
+        start = end;
+    }
+    features
+}
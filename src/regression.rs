@@ -0,0 +1,166 @@
+use super::{activations::{Identity, Sigmoid, IDENTITY, SIGMOID}, loss::{BINARY_CROSS_ENTROPY, MSE}};
+use super::network::{EndLayer, Network, ProcessLayer, TrainStats};
+use super::training::TrainOptions;
+use super::Float;
+
+
+/// Linear regression, a single layer predicting one value as a weighted sum of the inputs plus a bias, trained 
+/// against [MSE]. 
+/// 
+/// # Type Parameters
+/// * `IN` The number of inputs. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::regression::LinearRegressor;
+/// 
+/// let inputs = [[0.0, 1.0], [1.0, 0.0], [1.0, 1.0], [2.0, 1.0]];
+/// let targets = inputs.map(|[a, b]| 2.0 * a - b + 0.5);
+/// let mut model = LinearRegressor::new();
+/// 
+/// model.train(0.1, inputs, targets, 2_000);
+/// 
+/// assert!((model.coefficients()[0] - 2.0).abs() < 1e-3);
+/// assert!((model.intercept() - 0.5).abs() < 1e-3);
+/// assert!((model.predict([3.0, 2.0]) - 4.5).abs() < 1e-2);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct LinearRegressor<const IN: usize> {
+    /// The layer holding the coefficients and intercept. 
+    pub layer: ProcessLayer<1, IN, 1, EndLayer<1>, Identity>
+}
+
+impl <const IN: usize> LinearRegressor<IN> {
+
+    /// Instantiates a model with every coefficient and the intercept set to `0`. 
+    pub fn new() -> LinearRegressor<IN> {
+        LinearRegressor {
+            layer: ProcessLayer::new(EndLayer()).with_activation(IDENTITY)
+        }
+    }
+
+    /// Predicts the value for the data. 
+    pub fn predict(&mut self, data: [Float; IN]) -> Float {
+        self.layer.predict(data, &IDENTITY)[0]
+    }
+
+    /// Trains the model against [MSE], returning the statistics of the training run. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `inputs` Array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `epochs` Number of epochs. 
+    pub fn train<const DATA_S: usize>(&mut self, l_rate: Float, inputs: [[Float; IN]; DATA_S], targets: [Float; DATA_S], epochs: usize) -> TrainStats {
+        self.train_with(&inputs, &targets.map(|target| [target]), TrainOptions::new(l_rate, epochs))
+    }
+
+    /// Trains the model against [MSE] as [Network::train_with] does, with the options for the training run. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of targets, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `options` The options for the training run. 
+    pub fn train_with<const DATA_S: usize>(&mut self, inputs: &[[Float; IN]; DATA_S], targets: &[[Float; 1]; DATA_S], options: TrainOptions<IN, 1>) -> TrainStats {
+        self.layer.train_with(inputs, targets, &IDENTITY, &MSE, options)
+    }
+
+    /// The weight given to each input. 
+    pub fn coefficients(&self) -> [Float; IN] {
+        self.layer.weights.data[0]
+    }
+
+    /// The value predicted when every input is `0`. 
+    pub fn intercept(&self) -> Float {
+        self.layer.biases.data[0][0]
+    }
+}
+
+impl <const IN: usize> Default for LinearRegressor<IN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Logistic regression, a single layer predicting the probability of a binary class as the [SIGMOID] of a weighted 
+/// sum of the inputs plus a bias, trained against [BINARY_CROSS_ENTROPY]. 
+/// 
+/// # Type Parameters
+/// * `IN` The number of inputs. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::regression::LogisticClassifier;
+/// 
+/// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+/// let mut model = LogisticClassifier::new();
+/// 
+/// model.train(0.5, inputs, [false, false, false, true], 1_000);
+/// 
+/// assert!(model.classify([1.0, 1.0]));
+/// assert!(!model.classify([0.0, 1.0]));
+/// assert!(model.predict([0.0, 0.0]) < 0.05);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct LogisticClassifier<const IN: usize> {
+    /// The layer holding the coefficients and intercept. 
+    pub layer: ProcessLayer<1, IN, 1, EndLayer<1>, Sigmoid>
+}
+
+impl <const IN: usize> LogisticClassifier<IN> {
+
+    /// Instantiates a model with every coefficient and the intercept set to `0`, predicting `0.5` for any data. 
+    pub fn new() -> LogisticClassifier<IN> {
+        LogisticClassifier {
+            layer: ProcessLayer::new(EndLayer()).with_activation(SIGMOID)
+        }
+    }
+
+    /// Predicts the probability the data belongs to the class. 
+    pub fn predict(&mut self, data: [Float; IN]) -> Float {
+        self.layer.predict(data, &SIGMOID)[0]
+    }
+
+    /// Predicts whether the data belongs to the class, its probability being at least `0.5`. 
+    pub fn classify(&mut self, data: [Float; IN]) -> bool {
+        self.predict(data) >= 0.5
+    }
+
+    /// Trains the model against [BINARY_CROSS_ENTROPY], returning the statistics of the training run. 
+    /// 
+    /// # Parameters 
+    /// * `l_rate` The learning rate. 
+    /// * `inputs` Array of possible inputs, each index in this array must correspond with the same index in the `labels`. 
+    /// * `labels` Array of whether each input belongs to the class, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `epochs` Number of epochs. 
+    pub fn train<const DATA_S: usize>(&mut self, l_rate: Float, inputs: [[Float; IN]; DATA_S], labels: [bool; DATA_S], epochs: usize) -> TrainStats {
+        self.train_with(&inputs, &labels.map(|label| [label as u8 as Float]), TrainOptions::new(l_rate, epochs))
+    }
+
+    /// Trains the model against [BINARY_CROSS_ENTROPY] as [Network::train_with] does, with the options for the 
+    /// training run. 
+    /// 
+    /// # Parameters 
+    /// * `inputs` Reference to an array of possible inputs, each index in this array must correspond with the same index in the `targets`. 
+    /// * `targets` Reference to an array of target probabilities, each index in this array must correspond with the same index in the `inputs`. 
+    /// * `options` The options for the training run. 
+    pub fn train_with<const DATA_S: usize>(&mut self, inputs: &[[Float; IN]; DATA_S], targets: &[[Float; 1]; DATA_S], options: TrainOptions<IN, 1>) -> TrainStats {
+        self.layer.train_with(inputs, targets, &SIGMOID, &BINARY_CROSS_ENTROPY, options)
+    }
+
+    /// The weight given to each input, the change in the log-odds of the class per unit of the input. 
+    pub fn coefficients(&self) -> [Float; IN] {
+        self.layer.weights.data[0]
+    }
+
+    /// The log-odds of the class when every input is `0`. 
+    pub fn intercept(&self) -> Float {
+        self.layer.biases.data[0][0]
+    }
+}
+
+impl <const IN: usize> Default for LogisticClassifier<IN> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,418 @@
+use super::{activations::LayerActivation, loss::{Weighted, MSE, SOFTMAX_CROSS_ENTROPY}, matrix::Matrix, network::{Network, ParamSets}};
+use super::math::uniform;
+use super::optimizers::Sgd;
+use super::training::GradientStep;
+use super::Float;
+use fastrand::Rng;
+
+
+/// Policy gradient learning (REINFORCE), training a network to choose actions from the rewards they earn rather 
+/// than from labelled targets, such as a small controller learning on the device. 
+/// 
+/// The network gives the probability of taking each action, ending in a softmax such as a [SoftmaxLayer](crate::network::SoftmaxLayer) 
+/// or a [SOFTMAX](crate::activations::SOFTMAX) output layer. Each step of an episode an action is [sampled](Reinforce::sample) 
+/// and its [reward](Reinforce::reward) recorded, at the end of the episode the network is [updated](Reinforce::update) 
+/// to make each action more likely the more reward followed it than the running average. 
+/// 
+/// # Type Parameters
+/// * `CAPACITY` The most steps recorded in an episode, later steps are acted upon but not learnt from. 
+/// * `NEURONS` The number of values describing the state, the network's inputs. 
+/// * `ACTIONS` The number of actions to choose between, the network's outputs. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{SIGMOID, SOFTMAX};
+/// use mynn::network::Network;
+/// use mynn::reinforcement::Reinforce;
+/// 
+/// // Rewarded for taking the action matching the lit input.
+/// let mut network = make_network!(2, 4 => SIGMOID, 2 => SOFTMAX).with_seed(1);
+/// let mut policy = Reinforce::<8, 2, 2>::new(0.0).with_seed(2);
+/// 
+/// for episode in 0..500 {
+///     for step in 0..8 {
+///         let lit = (episode + step) % 2;
+///         let state = if lit == 0 { [1.0, 0.0] } else { [0.0, 1.0] };
+///         let action = policy.sample(&mut network, state, &SIGMOID);
+///         policy.reward(if action == lit { 1.0 } else { 0.0 });
+///     }
+///     policy.update(&mut network, 0.1, &SIGMOID);
+/// }
+/// 
+/// assert!(network.predict([1.0, 0.0], &SIGMOID)[0] > 0.9);
+/// assert!(network.predict([0.0, 1.0], &SIGMOID)[1] > 0.9);
+/// ```
+pub struct Reinforce<const CAPACITY: usize, const NEURONS: usize, const ACTIONS: usize> {
+    /// The state of each step recorded in the episode. 
+    pub states: [[Float; NEURONS]; CAPACITY],
+    /// The action taken each step recorded in the episode. 
+    pub actions: [usize; CAPACITY],
+    /// The reward earned each step recorded in the episode. 
+    pub rewards: [Float; CAPACITY],
+    /// The number of steps recorded in the episode. 
+    pub len: usize,
+    /// How much a reward counts towards the actions before it, per step back, from `0` to `1`. 
+    pub discount: Float,
+    /// The running average of the returns, subtracted from each return to lessen the variance of the updates. 
+    pub baseline: Float,
+    /// How quickly the baseline follows the returns, from `0` to `1`. 
+    pub baseline_rate: Float,
+    /// The generator the actions are sampled from. 
+    pub rng: Rng
+}
+
+impl <const CAPACITY: usize, const NEURONS: usize, const ACTIONS: usize> Reinforce<CAPACITY, NEURONS, ACTIONS> {
+
+    /// Instantiates an empty episode with the given discount, the baseline following the returns at a rate of 
+    /// `0.1`, with its generator seeded with `0`. 
+    /// 
+    /// # Parameters 
+    /// * `discount` How much a reward counts towards the actions before it, per step back, `0` crediting each action with its own reward alone. 
+    pub fn new(discount: Float) -> Reinforce<CAPACITY, NEURONS, ACTIONS> {
+        Reinforce {
+            states: [[0.0; NEURONS]; CAPACITY],
+            actions: [0; CAPACITY],
+            rewards: [0.0; CAPACITY],
+            len: 0,
+            discount,
+            baseline: 0.0,
+            baseline_rate: 0.1,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Consumes the episode, returning it with its generator seeded with `seed`. 
+    pub fn with_seed(mut self, seed: u64) -> Reinforce<CAPACITY, NEURONS, ACTIONS> {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
+    /// Samples an action from the probabilities the network gives for the state, recording the step. 
+    /// 
+    /// # Parameters 
+    /// * `network` The policy network. 
+    /// * `state` The current state. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    pub fn sample<N: Network<NEURONS, ACTIONS>, F: LayerActivation + ?Sized>(&mut self, network: &mut N, state: [Float; NEURONS], act: &F) -> usize {
+        let probabilities = network.predict(state, act);
+        let mut remaining = uniform(&mut self.rng) * probabilities.iter().sum::<Float>();
+        let mut action = ACTIONS.saturating_sub(1);
+        for (i, probability) in probabilities.iter().enumerate() {
+            remaining -= probability;
+            if remaining < 0.0 {
+                action = i;
+                break;
+            }
+        }
+        if self.len < CAPACITY {
+            self.states[self.len] = state;
+            self.actions[self.len] = action;
+            self.rewards[self.len] = 0.0;
+            self.len += 1;
+        }
+        action
+    }
+
+    /// Adds a reward to the step last recorded, does nothing before the first step. 
+    pub fn reward(&mut self, reward: Float) {
+        if let Some(last) = self.len.checked_sub(1) {
+            self.rewards[last] += reward;
+        }
+    }
+
+    /// Ends the episode, updating the network to make each action taken more likely the more the return following 
+    /// it beats the baseline, then clears the episode, returning the total reward earned. 
+    /// 
+    /// # Parameters 
+    /// * `network` The policy network the actions were sampled from. 
+    /// * `l_rate` The learning rate. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    pub fn update<N: Network<NEURONS, ACTIONS>, F: LayerActivation + ?Sized>(&mut self, network: &mut N, l_rate: Float, act: &F) -> Float {
+        let total = self.rewards[..self.len].iter().sum();
+        let mut returns = [0.0; CAPACITY];
+        let mut following = 0.0;
+        for step in (0..self.len).rev() {
+            following = self.rewards[step] + self.discount * following;
+            returns[step] = following;
+        }
+
+        let mut gradients = N::Snapshot::filled(0.0);
+        for step in 0..self.len {
+            let mut target = [0.0; ACTIONS];
+            target[self.actions[step]] = 1.0;
+            let loss = Weighted {
+                loss: &SOFTMAX_CROSS_ENTROPY,
+                weight: returns[step] - self.baseline
+            };
+            network.set_training(true);
+            let outputs = network.feed_forward(Matrix::from([self.states[step]]).transpose(), act);
+            network.set_training(false);
+            network.back_propagate(outputs, target, act, &loss, &mut gradients);
+        }
+        network.apply_gradients(&Sgd, &GradientStep::new(l_rate), &mut gradients, &mut ParamSets::filled([0.0; 2]));
+
+        for &following in returns[..self.len].iter() {
+            self.baseline += self.baseline_rate * (following - self.baseline);
+        }
+        self.clear();
+        total
+    }
+
+    /// Clears the episode without learning from it. 
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+/// A single step taken in an environment, as stored in a [ReplayBuffer]. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of values describing a state. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition<const NEURONS: usize> {
+    /// The state the action was taken in. 
+    pub state: [Float; NEURONS],
+    /// The action taken. 
+    pub action: usize,
+    /// The reward earned by the action. 
+    pub reward: Float,
+    /// The state the action led to. 
+    pub next_state: [Float; NEURONS],
+    /// Whether the action ended the episode, no reward following it. 
+    pub done: bool
+}
+
+impl <const NEURONS: usize> Transition<NEURONS> {
+    /// Instantiates a transition from the state, action, reward, the next state and whether the episode ended. 
+    pub const fn new(state: [Float; NEURONS], action: usize, reward: Float, next_state: [Float; NEURONS], done: bool) -> Transition<NEURONS> {
+        Transition {
+            state,
+            action,
+            reward,
+            next_state,
+            done
+        }
+    }
+}
+
+/// Fixed capacity ring buffer of past [Transition]s, sampled at random to train a [QLearning] network on 
+/// uncorrelated steps, once full each new transition replaces the oldest. 
+/// 
+/// # Type Parameters
+/// * `CAPACITY` The most transitions held. 
+/// * `NEURONS` The number of values describing a state. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::reinforcement::{ReplayBuffer, Transition};
+/// 
+/// let mut buffer = ReplayBuffer::<2, 1>::new();
+/// for reward in [1.0, 2.0, 3.0] {
+///     buffer.push(Transition::new([0.0], 0, reward, [0.0], false));
+/// }
+/// 
+/// assert_eq!(buffer.len(), 2);
+/// assert!(buffer.transitions.iter().all(|transition| transition.reward > 1.0));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ReplayBuffer<const CAPACITY: usize, const NEURONS: usize> {
+    /// The transitions held, only the first [len](ReplayBuffer::len) are valid before the buffer first fills. 
+    pub transitions: [Transition<NEURONS>; CAPACITY],
+    /// The number of transitions held. 
+    pub held: usize,
+    /// The index the next transition is written to. 
+    pub next: usize
+}
+
+impl <const CAPACITY: usize, const NEURONS: usize> ReplayBuffer<CAPACITY, NEURONS> {
+
+    /// Instantiates an empty buffer. 
+    pub const fn new() -> ReplayBuffer<CAPACITY, NEURONS> {
+        ReplayBuffer {
+            transitions: [Transition::new([0.0; NEURONS], 0, 0.0, [0.0; NEURONS], true); CAPACITY],
+            held: 0,
+            next: 0
+        }
+    }
+
+    /// Adds a transition, replacing the oldest once the buffer is full. 
+    pub fn push(&mut self, transition: Transition<NEURONS>) {
+        if CAPACITY == 0 {
+            return;
+        }
+        self.transitions[self.next] = transition;
+        self.next = (self.next + 1) % CAPACITY;
+        self.held = (self.held + 1).min(CAPACITY);
+    }
+
+    /// The number of transitions held. 
+    pub fn len(&self) -> usize {
+        self.held
+    }
+
+    /// Whether no transitions are held. 
+    pub fn is_empty(&self) -> bool {
+        self.held == 0
+    }
+
+    /// A transition picked at random, `None` should the buffer be empty. 
+    pub fn sample(&self, rng: &mut Rng) -> Option<&Transition<NEURONS>> {
+        if self.held == 0 {
+            return None;
+        }
+        self.transitions.get(rng.usize(..self.held))
+    }
+}
+
+impl <const CAPACITY: usize, const NEURONS: usize> Default for ReplayBuffer<CAPACITY, NEURONS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Deep Q-learning, training a network to estimate the return of taking each action in a state, the reward 
+/// expected from it on, learning from the temporal difference between its estimates for consecutive states. 
+/// 
+/// The estimates for the next states are made by a separate target network, [synced](QLearning::sync) from the 
+/// trained network every so often, keeping the targets from chasing the network's own updates. The network's output 
+/// layer should be unbounded, such as with an [IDENTITY](crate::activations::IDENTITY) activation. 
+/// 
+/// # Type Parameters
+/// * `NEURONS` The number of values describing the state, the network's inputs. 
+/// * `ACTIONS` The number of actions to choose between, the network's outputs. 
+/// * `N` The type of the network, must implement [Network]. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::{IDENTITY, TANH};
+/// use mynn::network::Network;
+/// use mynn::reinforcement::{QLearning, ReplayBuffer, Transition};
+/// 
+/// // A corridor of three cells, stepping right (action 1) off the last cell is rewarded. 
+/// let cell = |position: usize| core::array::from_fn::<_, 3, _>(|i| if i == position { 1.0 } else { 0.0 });
+/// let mut network = make_network!(3, 8 => TANH, 2 => IDENTITY).with_seed(1);
+/// let mut learner = QLearning::new(&network, make_network!(3, 8 => TANH, 2 => IDENTITY), 0.9).with_seed(2);
+/// let mut buffer = ReplayBuffer::<64, 3>::new();
+/// 
+/// let mut position = 0;
+/// for step in 0..2_000 {
+///     let action = learner.act(&mut network, cell(position), &TANH);
+///     let (next, reward, done) = match (action, position) {
+///         (1, 2) => (0, 1.0, true),
+///         (1, _) => (position + 1, 0.0, false),
+///         _ => (position.saturating_sub(1), 0.0, false)
+///     };
+///     buffer.push(Transition::new(cell(position), action, reward, cell(next), done));
+///     position = next;
+/// 
+///     learner.train_step(&mut network, &buffer, 8, 0.05, &TANH);
+///     if step % 50 == 0 {
+///         learner.sync(&network);
+///     }
+/// }
+/// 
+/// for position in 0..3 {
+///     let values = network.predict(cell(position), &TANH);
+///     assert!(values[1] > values[0]);
+/// }
+/// ```
+pub struct QLearning<const NEURONS: usize, const ACTIONS: usize, N: Network<NEURONS, ACTIONS>> {
+    /// The network estimating the returns of the next states. 
+    pub target: N,
+    /// How much the return of the next state counts towards the return of the current one, from `0` to `1`. 
+    pub discount: Float,
+    /// The chance of [acting](QLearning::act) at random rather than on the network's estimates, to explore. 
+    pub epsilon: Float,
+    /// The generator used to explore and to sample the replay buffer. 
+    pub rng: Rng
+}
+
+impl <const NEURONS: usize, const ACTIONS: usize, N: Network<NEURONS, ACTIONS>> QLearning<NEURONS, ACTIONS, N> {
+
+    /// Instantiates the learner, exploring a tenth of the time, with its generator seeded with `0`. 
+    /// 
+    /// # Parameters 
+    /// * `network` The network to be trained, its parameters are copied into `target`. 
+    /// * `target` A network of the same type to serve as the target network. 
+    /// * `discount` How much the return of the next state counts towards the return of the current one. 
+    pub fn new(network: &N, mut target: N, discount: Float) -> QLearning<NEURONS, ACTIONS, N> {
+        target.restore(&network.snapshot());
+        QLearning {
+            target,
+            discount,
+            epsilon: 0.1,
+            rng: Rng::with_seed(0)
+        }
+    }
+
+    /// Consumes the learner, returning it with its generator seeded with `seed`. 
+    pub fn with_seed(mut self, seed: u64) -> QLearning<NEURONS, ACTIONS, N> {
+        self.rng = Rng::with_seed(seed);
+        self
+    }
+
+    /// Consumes the learner, returning it acting at random with the chance `epsilon`. 
+    pub fn epsilon(mut self, epsilon: Float) -> QLearning<NEURONS, ACTIONS, N> {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Chooses an action for the state, at random with the chance [epsilon](QLearning::epsilon), otherwise the 
+    /// action the network estimates the highest return for. 
+    /// 
+    /// # Parameters 
+    /// * `network` The network being trained. 
+    /// * `state` The current state. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    pub fn act<F: LayerActivation + ?Sized>(&mut self, network: &mut N, state: [Float; NEURONS], act: &F) -> usize {
+        if ACTIONS > 0 && uniform(&mut self.rng) < self.epsilon {
+            return self.rng.usize(..ACTIONS);
+        }
+        network.classify(state, act)
+    }
+
+    /// Copies the parameters of the network being trained into the target network. 
+    pub fn sync(&mut self, network: &N) {
+        self.target.restore(&network.snapshot());
+    }
+
+    /// Trains the network upon a batch of transitions sampled from the buffer, moving its estimate for each action 
+    /// taken towards the reward plus the discounted best estimate of the target network for the next state, 
+    /// returning the average squared temporal difference error. 
+    /// 
+    /// # Parameters 
+    /// * `network` The network being trained. 
+    /// * `buffer` The transitions to sample from, nothing is trained while it's empty. 
+    /// * `batch` The number of transitions sampled, their gradients averaged into a single update. 
+    /// * `l_rate` The learning rate. 
+    /// * `act` The activation function, layers owning their own activation use theirs instead. 
+    pub fn train_step<F: LayerActivation + ?Sized, const CAPACITY: usize>(&mut self, network: &mut N, buffer: &ReplayBuffer<CAPACITY, NEURONS>, batch: usize, l_rate: Float, act: &F) -> Float {
+        if buffer.is_empty() || batch == 0 {
+            return 0.0;
+        }
+        let mut total = 0.0;
+        let mut gradients = N::Snapshot::filled(0.0);
+        for _ in 0..batch {
+            let Some(transition) = buffer.sample(&mut self.rng) else { break };
+            let mut expected = transition.reward;
+            if !transition.done {
+                let next = self.target.predict(transition.next_state, act);
+                expected += self.discount * next.iter().fold(Float::NEG_INFINITY, |max, &value| max.max(value));
+            }
+
+            network.set_training(true);
+            let outputs = network.feed_forward(Matrix::from([transition.state]).transpose(), act);
+            network.set_training(false);
+            let mut targets = outputs;
+            if let Some(target) = targets.get_mut(transition.action) {
+                total += (expected - *target) * (expected - *target);
+                *target = expected;
+            }
+            network.back_propagate(outputs, targets, act, &MSE, &mut gradients);
+        }
+        network.apply_gradients(&Sgd, &GradientStep { scale: 1.0 / batch as Float, ..GradientStep::new(l_rate) }, &mut gradients, &mut ParamSets::filled([0.0; 2]));
+        total / batch as Float
+    }
+}
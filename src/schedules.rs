@@ -0,0 +1,242 @@
+use super::math::{cos, PI};
+use super::Float;
+
+
+/// Generic behaviour for a learning rate schedule, giving the learning rate to use for each epoch of training. 
+/// 
+/// A plain [Float] is a schedule keeping the learning rate constant. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::schedules::{Schedule, StepDecay};
+/// use mynn::training::TrainOptions;
+/// use mynn::network::Network;
+/// 
+/// let schedule = StepDecay::new(0.5, 0.5, 100);
+/// assert_eq!(schedule.rate(0), 0.5);
+/// assert_eq!(schedule.rate(250), 0.125);
+/// 
+/// let mut network = make_network!(2, 3, 1);
+/// let options = TrainOptions::new(0.5, 300).schedule(&schedule);
+/// network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+/// ```
+pub trait Schedule {
+    /// The learning rate for an epoch. 
+    /// 
+    /// # Parameters 
+    /// * `epoch` The zero based index of the epoch. 
+    fn rate(&self, epoch: usize) -> Float;
+}
+
+impl Schedule for Float {
+    fn rate(&self, _epoch: usize) -> Float {
+        *self
+    }
+}
+
+/// Multiplies the learning rate by `factor` every `step` epochs. 
+#[derive(Clone, Copy)]
+pub struct StepDecay {
+    /// The learning rate for the first epochs. 
+    pub initial: Float,
+    /// The factor the learning rate is multiplied by each step, commonly `0.1` to `0.5`. 
+    pub factor: Float,
+    /// The number of epochs between each decay. 
+    pub step: usize
+}
+
+impl StepDecay {
+    /// Instantiates a step decay schedule from the initial learning rate, the decay factor and epochs per step. 
+    pub const fn new(initial: Float, factor: Float, step: usize) -> StepDecay {
+        StepDecay {
+            initial,
+            factor,
+            step
+        }
+    }
+}
+
+impl Schedule for StepDecay {
+    fn rate(&self, epoch: usize) -> Float {
+        let mut rate = self.initial;
+        for _ in 0..epoch / self.step.max(1) {
+            rate *= self.factor;
+        }
+        rate
+    }
+}
+
+/// Multiplies the learning rate by `decay` every epoch, `initial * decay^epoch`. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::Float;
+/// use mynn::schedules::{ExponentialDecay, Schedule};
+/// 
+/// let schedule = ExponentialDecay::new(1.0, 0.5);
+/// assert!((schedule.rate(3) - 0.125).abs() < Float::EPSILON);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ExponentialDecay {
+    /// The learning rate for the first epoch. 
+    pub initial: Float,
+    /// The factor the learning rate is multiplied by each epoch, commonly just under `1`. 
+    pub decay: Float
+}
+
+impl ExponentialDecay {
+    /// Instantiates an exponential decay schedule from the initial learning rate and the decay per epoch. 
+    pub const fn new(initial: Float, decay: Float) -> ExponentialDecay {
+        ExponentialDecay {
+            initial,
+            decay
+        }
+    }
+}
+
+impl Schedule for ExponentialDecay {
+    fn rate(&self, epoch: usize) -> Float {
+        let mut rate = self.initial;
+        for _ in 0..epoch {
+            rate *= self.decay;
+        }
+        rate
+    }
+}
+
+/// Anneals the learning rate from `max` down to `min` along half a cosine over `epochs`, holding at `min` after. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::schedules::{CosineAnnealing, Schedule};
+/// 
+/// let schedule = CosineAnnealing::new(1.0, 0.0, 100);
+/// assert_eq!(schedule.rate(0), 1.0);
+/// assert!((schedule.rate(50) - 0.5).abs() < 1e-9);
+/// assert_eq!(schedule.rate(200), 0.0);
+/// ```
+#[derive(Clone, Copy)]
+pub struct CosineAnnealing {
+    /// The learning rate at the start. 
+    pub max: Float,
+    /// The learning rate at the end. 
+    pub min: Float,
+    /// The number of epochs to anneal over. 
+    pub epochs: usize
+}
+
+impl CosineAnnealing {
+    /// Instantiates a cosine annealing schedule from the starting and ending learning rates and the epochs between. 
+    pub const fn new(max: Float, min: Float, epochs: usize) -> CosineAnnealing {
+        CosineAnnealing {
+            max,
+            min,
+            epochs
+        }
+    }
+}
+
+impl Schedule for CosineAnnealing {
+    fn rate(&self, epoch: usize) -> Float {
+        if epoch >= self.epochs {
+            return self.min;
+        }
+        let progress = epoch as Float / self.epochs as Float;
+        self.min + 0.5 * (self.max - self.min) * (1.0 + cos(PI * progress))
+    }
+}
+
+/// Cosine annealing with warm restarts (SGDR), annealing from `max` to `min` over `period` epochs then restarting 
+/// at `max`, each period lasting `multiplier` times longer than the last. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::schedules::{Schedule, WarmRestarts};
+/// 
+/// let schedule = WarmRestarts::new(1.0, 0.0, 10, 2);
+/// assert!((schedule.rate(5) - 0.5).abs() < 1e-9);
+/// assert_eq!(schedule.rate(10), 1.0);
+/// assert!((schedule.rate(20) - 0.5).abs() < 1e-9);
+/// assert_eq!(schedule.rate(30), 1.0);
+/// ```
+#[derive(Clone, Copy)]
+pub struct WarmRestarts {
+    /// The learning rate at the start of each period. 
+    pub max: Float,
+    /// The learning rate approached at the end of each period. 
+    pub min: Float,
+    /// The number of epochs in the first period. 
+    pub period: usize,
+    /// The factor each period's length is multiplied by after a restart, `1` keeps them equal. 
+    pub multiplier: usize
+}
+
+impl WarmRestarts {
+    /// Instantiates a warm restarts schedule from the learning rate range, the first period and the period multiplier. 
+    pub const fn new(max: Float, min: Float, period: usize, multiplier: usize) -> WarmRestarts {
+        WarmRestarts {
+            max,
+            min,
+            period,
+            multiplier
+        }
+    }
+}
+
+impl Schedule for WarmRestarts {
+    fn rate(&self, epoch: usize) -> Float {
+        let mut epoch = epoch;
+        let mut period = self.period.max(1);
+        while epoch >= period {
+            epoch -= period;
+            period *= self.multiplier.max(1);
+        }
+        CosineAnnealing::new(self.max, self.min, period).rate(epoch)
+    }
+}
+
+/// Triangular cyclical learning rate, rising linearly from `base` to `max` over `step_size` epochs then falling back 
+/// over the next `step_size`, repeating every `2 * step_size` epochs. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::schedules::{Cyclical, Schedule};
+/// 
+/// let schedule = Cyclical::new(0.1, 0.5, 10);
+/// assert_eq!(schedule.rate(0), 0.1);
+/// assert!((schedule.rate(10) - 0.5).abs() < 1e-9);
+/// assert!((schedule.rate(15) - 0.3).abs() < 1e-9);
+/// assert_eq!(schedule.rate(20), 0.1);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Cyclical {
+    /// The lowest learning rate, at the start of each cycle. 
+    pub base: Float,
+    /// The highest learning rate, at the middle of each cycle. 
+    pub max: Float,
+    /// The number of epochs to rise from `base` to `max`, half a cycle. 
+    pub step_size: usize
+}
+
+impl Cyclical {
+    /// Instantiates a triangular cyclical schedule from the lowest and highest learning rates and the epochs per half cycle. 
+    pub const fn new(base: Float, max: Float, step_size: usize) -> Cyclical {
+        Cyclical {
+            base,
+            max,
+            step_size
+        }
+    }
+}
+
+impl Schedule for Cyclical {
+    fn rate(&self, epoch: usize) -> Float {
+        let step_size = self.step_size.max(1);
+        let position = (epoch % (2 * step_size)) as Float / step_size as Float;
+        let distance = if position > 1.0 { position - 1.0 } else { 1.0 - position };
+        self.base + (self.max - self.base) * (1.0 - distance)
+    }
+}
@@ -0,0 +1,829 @@
+use super::math::{exp, ln, sqrt};
+use super::{optimizers::Optimizer, schedules::Schedule, Float};
+use core::fmt;
+use fastrand::Rng;
+
+
+/// Borrowed inputs and their corresponding targets, each index in one matching the same index in the other. 
+pub type Dataset<'a, const IN: usize, const OUT: usize> = (&'a [[Float; IN]], &'a [[Float; OUT]]);
+
+/// Transforms a copy of an input before it is trained on, drawing any randomness from the given generator. 
+pub type Augment<'a, const IN: usize> = dyn FnMut(&mut [Float; IN], &mut Rng) + 'a;
+
+/// Scores how difficult a sample is to learn from its input and target, lower scores being easier. 
+pub type Difficulty<'a, const IN: usize, const OUT: usize> = dyn FnMut(&[Float; IN], &[Float; OUT]) -> Float + 'a;
+
+/// Options controlling a training run, passed to [train_with](crate::network::Network::train_with). 
+/// 
+/// Built with [new](TrainOptions::new) then customised by chaining the builder methods. 
+/// 
+/// # Type Parameters 
+/// * `IN` The number of inputs to the network, inferred when passed to [train_with](crate::network::Network::train_with). 
+/// * `OUT` The number of outputs from the network, inferred when passed to [train_with](crate::network::Network::train_with). 
+/// 
+/// # Example 
+/// ```
+/// use mynn::make_network;
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::training::{LossHistory, TrainOptions};
+/// use mynn::network::Network;
+/// 
+/// let mut history = LossHistory::<8>::new();
+/// let mut network = make_network!(2, 3, 1);
+/// 
+/// let options = TrainOptions::new(0.5, 100).recorder(&mut history);
+/// network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+/// 
+/// assert_eq!(history.len(), 8);
+/// ```
+pub struct TrainOptions<'a, const IN: usize, const OUT: usize> {
+    /// The learning rate, is multiplied with the calculated gradient to allow for smaller/greater changes per learning revision. 
+    pub l_rate: Float,
+    /// Number of epochs (feeding forward/predicting and then back propagating/learning). 
+    pub epochs: usize,
+    /// Receives the average loss of each epoch. 
+    pub recorder: Option<&'a mut dyn LossRecorder>,
+    /// The update rule applied after each sample, [SGD](crate::optimizers::SGD) when `None`. 
+    pub optimizer: Option<&'a mut dyn Optimizer>,
+    /// The number of samples whose gradients are averaged into each update, `1` updates after every sample. 
+    /// A partial batch left at the end of an epoch is applied with the samples it has. 
+    pub accumulate: usize,
+    /// Strength of the L2 penalty on the weights, adding `weight_decay * weight` to each weight's gradient, `0` disables it. 
+    pub weight_decay: Float,
+    /// Limit each gradient to within `-clip_value..=clip_value` before it is applied. 
+    pub clip_value: Option<Float>,
+    /// Limit the norm of the gradients across the whole network, scaling them all down when it's exceeded. 
+    pub clip_norm: Option<Float>,
+    /// Gives the learning rate for each epoch in place of `l_rate`. 
+    pub schedule: Option<&'a dyn Schedule>,
+    /// The number of epochs after which stochastic weight averaging begins, see [average_from](TrainOptions::average_from). 
+    pub average_from: Option<usize>,
+    /// Limit on the norm of each neuron's incoming weights, see [max_norm](TrainOptions::max_norm). 
+    pub max_norm: Option<Float>,
+    /// Decaying Gaussian noise added to the gradients of each update, see [gradient_noise](TrainOptions::gradient_noise). 
+    pub gradient_noise: Option<GradientNoise>,
+    /// Weights scaling each sample's contribution to the gradients, by index into the dataset. 
+    pub sample_weights: Option<&'a [Float]>,
+    /// Weights for each class, scaling a sample's contribution by the weight of its class, see [class_weights](TrainOptions::class_weights). 
+    pub class_weights: Option<[Float; OUT]>,
+    /// Whether training stops with an error once a loss, weight or bias is NaN or infinite, set by 
+    /// [try_train_with](crate::network::Network::try_train_with). 
+    pub check_finite: bool,
+    /// Whether the order of the samples is shuffled with `rng` each epoch. 
+    pub shuffle: bool,
+    /// The random number generator used by stochastic training options. 
+    pub rng: Option<&'a mut Rng>,
+    /// Transform applied to a copy of each input before it is fed forward, see [augment](TrainOptions::augment). 
+    pub augment: Option<&'a mut Augment<'a, IN>>,
+    /// Scores how difficult each sample is, ordering them for a curriculum, see [curriculum](TrainOptions::curriculum). 
+    pub difficulty: Option<&'a mut Difficulty<'a, IN, OUT>>,
+    /// The number of epochs of each stage of the curriculum, see [curriculum](TrainOptions::curriculum). 
+    pub stages: &'a [usize],
+    /// Inputs and targets held out from training, their average loss measured after each epoch. 
+    pub validation: Option<Dataset<'a, IN, OUT>>,
+    /// The number of epochs without the validation loss improving after which training stops early. 
+    pub patience: Option<usize>,
+    /// Whether the weights with the lowest validation loss are restored once training finishes. 
+    pub restore_best: bool,
+    /// Cooperative yield point, invoked every `yield_every` samples or between epochs, see [yield_hook](TrainOptions::yield_hook). 
+    pub yield_hook: Option<&'a mut dyn FnMut()>,
+    /// The number of samples between each call to the yield hook, `0` calling it between epochs. 
+    pub yield_every: usize,
+    /// Invoked after each update and epoch, able to stop training early, see [TrainCallback]. 
+    pub callback: Option<&'a mut dyn TrainCallback>
+}
+
+impl<'a, const IN: usize, const OUT: usize> TrainOptions<'a, IN, OUT> {
+    /// Instantiates new options with the learning rate and number of epochs, all other options disabled. 
+    pub fn new(l_rate: Float, epochs: usize) -> TrainOptions<'a, IN, OUT> {
+        TrainOptions {
+            l_rate,
+            epochs,
+            recorder: None,
+            optimizer: None,
+            accumulate: 1,
+            weight_decay: 0.0,
+            clip_value: None,
+            clip_norm: None,
+            schedule: None,
+            average_from: None,
+            max_norm: None,
+            gradient_noise: None,
+            sample_weights: None,
+            class_weights: None,
+            check_finite: false,
+            shuffle: false,
+            rng: None,
+            augment: None,
+            difficulty: None,
+            stages: &[],
+            validation: None,
+            patience: None,
+            restore_best: false,
+            yield_hook: None,
+            yield_every: 0,
+            callback: None
+        }
+    }
+
+    /// Sets a recorder to receive the average loss of each epoch, such as a [LossHistory]. 
+    pub fn recorder(mut self, recorder: &'a mut dyn LossRecorder) -> TrainOptions<'a, IN, OUT> {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// Sets the optimizer used to update the weights and biases. 
+    pub fn optimizer(mut self, optimizer: &'a mut dyn Optimizer) -> TrainOptions<'a, IN, OUT> {
+        self.optimizer = Some(optimizer);
+        self
+    }
+
+    /// Averages the gradients over the whole dataset, applying them once per epoch. 
+    pub fn full_batch(mut self) -> TrainOptions<'a, IN, OUT> {
+        self.accumulate = usize::MAX;
+        self
+    }
+
+    /// Sums the gradients over `samples` samples, applying their average in a single update for smoother convergence. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// let targets = [[0.0], [0.0], [0.0], [1.0]];
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(1.0, 2_000).accumulate(2);
+    /// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// assert!(stats.average_loss < 0.01);
+    /// ```
+    pub fn accumulate(mut self, samples: usize) -> TrainOptions<'a, IN, OUT> {
+        self.accumulate = samples.max(1);
+        self
+    }
+
+    /// Trains in mini-batches of `BATCH` samples, applying a single averaged update per batch, checked to be 
+    /// non-zero at compile time. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// let targets = [[0.0], [1.0], [1.0], [1.0]];
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(2.0, 1_000).batch::<4>();
+    /// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// assert!(stats.average_loss < 0.01);
+    /// ```
+    pub fn batch<const BATCH: usize>(self) -> TrainOptions<'a, IN, OUT> {
+        const { assert!(BATCH > 0, "the batch size must be greater than zero") };
+        self.accumulate(BATCH)
+    }
+
+    /// Sets the strength of the L2 penalty applied to the weights each update, biases are not penalised. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 3, 1);
+    /// let options = TrainOptions::new(0.5, 100).weight_decay(1e-3);
+    /// 
+    /// network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+    /// ```
+    pub fn weight_decay(mut self, weight_decay: Float) -> TrainOptions<'a, IN, OUT> {
+        self.weight_decay = weight_decay;
+        self
+    }
+
+    /// Clips each gradient to within `-clip_value..=clip_value` before it is applied. 
+    pub fn clip_value(mut self, clip_value: Float) -> TrainOptions<'a, IN, OUT> {
+        self.clip_value = Some(clip_value);
+        self
+    }
+
+    /// Clips the gradients so their norm across the whole network is at most `clip_norm`, keeping their direction. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::RELU;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 3, 1);
+    /// let options = TrainOptions::new(5.0, 100).clip_norm(1.0).clip_value(0.5);
+    /// 
+    /// network.train_with(&[[10.0, 20.0]], &[[100.0]], &RELU, &MSE, options);
+    /// assert!(network.predict([10.0, 20.0], &RELU)[0].is_finite());
+    /// ```
+    pub fn clip_norm(mut self, clip_norm: Float) -> TrainOptions<'a, IN, OUT> {
+        self.clip_norm = Some(clip_norm);
+        self
+    }
+
+    /// Sets a schedule to give the learning rate for each epoch, replacing the constant `l_rate`. 
+    pub fn schedule(mut self, schedule: &'a dyn Schedule) -> TrainOptions<'a, IN, OUT> {
+        self.schedule = Some(schedule);
+        self
+    }
+
+    /// Enables stochastic weight averaging, the weights at the end of each epoch after the first `epochs` are 
+    /// averaged, the network being left with the average once training finishes. The running average is held by 
+    /// the training run alone, networks trained without it keep no copy. 
+    /// 
+    /// Combined with [restore_best](TrainOptions::restore_best) the average is kept only if its validation loss is 
+    /// lower than that of the best snapshot, otherwise the best snapshot is restored. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::schedules::Cyclical;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// let targets = [[0.0], [0.0], [0.0], [1.0]];
+    /// let schedule = Cyclical::new(0.2, 1.0, 5);
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(0.5, 2_000).schedule(&schedule).average_from(1_500);
+    /// network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// assert!(network.predict([1.0, 1.0], &SIGMOID)[0] > 0.8);
+    /// ```
+    pub fn average_from(mut self, epochs: usize) -> TrainOptions<'a, IN, OUT> {
+        self.average_from = Some(epochs);
+        self
+    }
+
+    /// Constrains the weights feeding each neuron, rescaling them after every update so their norm is at most `max_norm`. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::{make_network, Float};
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut network = make_network!(2, 3, 1);
+    /// network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, TrainOptions::new(0.5, 1_000).max_norm(2.0));
+    /// 
+    /// for row in network.weights.data {
+    ///     assert!(row.iter().map(|w| w * w).sum::<Float>() <= 4.0 + 1e-4);
+    /// }
+    /// ```
+    pub fn max_norm(mut self, max_norm: Float) -> TrainOptions<'a, IN, OUT> {
+        self.max_norm = Some(max_norm);
+        self
+    }
+
+    /// Adds decaying Gaussian noise to the gradients of each update, drawn from `rng`. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::fastrand::Rng;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::{GradientNoise, TrainOptions};
+    /// use mynn::network::Network;
+    /// 
+    /// let mut rng = Rng::with_seed(7);
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(0.5, 100).gradient_noise(GradientNoise::new(0.01, 0.55), &mut rng);
+    /// network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+    /// ```
+    pub fn gradient_noise(mut self, noise: GradientNoise, rng: &'a mut Rng) -> TrainOptions<'a, IN, OUT> {
+        self.gradient_noise = Some(noise);
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Augments each input before it is fed forward, `augment` is given a copy of the input to transform, such as 
+    /// adding noise or jitter, along with `rng`. The dataset itself is left untouched. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::fastrand::Rng;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::Float;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut jitter = |input: &mut [Float; 2], rng: &mut Rng| {
+    ///     for value in input.iter_mut() {
+    ///         *value += (rng.f64() as Float - 0.5) * 0.1;
+    ///     }
+    /// };
+    /// let mut rng = Rng::with_seed(3);
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(0.5, 100).augment(&mut jitter, &mut rng);
+    /// network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+    /// ```
+    pub fn augment(mut self, augment: &'a mut Augment<'a, IN>, rng: &'a mut Rng) -> TrainOptions<'a, IN, OUT> {
+        self.augment = Some(augment);
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Trains upon the easiest samples first, adding harder ones stage by stage, for models that only converge once 
+    /// they've learnt the clear cut cases. The samples are ordered by their `difficulty`, each stage `i` of 
+    /// `stages.len()` training for `stages[i]` epochs upon the easiest `(i + 1) / stages.len()` of them, then the 
+    /// whole dataset for any epochs left. Any shuffling is within the samples of the stage. Applies to 
+    /// [train_with](crate::network::Network::train_with). 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// use mynn::Float;
+    /// 
+    /// // Samples closer to the boundary between the classes are harder to tell apart. 
+    /// let inputs = [[0.5, 0.45], [0.9, 0.1], [0.4, 0.6], [0.1, 0.9], [0.6, 0.4], [0.45, 0.5]];
+    /// let targets = [[1.0], [1.0], [0.0], [0.0], [1.0], [0.0]];
+    /// let mut closeness = |input: &[Float; 2], _: &[Float; 1]| -(input[0] - input[1]).abs();
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(1.0, 2_000).curriculum(&mut closeness, &[200, 200, 200]);
+    /// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// assert_eq!(stats.epochs, 2_000);
+    /// assert!(network.predict([0.5, 0.45], &SIGMOID)[0] > 0.5);
+    /// assert!(network.predict([0.45, 0.5], &SIGMOID)[0] < 0.5);
+    /// ```
+    pub fn curriculum(mut self, difficulty: &'a mut Difficulty<'a, IN, OUT>, stages: &'a [usize]) -> TrainOptions<'a, IN, OUT> {
+        self.difficulty = Some(difficulty);
+        self.stages = stages;
+        self
+    }
+
+    /// Scales each sample's contribution to the gradients by its weight, such as by the confidence in its measurement, 
+    /// samples past the end of `weights` are given a weight of `1`. Applies to [train_with](crate::network::Network::train_with). 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// // Conflicting measurements, the second being far more trusted. 
+    /// let inputs = [[1.0, 0.0], [1.0, 0.0]];
+    /// let targets = [[0.0], [1.0]];
+    /// let weights = [0.1, 1.0];
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// network.train_with(&inputs, &targets, &SIGMOID, &MSE, TrainOptions::new(0.5, 1_000).sample_weights(&weights));
+    /// 
+    /// assert!(network.predict([1.0, 0.0], &SIGMOID)[0] > 0.8);
+    /// ```
+    pub fn sample_weights<const DATA_S: usize>(mut self, weights: &'a [Float; DATA_S]) -> TrainOptions<'a, IN, OUT> {
+        self.sample_weights = Some(weights);
+        self
+    }
+
+    /// Scales each sample's contribution to the gradients by the weight of its class, for one-hot targets with a 
+    /// weight per output, commonly inversely proportional to how often each class occurs in an imbalanced dataset. 
+    /// 
+    /// The weight of a sample is its targets multiplied with `weights` and summed, combining with any sample weights. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// // One defect among nine good parts, weighted up to balance the classes. 
+    /// let inputs = [[0.0, 0.1], [0.1, 0.0], [0.0, 0.0], [0.1, 0.1], [0.2, 0.1], [0.1, 0.2], [0.2, 0.0], [0.0, 0.2], [0.2, 0.2], [1.0, 1.0]];
+    /// let mut targets = [[1.0, 0.0]; 10];
+    /// targets[9] = [0.0, 1.0];
+    /// let mut network = make_network!(2, 3, 2);
+    /// 
+    /// let options = TrainOptions::new(0.5, 2_000).class_weights([1.0, 9.0]);
+    /// network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// let defect = network.predict([1.0, 1.0], &SIGMOID);
+    /// assert!(defect[1] > defect[0]);
+    /// ```
+    pub fn class_weights(mut self, weights: [Float; OUT]) -> TrainOptions<'a, IN, OUT> {
+        self.class_weights = Some(weights);
+        self
+    }
+
+    /// Sets the random number generator shared by the stochastic training options, a seeded generator making 
+    /// training runs reproducible. 
+    pub fn rng(mut self, rng: &'a mut Rng) -> TrainOptions<'a, IN, OUT> {
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Shuffles the order the samples are trained in each epoch using `rng`, replacing any generator already set 
+    /// as all stochastic options share one. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::fastrand::Rng;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+    /// let targets = [[0.0], [0.0], [0.0], [1.0]];
+    /// let mut rng = Rng::with_seed(42);
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, TrainOptions::new(0.5, 2_000).shuffle(&mut rng));
+    /// 
+    /// assert!(stats.average_loss < 0.01);
+    /// ```
+    pub fn shuffle(mut self, rng: &'a mut Rng) -> TrainOptions<'a, IN, OUT> {
+        self.shuffle = true;
+        self.rng = Some(rng);
+        self
+    }
+
+    /// Sets inputs and targets held out from training, measuring their average loss after each epoch into 
+    /// [TrainStats](crate::network::TrainStats). 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+    /// let targets = [[0.0], [1.0], [1.0]];
+    /// let validation_inputs = [[1.0, 1.0]];
+    /// let validation_targets = [[1.0]];
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(0.5, 10_000)
+    ///     .validation(&validation_inputs, &validation_targets)
+    ///     .early_stopping(50)
+    ///     .restore_best();
+    /// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// assert!(stats.validation_loss.is_some());
+    /// assert!(stats.best_validation_loss <= stats.validation_loss);
+    /// ```
+    pub fn validation(mut self, inputs: &'a [[Float; IN]], targets: &'a [[Float; OUT]]) -> TrainOptions<'a, IN, OUT> {
+        self.validation = Some((inputs, targets));
+        self
+    }
+
+    /// Stops training once the validation loss hasn't improved for `patience` epochs, requires [validation](TrainOptions::validation). 
+    pub fn early_stopping(mut self, patience: usize) -> TrainOptions<'a, IN, OUT> {
+        self.patience = Some(patience);
+        self
+    }
+
+    /// Keeps a [snapshot](crate::network::Network::snapshot) of the weights with the lowest validation loss, 
+    /// restoring it once training finishes, requires [validation](TrainOptions::validation). 
+    /// 
+    /// Combined with [average_from](TrainOptions::average_from) the snapshot is restored only if its validation loss 
+    /// is no higher than that of the average, otherwise the average is kept. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let inputs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0]];
+    /// let targets = [[0.0], [1.0], [1.0]];
+    /// let validation_inputs = [[1.0, 1.0]];
+    /// let validation_targets = [[1.0]];
+    /// let mut network = make_network!(2, 3, 1).with_seed(1);
+    /// 
+    /// let options = TrainOptions::new(0.5, 2_000)
+    ///     .validation(&validation_inputs, &validation_targets)
+    ///     .average_from(1_000)
+    ///     .restore_best();
+    /// let stats = network.train_with(&inputs, &targets, &SIGMOID, &MSE, options);
+    /// 
+    /// // Whichever of the average and the best snapshot is kept, it is at least as good as the best epoch. 
+    /// let kept_loss = network.evaluate(&validation_inputs, &validation_targets, &SIGMOID, &MSE);
+    /// assert!(kept_loss <= stats.best_validation_loss.unwrap());
+    /// ```
+    pub fn restore_best(mut self) -> TrainOptions<'a, IN, OUT> {
+        self.restore_best = true;
+        self
+    }
+
+    /// Sets a hook invoked every `samples` samples, or between epochs when `0`, letting firmware pet a watchdog or 
+    /// poll peripherals during a long training run. 
+    /// 
+    /// # Example 
+    /// ```
+    /// use mynn::make_network;
+    /// use mynn::activations::SIGMOID;
+    /// use mynn::loss::MSE;
+    /// use mynn::training::TrainOptions;
+    /// use mynn::network::Network;
+    /// 
+    /// let mut pets = 0;
+    /// let mut pet_watchdog = || pets += 1;
+    /// let mut network = make_network!(2, 3, 1);
+    /// 
+    /// let options = TrainOptions::new(0.5, 10).yield_hook(2, &mut pet_watchdog);
+    /// network.train_with(&[[0.0, 1.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]], &[[1.0], [1.0], [0.0], [0.0]], &SIGMOID, &MSE, options);
+    /// 
+    /// assert_eq!(pets, 20);
+    /// ```
+    pub fn yield_hook(mut self, samples: usize, hook: &'a mut dyn FnMut()) -> TrainOptions<'a, IN, OUT> {
+        self.yield_every = samples;
+        self.yield_hook = Some(hook);
+        self
+    }
+
+    /// Sets a callback invoked after each update and epoch, such as a closure receiving the epoch and its loss. 
+    pub fn callback(mut self, callback: &'a mut dyn TrainCallback) -> TrainOptions<'a, IN, OUT> {
+        self.callback = Some(callback);
+        self
+    }
+}
+
+/// Gaussian noise added to the gradients, its variance decaying as `eta / (1 + update)^gamma` over the updates made. 
+#[derive(Clone, Copy, Debug)]
+pub struct GradientNoise {
+    /// The initial variance of the noise, commonly `0.01` to `1`. 
+    pub eta: Float,
+    /// How quickly the variance decays, commonly `0.55`. 
+    pub gamma: Float
+}
+
+impl GradientNoise {
+    /// Instantiates gradient noise from the initial variance and its rate of decay. 
+    pub const fn new(eta: Float, gamma: Float) -> GradientNoise {
+        GradientNoise {
+            eta,
+            gamma
+        }
+    }
+
+    /// The standard deviation of the noise for an update. 
+    /// 
+    /// # Parameters 
+    /// * `update` The zero based index of the update. 
+    pub fn std_dev(&self, update: usize) -> Float {
+        sqrt(self.eta * exp(-self.gamma * ln(1.0 + update as Float)))
+    }
+}
+
+/// The settings for a single update, applying the gradients gathered during back propagation to each layer. 
+#[derive(Clone, Copy, Debug)]
+pub struct GradientStep {
+    /// The learning rate passed to the optimizer. 
+    pub l_rate: Float,
+    /// Multiplies the gathered gradients before they are applied, such as to average them over a batch. 
+    pub scale: Float,
+    /// Strength of the L2 penalty on the weights, see [TrainOptions::weight_decay]. 
+    pub weight_decay: Float,
+    /// Limit for each gradient, see [TrainOptions::clip_value]. 
+    pub clip_value: Option<Float>,
+    /// Limit for the norm of the gradients across the network, see [TrainOptions::clip_norm]. 
+    pub clip_norm: Option<Float>,
+    /// Limit for the norm of each neuron's incoming weights, see [TrainOptions::max_norm]. 
+    pub max_norm: Option<Float>
+}
+
+impl GradientStep {
+    /// Instantiates a plain update with the learning rate, the gradients unscaled and no other settings. 
+    pub const fn new(l_rate: Float) -> GradientStep {
+        GradientStep {
+            l_rate,
+            scale: 1.0,
+            weight_decay: 0.0,
+            clip_value: None,
+            clip_norm: None,
+            max_norm: None
+        }
+    }
+}
+
+/// The rule used for [Hebbian learning](crate::network::ProcessLayer::train_hebbian), each normalising the weights 
+/// so they don't grow without bound as plain Hebbian learning does. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HebbianRule {
+    /// Oja's rule, each neuron learning on its own to respond to the direction of greatest variance in its inputs, 
+    /// the first principal component, its weights tending to unit length. 
+    Oja,
+    /// Sanger's rule, the generalized Hebbian algorithm, each neuron learning from what the neurons before it leave 
+    /// unexplained, so together they learn the leading principal components in order. 
+    Sanger
+}
+
+/// Errors stopping a [checked](crate::network::Network::try_train_with) training run. 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrainError {
+    /// The loss of a prediction was NaN or infinite, commonly from the learning rate being too high. 
+    NonFiniteLoss {
+        /// The epoch number it occurred in, starting at `1`. 
+        epoch: usize
+    },
+    /// A weight or bias became NaN or infinite. 
+    NonFiniteParams {
+        /// The epoch number it occurred in, starting at `1`. 
+        epoch: usize
+    }
+}
+
+impl fmt::Display for TrainError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrainError::NonFiniteLoss { epoch } => write!(fmt, "loss became NaN or infinite in epoch {}", epoch),
+            TrainError::NonFiniteParams { epoch } => write!(fmt, "weights or biases became NaN or infinite in epoch {}", epoch)
+        }
+    }
+}
+
+impl core::error::Error for TrainError {}
+
+/// Generic behaviour for hooks invoked while training, for logging progress or stopping early. 
+/// 
+/// Closures taking the epoch number and its average loss implement this as an epoch end hook. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::{make_network, Float};
+/// use mynn::activations::SIGMOID;
+/// use mynn::loss::MSE;
+/// use mynn::training::TrainOptions;
+/// use mynn::network::Network;
+/// 
+/// let mut last_epoch = 0;
+/// let mut callback = |epoch: usize, loss: Float| {
+///     last_epoch = epoch;
+///     loss > 0.01
+/// };
+/// 
+/// let mut network = make_network!(2, 3, 1);
+/// let options = TrainOptions::new(0.5, 10_000).callback(&mut callback);
+/// let stats = network.train_with(&[[0.0, 1.0], [1.0, 1.0]], &[[1.0], [0.0]], &SIGMOID, &MSE, options);
+/// 
+/// assert!(stats.average_loss <= 0.01);
+/// assert_eq!(last_epoch, stats.epochs);
+/// ```
+pub trait TrainCallback {
+    /// Invoked after each update, returning `false` stops training. 
+    /// 
+    /// # Parameters 
+    /// * `epoch` The epoch number, starting at `1`. 
+    /// * `batch` The number of updates made so far in the run, starting at `1`. 
+    /// * `loss` The average loss over the samples of the update. 
+    fn on_batch_end(&mut self, _epoch: usize, _batch: usize, _loss: Float) -> bool {
+        true
+    }
+
+    /// Invoked after each epoch, returning `false` stops training. 
+    /// 
+    /// # Parameters 
+    /// * `epoch` The epoch number, starting at `1`. 
+    /// * `loss` The average loss over the samples of the epoch. 
+    fn on_epoch_end(&mut self, _epoch: usize, _loss: Float) -> bool {
+        true
+    }
+}
+
+impl<F: FnMut(usize, Float) -> bool> TrainCallback for F {
+    fn on_epoch_end(&mut self, epoch: usize, loss: Float) -> bool {
+        self(epoch, loss)
+    }
+}
+
+/// Generic behaviour for receiving the loss of each epoch while training. 
+pub trait LossRecorder {
+    /// Records the average loss of an epoch. 
+    /// 
+    /// # Parameters 
+    /// * `epoch` The epoch number, starting at `1`. 
+    /// * `loss` The average loss over the samples of the epoch. 
+    fn record(&mut self, epoch: usize, loss: Float);
+}
+
+/// Fixed capacity ring buffer of per-epoch losses, keeping the most recent `N` without allocating. 
+/// 
+/// # Example 
+/// ```
+/// use mynn::training::{LossHistory, LossRecorder};
+/// 
+/// let mut history = LossHistory::<3>::new();
+/// for (epoch, loss) in [0.4, 0.3, 0.2, 0.1].into_iter().enumerate() {
+///     history.record(epoch + 1, loss);
+/// }
+/// 
+/// assert_eq!(history.len(), 3);
+/// assert_eq!(history.latest(), Some(0.1));
+/// assert!(history.iter().eq([0.3, 0.2, 0.1]));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LossHistory<const N: usize> {
+    losses: [Float; N],
+    start: usize,
+    len: usize
+}
+
+impl<const N: usize> LossHistory<N> {
+    /// Instantiates a new empty history. 
+    pub const fn new() -> LossHistory<N> {
+        LossHistory {
+            losses: [0.0; N],
+            start: 0,
+            len: 0
+        }
+    }
+
+    /// Appends a loss, overwriting the oldest once `N` losses are held. 
+    pub fn push(&mut self, loss: Float) {
+        if N == 0 {
+            return;
+        }
+        if self.len < N {
+            self.losses[(self.start + self.len) % N] = loss;
+            self.len += 1;
+        } else {
+            self.losses[self.start] = loss;
+            self.start = (self.start + 1) % N;
+        }
+    }
+
+    /// The number of losses held, at most `N`. 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no losses have been recorded. 
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The most recently recorded loss. 
+    pub fn latest(&self) -> Option<Float> {
+        if self.len == 0 {
+            return None;
+        }
+        Some(self.losses[(self.start + self.len - 1) % N])
+    }
+
+    /// Iterates the held losses from oldest to newest. 
+    pub fn iter(&self) -> impl Iterator<Item = Float> + '_ {
+        (0..self.len).map(|i| self.losses[(self.start + i) % N])
+    }
+
+    /// Removes all held losses. 
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+}
+
+impl<const N: usize> Default for LossHistory<N> {
+    fn default() -> LossHistory<N> {
+        LossHistory::new()
+    }
+}
+
+impl<const N: usize> LossRecorder for LossHistory<N> {
+    fn record(&mut self, _epoch: usize, loss: Float) {
+        self.push(loss);
+    }
+}